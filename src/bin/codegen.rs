@@ -0,0 +1,139 @@
+//! Emits TypeScript account interfaces and account-meta builders from beethoven's layouts.
+//!
+//! ```text
+//! cargo run --features codegen,kamino,jupiter --bin beethoven-codegen > beethoven.ts
+//! ```
+
+fn main() {
+    let mut out = String::new();
+    out.push_str("// Generated by beethoven-codegen. Do not edit.\n\n");
+    out.push_str("import type { AccountMeta, PublicKey } from \"@solana/web3.js\";\n");
+
+    // Layouts only exist once a protocol feature is enabled.
+    #[cfg(any(
+        feature = "drift",
+        feature = "jito",
+        feature = "jupiter",
+        feature = "kamino",
+        feature = "marinade",
+        feature = "solayer",
+        feature = "stake_pool"
+    ))]
+    for layout in beethoven::LAYOUTS {
+        typescript::emit_layout(&mut out, layout).expect("writing to a String cannot fail");
+    }
+
+    print!("{out}");
+}
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+mod typescript {
+    use {beethoven::NamedLayout, std::fmt::Write};
+
+    pub fn emit_layout(out: &mut String, layout: &NamedLayout) -> std::fmt::Result {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "/** Accounts expected by `{}::try_from` ({}), in order. */",
+            layout.name, layout.protocol
+        )?;
+        writeln!(out, "export interface {} {{", layout.name)?;
+        for spec in layout.accounts {
+            writeln!(out, "  {}: PublicKey;", camel_case(spec.name))?;
+        }
+        writeln!(out, "}}")?;
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "export function {}Metas(accounts: {}, remaining: AccountMeta[] = []): AccountMeta[] {{",
+            lower_first(layout.name),
+            layout.name
+        )?;
+        writeln!(out, "  return [")?;
+        for spec in layout.accounts {
+            writeln!(
+                out,
+                "    {{ pubkey: accounts.{}, isSigner: {}, isWritable: {} }},",
+                camel_case(spec.name),
+                spec.signer,
+                spec.writable
+            )?;
+        }
+        writeln!(out, "    ...remaining,")?;
+        writeln!(out, "  ];")?;
+        writeln!(out, "}}")
+    }
+
+    fn camel_case(name: &str) -> String {
+        let mut out = String::with_capacity(name.len());
+        let mut upper = false;
+        for c in name.chars() {
+            if c == '_' {
+                upper = true;
+            } else if upper {
+                out.push(c.to_ascii_uppercase());
+                upper = false;
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    fn lower_first(name: &str) -> String {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use {super::*, beethoven::AccountSpec};
+
+        const ACCOUNTS: &[AccountSpec] = &[
+            AccountSpec::readonly("program"),
+            AccountSpec::writable_signer("user_owner"),
+        ];
+
+        #[test]
+        fn converts_field_names() {
+            assert_eq!(camel_case("user_source_liquidity"), "userSourceLiquidity");
+            assert_eq!(camel_case("reserve"), "reserve");
+            assert_eq!(
+                lower_first("KaminoDepositAccounts"),
+                "kaminoDepositAccounts"
+            );
+            assert_eq!(lower_first(""), "");
+        }
+
+        #[test]
+        fn emits_an_interface_and_a_metas_builder() {
+            let layout = NamedLayout {
+                protocol: beethoven::programs::Protocol::ALL[0],
+                name: "TestAccounts",
+                accounts: ACCOUNTS,
+            };
+            let mut out = String::new();
+            emit_layout(&mut out, &layout).unwrap();
+
+            assert!(out.contains(
+                "export interface TestAccounts {\n  program: PublicKey;\n  userOwner: PublicKey;\n}"
+            ));
+            assert!(out.contains("export function testAccountsMetas(accounts: TestAccounts, remaining: AccountMeta[] = []): AccountMeta[] {"));
+            assert!(
+                out.contains("{ pubkey: accounts.userOwner, isSigner: true, isWritable: true },")
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{client::Pubkey, programs::jupiter::JupiterLendingState};
+
+/// Vault-specific accounts of a Jupiter Earn deposit, resolved from a `Lending` account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JupiterVault {
+    /// Vault id, distinguishing vaults of the same asset
+    pub lending_id: u16,
+    /// `lending` account
+    pub lending: Pubkey,
+    /// `f_token_mint` account
+    pub f_token_mint: Pubkey,
+    /// `supply_token_reserves_liquidity` account
+    pub supply_token_reserves_liquidity: Pubkey,
+    /// `lending_supply_position_on_liquidity` account
+    pub lending_supply_position_on_liquidity: Pubkey,
+    /// `rewards_rate_model` account
+    pub rewards_rate_model: Pubkey,
+}
+
+/// Picks the Jupiter Earn vault of `mint` among fetched `Lending` accounts.
+///
+/// # Arguments
+/// * `lendings` - `(address, data)` of Jupiter Earn `Lending` accounts (e.g. from
+///   `getProgramAccounts`); other accounts are ignored
+/// * `mint` - Underlying token mint to deposit
+///
+/// # Returns
+/// * `Some(JupiterVault)` - The vault of `mint` with the lowest `lending_id`
+/// * `None` - No vault accepts `mint`
+pub fn select_jupiter_vault(lendings: &[(Pubkey, &[u8])], mint: &Pubkey) -> Option<JupiterVault> {
+    lendings
+        .iter()
+        .filter_map(|(address, data)| {
+            let state = JupiterLendingState::from_bytes(data).ok()?;
+            (state.mint.as_array() == mint).then(|| JupiterVault {
+                lending_id: state.lending_id,
+                lending: *address,
+                f_token_mint: state.f_token_mint.to_bytes(),
+                supply_token_reserves_liquidity: state.token_reserves_liquidity.to_bytes(),
+                lending_supply_position_on_liquidity: state.supply_position_on_liquidity.to_bytes(),
+                rewards_rate_model: state.rewards_rate_model.to_bytes(),
+            })
+        })
+        .min_by_key(|vault| vault.lending_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{programs::jupiter::tests::lending_data_of, test_utils::address},
+    };
+
+    #[test]
+    fn picks_the_lowest_vault_id_of_the_mint() {
+        let (usdc, usdt) = (address(1), address(2));
+        let lendings = [
+            lending_data_of(&usdc, 3),
+            lending_data_of(&usdt, 0),
+            lending_data_of(&usdc, 1),
+            std::vec![0; 200],
+        ];
+        let accounts: std::vec::Vec<(Pubkey, &[u8])> = lendings
+            .iter()
+            .enumerate()
+            .map(|(index, data)| ([index as u8; 32], &data[..]))
+            .collect();
+
+        let vault = select_jupiter_vault(&accounts, usdc.as_array()).unwrap();
+        assert_eq!((vault.lending_id, vault.lending), (1, [2; 32]));
+        assert_eq!(select_jupiter_vault(&accounts, &[3; 32]), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! Off-chain helpers building the account lists beethoven's parsers expect.
+//!
+//! Only depends on `core` and `alloc`: no pinocchio account types and no syscalls, so it compiles
+//! for `wasm32-unknown-unknown` and can back browser SDKs. Orderings come from the same
+//! `AccountSpec` tables the protocol modules expose, so clients never drift from `try_from`.
+//!
+//! ```ignore
+//! let metas = account_metas(KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS, &keys)?;
+//! ```
+
+use {crate::programs::AccountSpec, alloc::vec::Vec};
+
+#[cfg(feature = "jupiter")]
+mod jupiter;
+#[cfg(feature = "jupiter")]
+pub use jupiter::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+mod simulate;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use simulate::*;
+
+mod squads;
+pub use squads::*;
+
+mod transaction;
+pub use transaction::*;
+
+/// A 32-byte account address.
+pub type Pubkey = [u8; 32];
+
+/// Account entry of a transaction instruction, matching `solana_instruction::AccountMeta`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    /// Builds the meta for `pubkey` with the flags required by `spec`.
+    pub const fn from_spec(spec: &AccountSpec, pubkey: Pubkey) -> Self {
+        Self {
+            pubkey,
+            is_signer: spec.signer,
+            is_writable: spec.writable,
+        }
+    }
+}
+
+/// Errors returned by the client builders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientError {
+    /// Fewer keys than the layout requires were provided
+    NotEnoughAccounts { expected: usize, provided: usize },
+    /// An account other than the executing vault must sign
+    UnsupportedSigner { index: usize },
+    /// The account data passed for the named layout account is missing
+    MissingAccount { name: &'static str },
+    /// Account data does not hold the expected protocol state
+    InvalidAccountData,
+    /// The simulated amounts overflow
+    ArithmeticOverflow,
+}
+
+/// Builds the account metas for `layout` from `keys`, given in the same order.
+///
+/// # Arguments
+/// * `layout` - Protocol account layout (e.g. `JUPITER_EARN_DEPOSIT_ACCOUNTS`)
+/// * `keys` - Account addresses in layout order, optionally followed by trailing accounts
+///
+/// # Returns
+/// * `Ok(Vec<AccountMeta>)` - Metas in parse order; trailing keys are appended read-only
+/// * `Err(ClientError::NotEnoughAccounts)` - Fewer keys than the layout requires
+///
+/// # Notes
+/// * Signer flags assume the owner signs the transaction. When a PDA owns the position and
+///   the integrating program signs for it, clear `is_signer` on that meta.
+pub fn account_metas(
+    layout: &[AccountSpec],
+    keys: &[Pubkey],
+) -> Result<Vec<AccountMeta>, ClientError> {
+    if keys.len() < layout.len() {
+        return Err(ClientError::NotEnoughAccounts {
+            expected: layout.len(),
+            provided: keys.len(),
+        });
+    }
+
+    let (fixed, trailing) = keys.split_at(layout.len());
+    let mut metas = Vec::with_capacity(keys.len());
+    metas.extend(
+        layout
+            .iter()
+            .zip(fixed)
+            .map(|(spec, key)| AccountMeta::from_spec(spec, *key)),
+    );
+    metas.extend(trailing.iter().map(|key| AccountMeta {
+        pubkey: *key,
+        is_signer: false,
+        is_writable: false,
+    }));
+    Ok(metas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: &[AccountSpec] = &[
+        AccountSpec::readonly("program"),
+        AccountSpec::writable_signer("owner"),
+        AccountSpec::writable("vault"),
+        AccountSpec::readonly_signer("base"),
+    ];
+
+    #[test]
+    fn account_metas_follow_the_layout_flags() {
+        let metas = account_metas(LAYOUT, &[[0; 32], [1; 32], [2; 32], [3; 32], [4; 32]]).unwrap();
+
+        let flags: Vec<(Pubkey, bool, bool)> = metas
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                ([0; 32], false, false),
+                ([1; 32], true, true),
+                ([2; 32], false, true),
+                ([3; 32], true, false),
+                // Trailing accounts are appended read-only.
+                ([4; 32], false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn account_metas_require_the_whole_layout() {
+        assert_eq!(
+            account_metas(LAYOUT, &[[0; 32], [1; 32]]),
+            Err(ClientError::NotEnoughAccounts {
+                expected: 4,
+                provided: 2,
+            })
+        );
+    }
+}
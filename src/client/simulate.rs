@@ -0,0 +1,165 @@
+#[cfg(any(feature = "drift", feature = "solayer"))]
+use crate::util::bytes::{TOKEN_ACCOUNT_AMOUNT_OFFSET, read_u64};
+use {
+    crate::{
+        client::ClientError,
+        programs::{AccountSpec, Protocol},
+    },
+    pinocchio::error::ProgramError,
+};
+
+/// Returns the data of the account named `name` in `layout`.
+fn data_of<'a>(
+    layout: &[AccountSpec],
+    account_datas: &[&'a [u8]],
+    name: &'static str,
+) -> Result<&'a [u8], ClientError> {
+    layout
+        .iter()
+        .position(|spec| spec.name == name)
+        .and_then(|index| account_datas.get(index).copied())
+        .ok_or(ClientError::MissingAccount { name })
+}
+
+impl From<ProgramError> for ClientError {
+    /// Maps the errors of the protocol state parsers and share math.
+    fn from(error: ProgramError) -> Self {
+        match error {
+            ProgramError::ArithmeticOverflow => ClientError::ArithmeticOverflow,
+            _ => ClientError::InvalidAccountData,
+        }
+    }
+}
+
+/// Runs the on-chain `preview_deposit` math against fetched account data.
+///
+/// Off-chain allocators and bots can plan rebalances with the exact logic the program uses,
+/// without an RPC client inside the crate: fetch the accounts, pass their data here.
+///
+/// # Arguments
+/// * `protocol` - Protocol the accounts belong to
+/// * `account_datas` - Data of the deposit accounts, in the protocol's layout order (e.g.
+///   `KAMINO_DEPOSIT_ACCOUNTS`); accounts the preview does not read may be empty
+/// * `amount` - Amount of tokens to deposit
+///
+/// # Returns
+/// * `Ok(u64)` - Expected shares, as returned by `Preview::preview_deposit`
+/// * `Err(ClientError::MissingAccount)` - An account the preview reads is missing
+/// * `Err(ClientError::InvalidAccountData)` - Account data could not be parsed, or `protocol`
+///   takes no deposits
+/// * `Err(ClientError::ArithmeticOverflow)` - The share math overflows
+///
+/// # Notes
+/// * Account owners are not known off-chain and are not checked.
+/// * For Kamino, a `Reserve` at index 2 selects the liquidity-only layout, as on-chain.
+pub fn simulate_deposit(
+    protocol: Protocol,
+    account_datas: &[&[u8]],
+    amount: u64,
+) -> Result<u64, ClientError> {
+    let shares = match protocol {
+        #[cfg(feature = "kamino")]
+        Protocol::Kamino => {
+            let layout = match account_datas.get(2) {
+                Some(data) if crate::programs::kamino::KaminoReserveState::is_reserve(data) => {
+                    crate::programs::kamino::KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS
+                }
+                _ => crate::programs::kamino::KAMINO_DEPOSIT_ACCOUNTS,
+            };
+            let reserve = data_of(layout, account_datas, "reserve")?;
+            crate::programs::kamino::KaminoReserveState::from_bytes(reserve)?
+                .liquidity_to_collateral(amount)
+        }
+
+        #[cfg(feature = "jupiter")]
+        Protocol::Jupiter => {
+            let layout = crate::programs::jupiter::JUPITER_EARN_DEPOSIT_ACCOUNTS;
+            let lending = data_of(layout, account_datas, "lending")?;
+            crate::programs::jupiter::JupiterLendingState::from_bytes(lending)?
+                .assets_to_shares(amount)
+        }
+
+        #[cfg(feature = "drift")]
+        Protocol::Drift => {
+            let layout = crate::programs::drift::DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS;
+            let vault = data_of(layout, account_datas, "insurance_fund_vault")?;
+            let spot_market = data_of(layout, account_datas, "spot_market")?;
+            let vault_amount = read_u64(vault, TOKEN_ACCOUNT_AMOUNT_OFFSET)?;
+            crate::programs::drift::DriftSpotMarketState::from_bytes(spot_market)?
+                .amount_to_if_shares(amount, vault_amount)
+        }
+
+        #[cfg(feature = "solayer")]
+        Protocol::Solayer => {
+            let layout = crate::programs::solayer::SOLAYER_RESTAKE_ACCOUNTS;
+            crate::programs::solayer::SolayerRestakingPoolState::from_bytes(data_of(
+                layout,
+                account_datas,
+                "pool",
+            )?)?;
+            let vault = data_of(layout, account_datas, "vault")?;
+            let rst_mint = data_of(layout, account_datas, "rst_mint")?;
+            crate::programs::solayer::lst_to_rst(
+                amount,
+                read_u64(vault, TOKEN_ACCOUNT_AMOUNT_OFFSET)?,
+                read_u64(rst_mint, crate::util::bytes::MINT_SUPPLY_OFFSET)?,
+            )
+        }
+
+        #[cfg(feature = "marinade")]
+        Protocol::Marinade => {
+            let layout = crate::programs::marinade::MARINADE_DEPOSIT_ACCOUNTS;
+            let state = data_of(layout, account_datas, "state")?;
+            crate::programs::marinade::MarinadeState::from_bytes(state)?.lamports_to_msol(amount)
+        }
+
+        #[cfg(feature = "stake_pool")]
+        Protocol::StakePool => {
+            let layout = crate::programs::stake_pool::SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS;
+            let stake_pool = data_of(layout, account_datas, "stake_pool")?;
+            crate::programs::stake_pool::SplStakePoolState::from_bytes(stake_pool)?
+                .lamports_to_pool_tokens(amount)
+        }
+
+        // Jito vaults only take part in two-phase withdrawals.
+        #[cfg(feature = "jito")]
+        Protocol::Jito => Err(ProgramError::InvalidAccountData),
+    };
+    shares.map_err(ClientError::from)
+}
+
+#[cfg(all(test, feature = "jupiter"))]
+mod tests {
+    use {super::*, crate::programs::jupiter::JUPITER_EARN_DEPOSIT_ACCOUNTS, std::vec};
+
+    #[test]
+    fn reports_the_missing_account_by_name() {
+        assert_eq!(
+            simulate_deposit(Protocol::Jupiter, &[&[][..]; 6], 1),
+            Err(ClientError::MissingAccount { name: "lending" })
+        );
+    }
+
+    #[test]
+    fn rejects_unparseable_state() {
+        let datas = vec![&[][..]; JUPITER_EARN_DEPOSIT_ACCOUNTS.len()];
+        assert_eq!(
+            simulate_deposit(Protocol::Jupiter, &datas, 1),
+            Err(ClientError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn matches_the_on_chain_preview() {
+        // `Lending` discriminator, and `token_exchange_price` at byte 115
+        let mut lending = vec![0u8; 200];
+        lending[..8].copy_from_slice(&[135, 199, 82, 16, 249, 131, 182, 241]);
+        lending[115..123].copy_from_slice(&1_043_512_345_678u64.to_le_bytes());
+        let mut datas = vec![&[][..]; JUPITER_EARN_DEPOSIT_ACCOUNTS.len()];
+        datas[6] = &lending;
+        assert_eq!(
+            simulate_deposit(Protocol::Jupiter, &datas, 1_000_000_000),
+            Ok(958_302_030)
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use {
+    crate::{
+        client::{ClientError, Instruction, Pubkey, account_metas},
+        programs::AccountSpec,
+    },
+    alloc::vec::Vec,
+};
+
+/// Squads v4 multisig program.
+pub const SQUADS_V4_PROGRAM_ID: Pubkey = [
+    6, 129, 196, 206, 71, 226, 35, 104, 184, 177, 85, 94, 200, 135, 175, 9, 46, 252, 126, 251, 182,
+    108, 163, 245, 47, 191, 104, 212, 172, 156, 183, 168,
+];
+
+/// Builds an instruction to be executed by a Squads vault transaction.
+///
+/// The vault PDA is the only signer: Squads signs for it when the proposal executes. Accounts
+/// the layout marks as signers must therefore be `vault`, since no ephemeral signers are used.
+///
+/// # Arguments
+/// * `program_id` - Program the instruction targets (the protocol, or the integrating program)
+/// * `layout` - Account layout of the instruction
+/// * `keys` - Account addresses in layout order, optionally followed by trailing accounts
+/// * `vault` - Squads vault PDA executing the instruction
+/// * `data` - Instruction data
+///
+/// # Returns
+/// * `Ok(Instruction)` - Instruction with signer flags set for the vault only
+/// * `Err(ClientError::NotEnoughAccounts)` - Fewer keys than the layout requires
+/// * `Err(ClientError::UnsupportedSigner)` - A signer account other than `vault`
+pub fn vault_instruction(
+    program_id: Pubkey,
+    layout: &[AccountSpec],
+    keys: &[Pubkey],
+    vault: Pubkey,
+    data: Vec<u8>,
+) -> Result<Instruction, ClientError> {
+    let mut accounts = account_metas(layout, keys)?;
+    for (index, meta) in accounts.iter_mut().enumerate() {
+        if meta.pubkey == vault {
+            meta.is_signer = true;
+        } else if meta.is_signer {
+            return Err(ClientError::UnsupportedSigner { index });
+        }
+    }
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloc::vec};
+
+    const LAYOUT: &[AccountSpec] = &[
+        AccountSpec::readonly("program"),
+        AccountSpec::writable_signer("owner"),
+        AccountSpec::writable("vault_token_account"),
+    ];
+
+    #[test]
+    fn only_the_vault_signs() {
+        let keys = [[0; 32], [1; 32], [2; 32], [1; 32]];
+        let instruction = vault_instruction([0; 32], LAYOUT, &keys, [1; 32], vec![7]).unwrap();
+        let signers: Vec<bool> = instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.is_signer)
+            .collect();
+        // The vault signs in every position it appears in.
+        assert_eq!(signers, [false, true, false, true]);
+        assert_eq!(instruction.data, [7]);
+    }
+
+    #[test]
+    fn rejects_signers_other_than_the_vault() {
+        assert_eq!(
+            vault_instruction(
+                [0; 32],
+                LAYOUT,
+                &[[0; 32], [1; 32], [2; 32]],
+                [2; 32],
+                vec![]
+            ),
+            Err(ClientError::UnsupportedSigner { index: 1 })
+        );
+    }
+}
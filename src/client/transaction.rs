@@ -0,0 +1,138 @@
+use {
+    crate::client::{AccountMeta, Pubkey},
+    alloc::vec::Vec,
+};
+
+/// Maximum serialized transaction size, in bytes (`PACKET_DATA_SIZE`).
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Maximum number of accounts a transaction may lock.
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+
+/// Instruction ready to be placed in a transaction, matching `solana_instruction::Instruction`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Estimated footprint of a legacy transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionSize {
+    /// Unique accounts referenced, program ids and fee payer included
+    pub accounts: usize,
+    /// Number of required signatures
+    pub signers: usize,
+    /// Serialized size in bytes, signatures included
+    pub bytes: usize,
+}
+
+impl TransactionSize {
+    /// Returns `true` when the transaction stays within the size and account lock limits.
+    pub const fn fits(&self) -> bool {
+        self.bytes <= MAX_TRANSACTION_SIZE && self.accounts <= MAX_TRANSACTION_ACCOUNTS
+    }
+}
+
+/// Estimates the size of a legacy transaction carrying `instructions`, paid by `payer`.
+///
+/// # Notes
+/// * Address lookup tables are not taken into account: the estimate is an upper bound for
+///   v0 transactions.
+pub fn estimate_transaction_size(instructions: &[Instruction], payer: Pubkey) -> TransactionSize {
+    let mut keys: Vec<(Pubkey, bool)> = Vec::new();
+    let mut add =
+        |pubkey: Pubkey, is_signer: bool| match keys.iter_mut().find(|(key, _)| *key == pubkey) {
+            Some((_, signer)) => *signer |= is_signer,
+            None => keys.push((pubkey, is_signer)),
+        };
+
+    add(payer, true);
+    let mut instructions_len = compact_len(instructions.len());
+    for instruction in instructions {
+        add(instruction.program_id, false);
+        for meta in &instruction.accounts {
+            add(meta.pubkey, meta.is_signer);
+        }
+        instructions_len += 1
+            + compact_len(instruction.accounts.len())
+            + instruction.accounts.len()
+            + compact_len(instruction.data.len())
+            + instruction.data.len();
+    }
+
+    let signers = keys.iter().filter(|(_, is_signer)| *is_signer).count();
+    // Signatures, message header (3 bytes), account keys, recent blockhash, instructions
+    let bytes = compact_len(signers)
+        + signers * 64
+        + 3
+        + compact_len(keys.len())
+        + keys.len() * 32
+        + 32
+        + instructions_len;
+
+    TransactionSize {
+        accounts: keys.len(),
+        signers,
+        bytes,
+    }
+}
+
+/// Length of `len` encoded as a compact-u16.
+const fn compact_len(len: usize) -> usize {
+    match len {
+        0..0x80 => 1,
+        0x80..0x4000 => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloc::vec};
+
+    fn meta(byte: u8, is_signer: bool) -> AccountMeta {
+        AccountMeta {
+            pubkey: [byte; 32],
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    #[test]
+    fn counts_each_key_once() {
+        let instruction = Instruction {
+            program_id: [9; 32],
+            accounts: vec![meta(1, true), meta(2, false)],
+            data: vec![0; 9],
+        };
+        let size = estimate_transaction_size(&[instruction.clone(), instruction], [1; 32]);
+
+        // Payer, program and one other account; the payer is the only signer.
+        assert_eq!((size.accounts, size.signers), (3, 1));
+        // 65 signature + 3 header + 97 keys + 32 blockhash + 1 + 2 * 14 instructions
+        assert_eq!(size.bytes, 226);
+        assert!(size.fits());
+    }
+
+    #[test]
+    fn flags_oversized_transactions() {
+        let instruction = Instruction {
+            program_id: [0; 32],
+            accounts: (1..=64).map(|byte| meta(byte, false)).collect(),
+            data: vec![],
+        };
+        let size = estimate_transaction_size(&[instruction], [1; 32]);
+        assert_eq!(size.accounts, 65);
+        assert!(!size.fits());
+    }
+
+    #[test]
+    fn compact_lengths_grow_at_seven_bit_boundaries() {
+        assert_eq!(
+            [0, 0x7f, 0x80, 0x3fff, 0x4000].map(compact_len),
+            [1, 1, 2, 2, 3]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use {
+    crate::{
+        Deposit, DepositContext, check_protocol_executable_accounts,
+        programs::{Detector, ProgramIdDetector, ProgramIds},
+        try_from_deposit_context_for_with_ids,
+        util::{Rounding, log::LogLine, mul_div_u64},
+    },
+    pinocchio::{AccountView, Address, cpi::Signer, error::ProgramError},
+};
+
+/// Basis points in 100%.
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Runtime settings for the `*_with_config` convenience functions.
+///
+/// Lets governance-controlled programs decide from an on-chain config account what would
+/// otherwise be fixed at compile time: which deployments to route to, how strictly to
+/// validate client-supplied accounts, the fee to withhold and whether to log events.
+///
+/// # Example
+/// ```ignore
+/// let config = BeethovenConfig {
+///     strict: true,
+///     trusted_programs: &[SWAP_HOOK_PROGRAM_ID],
+///     fee_bps: settings.fee_bps,
+///     ..BeethovenConfig::DEFAULT
+/// };
+/// let fee = deposit_signed_with_config(accounts, amount, signers, &config)?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BeethovenConfig<'a> {
+    /// Program IDs validated against and detected by
+    pub program_ids: ProgramIds,
+    /// Reject untrusted executable accounts (see `check_protocol_executable_accounts`)
+    pub strict: bool,
+    /// Programs trusted in strict mode besides the protocol, the programs it invokes and the
+    /// token programs
+    pub trusted_programs: &'a [Address],
+    /// Share of each deposit withheld as a fee, in basis points
+    pub fee_bps: u16,
+    /// Log a `beethoven: deposit` line after each deposit
+    pub emit_events: bool,
+}
+
+impl BeethovenConfig<'_> {
+    /// Mainnet IDs, no strict validation, no fee and no events: the behavior of the plain
+    /// convenience functions.
+    pub const DEFAULT: Self = Self {
+        program_ids: ProgramIds::MAINNET,
+        strict: false,
+        trusted_programs: &[],
+        fee_bps: 0,
+        emit_events: false,
+    };
+
+    /// Splits `amount` into the part to deposit and the fee withheld, rounding the fee down.
+    ///
+    /// # Returns
+    /// * `Ok((net, fee))` - `net + fee == amount`
+    /// * `Err(ProgramError::InvalidArgument)` - `fee_bps` exceeds `MAX_FEE_BPS`
+    pub fn split_fee(&self, amount: u64) -> Result<(u64, u64), ProgramError> {
+        if self.fee_bps > MAX_FEE_BPS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let fee = mul_div_u64(
+            amount,
+            self.fee_bps as u64,
+            MAX_FEE_BPS as u64,
+            Rounding::Down,
+        )?;
+        Ok((amount - fee, fee))
+    }
+}
+
+impl Default for BeethovenConfig<'_> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Parses accounts into a `DepositContext` following `config`.
+///
+/// Detects the protocol against `config.program_ids` and, in strict mode, rejects untrusted
+/// executable accounts before parsing.
+///
+/// # Returns
+/// * `Ok(DepositContext)` - Typed context for the detected protocol
+/// * `Err(BeethovenError::UntrustedProgram)` - Strict mode found an untrusted program
+/// * `Err(ProgramError)` - Detection or parsing failed
+pub fn try_from_deposit_context_with_config<'info>(
+    accounts: &'info [AccountView],
+    config: &BeethovenConfig,
+) -> Result<DepositContext<'info>, ProgramError> {
+    let ids = &config.program_ids;
+    let protocol = ProgramIdDetector.detect(accounts, ids)?;
+    if config.strict {
+        check_protocol_executable_accounts(accounts, protocol, ids, config.trusted_programs)?;
+    }
+    try_from_deposit_context_for_with_ids(protocol, accounts, ids)
+}
+
+/// Convenience function: Parses accounts following `config` and deposits `amount` minus the
+/// configured fee with PDA signing.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit, fee included
+/// * `signer_seeds` - Seeds for PDA signing
+/// * `config` - Runtime settings
+///
+/// # Returns
+/// * `Ok(u64)` - Fee withheld from the deposit
+/// * `Err(ProgramError)` - Invalid config, parsing, validation or CPI failed
+///
+/// # Notes
+/// * The fee is not transferred: it stays in the source account for the caller to sweep.
+pub fn deposit_signed_with_config(
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+    config: &BeethovenConfig,
+) -> Result<u64, ProgramError> {
+    let (net, fee) = config.split_fee(amount)?;
+    let ctx = try_from_deposit_context_with_config(accounts, config)?;
+    DepositContext::deposit_signed(&ctx, net, signer_seeds)?;
+
+    if config.emit_events {
+        LogLine::new()
+            .push(b"beethoven: deposit protocol=")
+            .push(ctx.protocol().name().as_bytes())
+            .push(b" amount=")
+            .push_u64(net)
+            .push(b" fee=")
+            .push_u64(fee)
+            .log();
+    }
+
+    Ok(fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "jupiter")]
+    use crate::{
+        BeethovenError,
+        programs::jupiter::tests::deposit_accounts,
+        test_utils::{TestAccount, address, views},
+    };
+
+    fn config(fee_bps: u16) -> BeethovenConfig<'static> {
+        BeethovenConfig {
+            fee_bps,
+            ..BeethovenConfig::DEFAULT
+        }
+    }
+
+    #[test]
+    fn splits_the_fee_rounding_down() {
+        assert_eq!(config(0).split_fee(1_000), Ok((1_000, 0)));
+        assert_eq!(config(25).split_fee(1_000), Ok((998, 2)));
+        assert_eq!(config(MAX_FEE_BPS).split_fee(1_000), Ok((0, 1_000)));
+        assert_eq!(
+            config(MAX_FEE_BPS + 1).split_fee(1_000),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn deposits_net_of_the_fee() {
+        let mut accounts = deposit_accounts();
+        let config = BeethovenConfig {
+            emit_events: true,
+            ..config(100)
+        };
+        assert_eq!(
+            deposit_signed_with_config(&views(&mut accounts), 1_000, &[], &config),
+            Ok(10)
+        );
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn strict_mode_rejects_untrusted_programs() {
+        let mut accounts = deposit_accounts();
+        accounts.push(TestAccount::empty(address(7)).executable());
+        let views = views(&mut accounts);
+
+        assert!(try_from_deposit_context_with_config(&views, &BeethovenConfig::DEFAULT).is_ok());
+        let strict = BeethovenConfig {
+            strict: true,
+            ..BeethovenConfig::DEFAULT
+        };
+        assert_eq!(
+            try_from_deposit_context_with_config(&views, &strict).err(),
+            Some(BeethovenError::UntrustedProgram.into())
+        );
+        let trusting = BeethovenConfig {
+            trusted_programs: &[address(7)],
+            ..strict
+        };
+        assert!(try_from_deposit_context_with_config(&views, &trusting).is_ok());
+    }
+}
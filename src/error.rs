@@ -0,0 +1,309 @@
+use {
+    crate::{programs::AccountSpec, util::log::LogLine},
+    pinocchio::error::ProgramError,
+};
+
+/// Offset applied to every [`BeethovenError`] code so it does not collide with
+/// the custom error codes of the program embedding Beethoven.
+pub const BEETHOVEN_ERROR_OFFSET: u32 = 0xBEE7_0000;
+
+/// Errors raised by Beethoven itself, surfaced as `ProgramError::Custom`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BeethovenError {
+    /// The oracle price is older than the allowed staleness window.
+    StaleOracle = 0,
+    /// The oracle confidence interval is wider than the allowed threshold.
+    OracleConfidenceTooWide = 1,
+    /// The account is not a valid price account for the requested feed.
+    InvalidOracle = 2,
+    /// The deposit would exceed the venue's deposit cap.
+    DepositCapExceeded = 3,
+    /// The operation landed after its deadline slot.
+    DeadlineExceeded = 4,
+    /// The operation would return less than the requested minimum output.
+    SlippageExceeded = 5,
+    /// No venue could accept the operation.
+    NoVenueAvailable = 6,
+    /// The venue is paused or in emergency mode.
+    VenuePaused = 7,
+    /// The venue does not hold enough liquidity for the operation.
+    InsufficientLiquidity = 8,
+    /// The venue state must be refreshed earlier in the transaction.
+    RefreshRequired = 9,
+    /// The obligation is not in the expected elevation group.
+    ElevationGroupMismatch = 10,
+    /// The pending withdrawal cannot be claimed yet.
+    WithdrawalNotReady = 11,
+    /// An executable account is not in the set of trusted programs.
+    UntrustedProgram = 12,
+    /// Two writable accounts of a protocol context share an address
+    DuplicateAccount = 13,
+    /// A balance moved differently than the operation implies (e.g. a substituted account)
+    UnexpectedBalanceChange = 14,
+}
+
+impl BeethovenError {
+    /// Decodes a `ProgramError` produced by beethoven back into a `BeethovenError`.
+    ///
+    /// # Returns
+    /// * `Some(BeethovenError)` - The error is a beethoven custom error
+    /// * `None` - The error comes from elsewhere (runtime, protocol, ...)
+    pub fn from_program_error(error: &ProgramError) -> Option<Self> {
+        let ProgramError::Custom(code) = error else {
+            return None;
+        };
+
+        match code.checked_sub(BEETHOVEN_ERROR_OFFSET)? {
+            0 => Some(Self::StaleOracle),
+            1 => Some(Self::OracleConfidenceTooWide),
+            2 => Some(Self::InvalidOracle),
+            3 => Some(Self::DepositCapExceeded),
+            4 => Some(Self::DeadlineExceeded),
+            5 => Some(Self::SlippageExceeded),
+            6 => Some(Self::NoVenueAvailable),
+            7 => Some(Self::VenuePaused),
+            8 => Some(Self::InsufficientLiquidity),
+            9 => Some(Self::RefreshRequired),
+            10 => Some(Self::ElevationGroupMismatch),
+            11 => Some(Self::WithdrawalNotReady),
+            12 => Some(Self::UntrustedProgram),
+            13 => Some(Self::DuplicateAccount),
+            14 => Some(Self::UnexpectedBalanceChange),
+            _ => None,
+        }
+    }
+
+    /// Variant name, used in program logs.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::StaleOracle => "StaleOracle",
+            Self::OracleConfidenceTooWide => "OracleConfidenceTooWide",
+            Self::InvalidOracle => "InvalidOracle",
+            Self::DepositCapExceeded => "DepositCapExceeded",
+            Self::DeadlineExceeded => "DeadlineExceeded",
+            Self::SlippageExceeded => "SlippageExceeded",
+            Self::NoVenueAvailable => "NoVenueAvailable",
+            Self::VenuePaused => "VenuePaused",
+            Self::InsufficientLiquidity => "InsufficientLiquidity",
+            Self::RefreshRequired => "RefreshRequired",
+            Self::ElevationGroupMismatch => "ElevationGroupMismatch",
+            Self::WithdrawalNotReady => "WithdrawalNotReady",
+            Self::UntrustedProgram => "UntrustedProgram",
+            Self::DuplicateAccount => "DuplicateAccount",
+            Self::UnexpectedBalanceChange => "UnexpectedBalanceChange",
+        }
+    }
+
+    /// Returns `true` when the error only means this venue cannot take the operation right
+    /// now, so another venue may be tried instead.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::DepositCapExceeded | Self::VenuePaused | Self::InsufficientLiquidity
+        )
+    }
+}
+
+/// Returns `true` when `error` is a retryable [`BeethovenError`].
+pub fn is_retryable(error: &ProgramError) -> bool {
+    BeethovenError::from_program_error(error).is_some_and(BeethovenError::is_retryable)
+}
+
+impl From<BeethovenError> for ProgramError {
+    fn from(error: BeethovenError) -> Self {
+        ProgramError::Custom(BEETHOVEN_ERROR_OFFSET + error as u32)
+    }
+}
+
+/// Check an account failed while parsing a protocol context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountCheck {
+    /// The account is missing from the slice
+    Missing,
+    /// The account is not the expected program
+    ProgramId,
+    /// The account is not owned by the expected program
+    Owner,
+    /// The account address is not the expected one
+    Key,
+    /// The account did not sign the transaction
+    Signer,
+    /// The account is not writable
+    Writable,
+    /// The writable account aliases an earlier writable account
+    Duplicate,
+}
+
+impl AccountCheck {
+    /// Short label used in program logs.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::ProgramId => "expected program id",
+            Self::Owner => "expected owner",
+            Self::Key => "expected key",
+            Self::Signer => "missing signer",
+            Self::Writable => "expected writable",
+            Self::Duplicate => "duplicate writable",
+        }
+    }
+}
+
+/// Describes which account of a parsed context failed, and why.
+///
+/// Converting into a `ProgramError` logs `beethoven: account #<index> (<name>): <check>`
+/// and maps to the `ProgramError` each check has always surfaced as, so callers matching
+/// on error variants are unaffected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountError {
+    /// Index of the offending account in the parsed slice
+    pub index: usize,
+    /// Field name of the offending account
+    pub name: &'static str,
+    /// The check that failed
+    pub check: AccountCheck,
+}
+
+impl AccountError {
+    /// Builds the error for the account at `index` of `layout`.
+    pub fn new(layout: &[AccountSpec], index: usize, check: AccountCheck) -> Self {
+        Self {
+            index,
+            name: layout.get(index).map_or("remaining", |spec| spec.name),
+            check,
+        }
+    }
+
+    /// Builds the error for the account named `name` in `layout`.
+    pub fn of(layout: &[AccountSpec], name: &'static str, check: AccountCheck) -> Self {
+        let index = layout
+            .iter()
+            .position(|spec| spec.name == name)
+            .unwrap_or(layout.len());
+        Self { index, name, check }
+    }
+
+    /// Builds the error for a slice holding only `provided` of the `layout` accounts.
+    pub fn missing(layout: &[AccountSpec], provided: usize) -> Self {
+        Self::new(layout, provided, AccountCheck::Missing)
+    }
+
+    /// Writes the error to the program logs.
+    pub fn log(&self) {
+        LogLine::new()
+            .push(b"beethoven: account #")
+            .push_u64(self.index as u64)
+            .push(b" (")
+            .push(self.name.as_bytes())
+            .push(b"): ")
+            .push(self.check.label().as_bytes())
+            .log();
+    }
+}
+
+impl From<AccountError> for ProgramError {
+    fn from(error: AccountError) -> Self {
+        error.log();
+
+        match error.check {
+            AccountCheck::Missing => ProgramError::NotEnoughAccountKeys,
+            AccountCheck::ProgramId => ProgramError::IncorrectProgramId,
+            AccountCheck::Owner => ProgramError::IllegalOwner,
+            AccountCheck::Key => ProgramError::InvalidAccountData,
+            AccountCheck::Signer => ProgramError::MissingRequiredSignature,
+            AccountCheck::Writable => ProgramError::InvalidAccountData,
+            AccountCheck::Duplicate => BeethovenError::DuplicateAccount.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_through_program_error() {
+        let mut decoded = 0;
+        for code in 0..32 {
+            let error = ProgramError::Custom(BEETHOVEN_ERROR_OFFSET + code);
+            if let Some(beethoven) = BeethovenError::from_program_error(&error) {
+                assert_eq!(beethoven as u32, code, "{}", beethoven.name());
+                assert_eq!(ProgramError::from(beethoven), error);
+                decoded += 1;
+            }
+        }
+        assert_eq!(decoded, BeethovenError::UnexpectedBalanceChange as u32 + 1);
+    }
+
+    #[test]
+    fn ignores_errors_from_elsewhere() {
+        assert_eq!(
+            BeethovenError::from_program_error(&ProgramError::Custom(6)),
+            None
+        );
+        assert_eq!(
+            BeethovenError::from_program_error(&ProgramError::InvalidAccountData),
+            None
+        );
+    }
+
+    #[test]
+    fn only_venue_capacity_errors_are_retryable() {
+        assert!(is_retryable(&BeethovenError::DepositCapExceeded.into()));
+        assert!(is_retryable(&BeethovenError::VenuePaused.into()));
+        assert!(is_retryable(&BeethovenError::InsufficientLiquidity.into()));
+        assert!(!is_retryable(&BeethovenError::SlippageExceeded.into()));
+        assert!(!is_retryable(&ProgramError::InsufficientFunds));
+    }
+
+    const LAYOUT: &[AccountSpec] = &[
+        AccountSpec::readonly("program"),
+        AccountSpec::writable_signer("owner"),
+    ];
+
+    #[test]
+    fn account_errors_name_the_failing_account() {
+        assert_eq!(
+            AccountError::of(LAYOUT, "owner", AccountCheck::Signer),
+            AccountError {
+                index: 1,
+                name: "owner",
+                check: AccountCheck::Signer,
+            }
+        );
+        assert_eq!(AccountError::missing(LAYOUT, 1).name, "owner");
+        // Accounts past the layout are trailing accounts.
+        assert_eq!(
+            AccountError::new(LAYOUT, 5, AccountCheck::Writable).name,
+            "remaining"
+        );
+    }
+
+    #[test]
+    fn account_errors_keep_their_program_errors() {
+        let error = |check| ProgramError::from(AccountError::new(LAYOUT, 1, check));
+        assert_eq!(
+            error(AccountCheck::Missing),
+            ProgramError::NotEnoughAccountKeys
+        );
+        assert_eq!(
+            error(AccountCheck::ProgramId),
+            ProgramError::IncorrectProgramId
+        );
+        assert_eq!(error(AccountCheck::Owner), ProgramError::IllegalOwner);
+        assert_eq!(error(AccountCheck::Key), ProgramError::InvalidAccountData);
+        assert_eq!(
+            error(AccountCheck::Signer),
+            ProgramError::MissingRequiredSignature
+        );
+        assert_eq!(
+            error(AccountCheck::Writable),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            error(AccountCheck::Duplicate),
+            BeethovenError::DuplicateAccount.into()
+        );
+    }
+}
@@ -0,0 +1,569 @@
+//! Per-user accounting ledger recording cumulative deposits and withdrawals.
+//!
+//! Each `(user, protocol, mint)` triple gets one PDA of the integrating program, derived
+//! from `[LEDGER_SEED, user, [protocol tag], mint]`. Points programs and audits can read
+//! totals straight from these accounts instead of reconstructing them from transactions.
+//!
+//! | bytes  | field                                   |
+//! |--------|-----------------------------------------|
+//! | 0..8   | discriminator                           |
+//! | 8      | PDA bump                                |
+//! | 9      | protocol tag (see [`Protocol`])         |
+//! | 10..42 | user                                    |
+//! | 42..74 | mint                                    |
+//! | 74..82 | total deposited, little-endian `u64`    |
+//! | 82..90 | total withdrawn, little-endian `u64`    |
+//! | 90..98 | slot of the last update                 |
+//!
+//! The discriminator is Anchor's account discriminator for `LedgerEntry`, the first 8 bytes
+//! of `sha256("account:LedgerEntry")`, so Anchor-based readers can decode the account as-is.
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+use crate::{ClaimWithdraw, ClaimWithdrawContext, try_from_claim_withdraw_context};
+use {
+    crate::{
+        Deposit, DepositContext,
+        programs::{Detector, ProgramIdDetector, ProgramIds, Protocol},
+        try_from_deposit_context_for_with_ids,
+        util::{SYSTEM_PROGRAM_ID, bytes::*, create_program_address},
+    },
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::{Signer, invoke_signed},
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+        sysvars::{Sysvar, clock::Clock, rent::Rent},
+    },
+};
+
+/// First seed of every ledger PDA.
+pub const LEDGER_SEED: &[u8] = b"ledger";
+
+/// Size of a ledger account, in bytes.
+pub const LEDGER_ACCOUNT_LEN: usize = 98;
+
+/// `sha256("account:LedgerEntry")[..8]`
+const LEDGER_DISCRIMINATOR: [u8; 8] = [216, 84, 198, 15, 37, 222, 142, 144];
+
+// System program instruction tags.
+const CREATE_ACCOUNT_TAG: u32 = 0;
+const ASSIGN_TAG: u32 = 1;
+const TRANSFER_TAG: u32 = 2;
+const ALLOCATE_TAG: u32 = 8;
+
+const LEDGER_BUMP_OFFSET: usize = 8;
+const LEDGER_PROTOCOL_OFFSET: usize = 9;
+const LEDGER_USER_OFFSET: usize = 10;
+const LEDGER_MINT_OFFSET: usize = 42;
+const LEDGER_TOTAL_DEPOSITED_OFFSET: usize = 74;
+const LEDGER_TOTAL_WITHDRAWN_OFFSET: usize = 82;
+const LEDGER_LAST_SLOT_OFFSET: usize = 90;
+
+/// Decoded ledger account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LedgerEntry {
+    /// Bump of the ledger PDA
+    pub bump: u8,
+    /// Stable tag of the protocol the entry tracks
+    pub protocol: u8,
+    /// User the entry tracks
+    pub user: Address,
+    /// Mint deposited and withdrawn
+    pub mint: Address,
+    /// Cumulative amount deposited
+    pub total_deposited: u64,
+    /// Cumulative amount withdrawn
+    pub total_withdrawn: u64,
+    /// Slot of the last recorded operation
+    pub last_slot: u64,
+}
+
+impl LedgerEntry {
+    /// Returns the PDA seeds (without the bump) of the entry for `user`, `protocol` and `mint`.
+    ///
+    /// `protocol` is the one-byte protocol tag, e.g. `[Protocol::Kamino as u8]`.
+    pub fn seeds<'a>(user: &'a Address, protocol: &'a [u8; 1], mint: &'a Address) -> [&'a [u8]; 4] {
+        [LEDGER_SEED, user.as_ref(), protocol, mint.as_ref()]
+    }
+
+    /// Parses raw ledger account data.
+    ///
+    /// # Returns
+    /// * `Ok(LedgerEntry)` - Parsed entry
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a ledger account
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &LEDGER_DISCRIMINATOR)?;
+
+        Ok(Self {
+            bump: read_array::<1>(data, LEDGER_BUMP_OFFSET)?[0],
+            protocol: read_array::<1>(data, LEDGER_PROTOCOL_OFFSET)?[0],
+            user: read_address(data, LEDGER_USER_OFFSET)?,
+            mint: read_address(data, LEDGER_MINT_OFFSET)?,
+            total_deposited: read_u64(data, LEDGER_TOTAL_DEPOSITED_OFFSET)?,
+            total_withdrawn: read_u64(data, LEDGER_TOTAL_WITHDRAWN_OFFSET)?,
+            last_slot: read_u64(data, LEDGER_LAST_SLOT_OFFSET)?,
+        })
+    }
+
+    /// Reads the entry stored in `ledger`, checking it is owned by `program_id`.
+    ///
+    /// # Returns
+    /// * `Ok(LedgerEntry)` - Parsed entry
+    /// * `Err(ProgramError::IllegalOwner)` - Ledger is not owned by `program_id`
+    /// * `Err(ProgramError)` - Ledger data could not be read
+    pub fn load(ledger: &AccountView, program_id: &Address) -> Result<Self, ProgramError> {
+        if !ledger.owned_by(program_id) {
+            return Err(ProgramError::IllegalOwner);
+        }
+        Self::from_bytes(&ledger.try_borrow()?)
+    }
+
+    /// Creates and initializes the rent-exempt ledger PDA for `user`, `protocol` and `mint`.
+    ///
+    /// # Arguments
+    /// * `payer` - Funds the rent (mutable, signer)
+    /// * `ledger` - Ledger PDA to create (writable)
+    /// * `system_program` - System program
+    /// * `program_id` - Integrating program, owner of the ledger
+    /// * `user`, `protocol`, `mint` - Triple the entry tracks
+    /// * `bump` - Bump of the ledger PDA
+    /// * `signer_seeds` - Signer seeds of the ledger PDA (and of `payer` when it is a PDA)
+    ///
+    /// # Returns
+    /// * `Ok(())` - Ledger created with zeroed totals
+    /// * `Err(ProgramError::IncorrectProgramId)` - `system_program` is not the system program
+    /// * `Err(ProgramError::InvalidSeeds)` - `ledger` is not the PDA of the triple and `bump`
+    /// * `Err(ProgramError)` - CPI failure (e.g. the ledger already exists)
+    ///
+    /// # Notes
+    /// * An address that already holds lamports cannot be created with `CreateAccount`, so a
+    ///   pre-funded ledger is topped up to rent exemption, allocated and assigned instead.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_signed(
+        payer: &AccountView,
+        ledger: &AccountView,
+        system_program: &AccountView,
+        program_id: &Address,
+        user: &Address,
+        protocol: Protocol,
+        mint: &Address,
+        bump: u8,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        if !address_eq(system_program.address(), &SYSTEM_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let tag = [protocol as u8];
+        let [seed, user_seed, protocol_seed, mint_seed] = Self::seeds(user, &tag, mint);
+        let address = create_program_address(
+            &[seed, user_seed, protocol_seed, mint_seed, &[bump]],
+            program_id,
+        )?;
+        if !address_eq(ledger.address(), &address) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let rent = Rent::get()?.try_minimum_balance(LEDGER_ACCOUNT_LEN)?;
+        let funded = ledger.lamports();
+
+        if funded == 0 {
+            create_account(
+                payer,
+                ledger,
+                system_program,
+                program_id,
+                rent,
+                signer_seeds,
+            )?;
+        } else {
+            if funded < rent {
+                transfer(payer, ledger, system_program, rent - funded, signer_seeds)?;
+            }
+            allocate(ledger, system_program, signer_seeds)?;
+            assign(ledger, system_program, program_id, signer_seeds)?;
+        }
+
+        write_header(&mut ledger.try_borrow_mut()?, bump, protocol, user, mint)
+    }
+
+    /// Adds `amount` to the deposits recorded in `ledger`, stamping `slot`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Ledger updated
+    /// * `Err(ProgramError::IllegalOwner)` - Ledger is not owned by `program_id`
+    /// * `Err(ProgramError::ArithmeticOverflow)` - The total would overflow
+    /// * `Err(ProgramError)` - Ledger data could not be read
+    pub fn record_deposit(
+        ledger: &AccountView,
+        program_id: &Address,
+        amount: u64,
+        slot: u64,
+    ) -> ProgramResult {
+        Self::record(
+            ledger,
+            program_id,
+            LEDGER_TOTAL_DEPOSITED_OFFSET,
+            amount,
+            slot,
+        )
+    }
+
+    /// Adds `amount` to the withdrawals recorded in `ledger`, stamping `slot`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Ledger updated
+    /// * `Err(ProgramError::IllegalOwner)` - Ledger is not owned by `program_id`
+    /// * `Err(ProgramError::ArithmeticOverflow)` - The total would overflow
+    /// * `Err(ProgramError)` - Ledger data could not be read
+    pub fn record_withdraw(
+        ledger: &AccountView,
+        program_id: &Address,
+        amount: u64,
+        slot: u64,
+    ) -> ProgramResult {
+        Self::record(
+            ledger,
+            program_id,
+            LEDGER_TOTAL_WITHDRAWN_OFFSET,
+            amount,
+            slot,
+        )
+    }
+
+    fn record(
+        ledger: &AccountView,
+        program_id: &Address,
+        offset: usize,
+        amount: u64,
+        slot: u64,
+    ) -> ProgramResult {
+        if !ledger.owned_by(program_id) {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut data = ledger.try_borrow_mut()?;
+        check_discriminator(&data, &LEDGER_DISCRIMINATOR)?;
+
+        let total = read_u64(&data, offset)?
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        data[offset..offset + 8].copy_from_slice(&total.to_le_bytes());
+        data[LEDGER_LAST_SLOT_OFFSET..LEDGER_ACCOUNT_LEN].copy_from_slice(&slot.to_le_bytes());
+        Ok(())
+    }
+}
+
+/// Writes the discriminator, bump and tracked triple of a freshly allocated ledger.
+fn write_header(
+    data: &mut [u8],
+    bump: u8,
+    protocol: Protocol,
+    user: &Address,
+    mint: &Address,
+) -> ProgramResult {
+    if data.len() < LEDGER_ACCOUNT_LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    data[..8].copy_from_slice(&LEDGER_DISCRIMINATOR);
+    data[LEDGER_BUMP_OFFSET] = bump;
+    data[LEDGER_PROTOCOL_OFFSET] = protocol as u8;
+    data[LEDGER_USER_OFFSET..LEDGER_MINT_OFFSET].copy_from_slice(user.as_ref());
+    data[LEDGER_MINT_OFFSET..LEDGER_TOTAL_DEPOSITED_OFFSET].copy_from_slice(mint.as_ref());
+    Ok(())
+}
+
+fn create_account(
+    payer: &AccountView,
+    ledger: &AccountView,
+    system_program: &AccountView,
+    program_id: &Address,
+    lamports: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let accounts = [
+        InstructionAccount::writable_signer(payer.address()),
+        InstructionAccount::writable_signer(ledger.address()),
+    ];
+
+    // Build instruction data: tag (4 bytes) + lamports (8 bytes) + space (8 bytes) + owner (32 bytes)
+    let mut instruction_data = [0u8; 52];
+    instruction_data[..4].copy_from_slice(&CREATE_ACCOUNT_TAG.to_le_bytes());
+    instruction_data[4..12].copy_from_slice(&lamports.to_le_bytes());
+    instruction_data[12..20].copy_from_slice(&(LEDGER_ACCOUNT_LEN as u64).to_le_bytes());
+    instruction_data[20..].copy_from_slice(program_id.as_ref());
+
+    let create_ix = InstructionView {
+        program_id: system_program.address(),
+        accounts: &accounts,
+        data: &instruction_data,
+    };
+
+    invoke_signed(&create_ix, &[payer, ledger], signer_seeds)
+}
+
+fn transfer(
+    payer: &AccountView,
+    ledger: &AccountView,
+    system_program: &AccountView,
+    lamports: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let accounts = [
+        InstructionAccount::writable_signer(payer.address()),
+        InstructionAccount::writable(ledger.address()),
+    ];
+
+    // Build instruction data: tag (4 bytes) + lamports (8 bytes)
+    let mut instruction_data = [0u8; 12];
+    instruction_data[..4].copy_from_slice(&TRANSFER_TAG.to_le_bytes());
+    instruction_data[4..].copy_from_slice(&lamports.to_le_bytes());
+
+    let transfer_ix = InstructionView {
+        program_id: system_program.address(),
+        accounts: &accounts,
+        data: &instruction_data,
+    };
+
+    invoke_signed(&transfer_ix, &[payer, ledger], signer_seeds)
+}
+
+fn allocate(
+    ledger: &AccountView,
+    system_program: &AccountView,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let accounts = [InstructionAccount::writable_signer(ledger.address())];
+
+    // Build instruction data: tag (4 bytes) + space (8 bytes)
+    let mut instruction_data = [0u8; 12];
+    instruction_data[..4].copy_from_slice(&ALLOCATE_TAG.to_le_bytes());
+    instruction_data[4..].copy_from_slice(&(LEDGER_ACCOUNT_LEN as u64).to_le_bytes());
+
+    let allocate_ix = InstructionView {
+        program_id: system_program.address(),
+        accounts: &accounts,
+        data: &instruction_data,
+    };
+
+    invoke_signed(&allocate_ix, &[ledger], signer_seeds)
+}
+
+fn assign(
+    ledger: &AccountView,
+    system_program: &AccountView,
+    program_id: &Address,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let accounts = [InstructionAccount::writable_signer(ledger.address())];
+
+    // Build instruction data: tag (4 bytes) + owner (32 bytes)
+    let mut instruction_data = [0u8; 36];
+    instruction_data[..4].copy_from_slice(&ASSIGN_TAG.to_le_bytes());
+    instruction_data[4..].copy_from_slice(program_id.as_ref());
+
+    let assign_ix = InstructionView {
+        program_id: system_program.address(),
+        accounts: &accounts,
+        data: &instruction_data,
+    };
+
+    invoke_signed(&assign_ix, &[ledger], signer_seeds)
+}
+
+/// Convenience function: Parses accounts, deposits with PDA signing, and records the deposit
+/// in `ledger`.
+///
+/// # Arguments
+/// * `ledger` - Ledger entry of the depositing user (writable)
+/// * `program_id` - Integrating program, owner of the ledger
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit
+/// * `signer_seeds` - Seeds for PDA signing
+///
+/// # Returns
+/// * `Ok(())` - Deposit executed and recorded
+/// * `Err(ProgramError::InvalidAccountData)` - The ledger tracks another protocol
+/// * `Err(ProgramError)` - Parsing, the CPI or the ledger update failed
+///
+/// # Notes
+/// * The ledger's user and mint are not matched against the protocol accounts; deriving the
+///   ledger PDA from them is up to the caller.
+pub fn deposit_signed_with_ledger(
+    ledger: &AccountView,
+    program_id: &Address,
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ids = &ProgramIds::MAINNET;
+    let protocol = ProgramIdDetector.detect(accounts, ids)?;
+    if LedgerEntry::load(ledger, program_id)?.protocol != protocol as u8 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let ctx = try_from_deposit_context_for_with_ids(protocol, accounts, ids)?;
+    DepositContext::deposit_signed(&ctx, amount, signer_seeds)?;
+    LedgerEntry::record_deposit(ledger, program_id, amount, Clock::get()?.slot)
+}
+
+/// Convenience function: Parses accounts, claims a matured withdrawal with PDA signing, and
+/// records the amount paid out in `ledger`.
+///
+/// # Arguments
+/// * `ledger` - Ledger entry of the withdrawing user (writable)
+/// * `program_id` - Integrating program, owner of the ledger
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `signer_seeds` - Seeds for PDA signing
+///
+/// # Returns
+/// * `Ok(())` - Claim executed and recorded
+/// * `Err(ProgramError::InvalidAccountData)` - The ledger tracks another protocol
+/// * `Err(ProgramError)` - Parsing, the CPI or the ledger update failed
+///
+/// # Notes
+/// * The withdrawal is recorded at claim time, in the underlying asset (lamports for
+///   Marinade, spot market tokens for Drift), as the increase of the claim destination's
+///   balance. Requests only burn receipts, so they are not recorded.
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn claim_withdraw_signed_with_ledger(
+    ledger: &AccountView,
+    program_id: &Address,
+    accounts: &[AccountView],
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ctx = try_from_claim_withdraw_context(accounts)?;
+    if LedgerEntry::load(ledger, program_id)?.protocol != ctx.protocol() as u8 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let destination = ctx.destination();
+    let before = destination.read()?;
+    ClaimWithdrawContext::claim_withdraw_signed(&ctx, signer_seeds)?;
+    let claimed = destination.read()?.saturating_sub(before);
+
+    LedgerEntry::record_withdraw(ledger, program_id, claimed, Clock::get()?.slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address},
+        std::{vec, vec::Vec},
+    };
+
+    const PROGRAM_ID: Address = Address::new_from_array([7; 32]);
+
+    fn ledger_data(protocol: Protocol) -> Vec<u8> {
+        let mut data = vec![0u8; LEDGER_ACCOUNT_LEN];
+        write_header(&mut data, 254, protocol, &address(1), &address(2)).unwrap();
+        data
+    }
+
+    #[test]
+    fn header_round_trips() {
+        let entry = LedgerEntry::from_bytes(&ledger_data(Protocol::Kamino)).unwrap();
+        assert_eq!(
+            entry,
+            LedgerEntry {
+                bump: 254,
+                protocol: Protocol::Kamino as u8,
+                user: address(1),
+                mint: address(2),
+                total_deposited: 0,
+                total_withdrawn: 0,
+                last_slot: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn header_rejects_short_data() {
+        let mut data = [0u8; LEDGER_ACCOUNT_LEN - 1];
+        assert_eq!(
+            write_header(&mut data, 0, Protocol::Kamino, &address(1), &address(2)),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn records_accumulate_and_stamp_the_slot() {
+        let mut ledger = TestAccount::new(address(3), PROGRAM_ID, &ledger_data(Protocol::Kamino));
+        let ledger = ledger.view();
+
+        LedgerEntry::record_deposit(&ledger, &PROGRAM_ID, 100, 10).unwrap();
+        LedgerEntry::record_deposit(&ledger, &PROGRAM_ID, 50, 11).unwrap();
+        LedgerEntry::record_withdraw(&ledger, &PROGRAM_ID, 30, 12).unwrap();
+
+        let entry = LedgerEntry::load(&ledger, &PROGRAM_ID).unwrap();
+        assert_eq!(entry.total_deposited, 150);
+        assert_eq!(entry.total_withdrawn, 30);
+        assert_eq!(entry.last_slot, 12);
+    }
+
+    #[test]
+    fn records_reject_overflow_and_foreign_owner() {
+        let mut ledger = TestAccount::new(address(3), PROGRAM_ID, &ledger_data(Protocol::Kamino));
+        let ledger = ledger.view();
+
+        LedgerEntry::record_withdraw(&ledger, &PROGRAM_ID, u64::MAX, 1).unwrap();
+        assert_eq!(
+            LedgerEntry::record_withdraw(&ledger, &PROGRAM_ID, 1, 2),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            LedgerEntry::record_deposit(&ledger, &address(9), 1, 2),
+            Err(ProgramError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn create_checks_the_system_program_and_the_pda() {
+        let tag = [Protocol::Kamino as u8];
+        let (pda, bump) = crate::util::find_program_address(
+            &LedgerEntry::seeds(&address(1), &tag, &address(2)),
+            &PROGRAM_ID,
+        );
+        let mut payer = TestAccount::empty(address(4)).signer();
+        let payer = payer.view();
+        let mut system_program = TestAccount::empty(SYSTEM_PROGRAM_ID);
+        let mut other_program = TestAccount::empty(address(5));
+        let mut ledger = TestAccount::empty(address(3));
+        let mut pda_ledger = TestAccount::empty(pda);
+
+        let create = |ledger: &AccountView, system_program: &AccountView, bump: u8| {
+            LedgerEntry::create_signed(
+                &payer,
+                ledger,
+                system_program,
+                &PROGRAM_ID,
+                &address(1),
+                Protocol::Kamino,
+                &address(2),
+                bump,
+                &[],
+            )
+        };
+
+        assert_eq!(
+            create(&pda_ledger.view(), &other_program.view(), bump),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            create(&ledger.view(), &system_program.view(), bump),
+            Err(ProgramError::InvalidSeeds)
+        );
+        assert_eq!(
+            create(
+                &pda_ledger.view(),
+                &system_program.view(),
+                bump.wrapping_sub(1)
+            ),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+}
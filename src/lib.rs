@@ -1,7 +1,119 @@
+//! On-chain code is `no_std`: protocol modules may only use `core`, plus `alloc` under the
+//! off-chain `client` feature. `make check-no-std` builds the library for a target without
+//! `std` to catch accidental leaks; std-only tooling lives in binaries behind `codegen`.
+//!
+//! Contexts, dispatch and everything built on them need at least one protocol feature.
+//! Without one, the crate still builds its protocol-agnostic parts: errors, account
+//! layouts, `util`, `oracle`, `Memo` and the `client` builders.
+
 #![no_std]
 
+#[cfg(feature = "client")]
+extern crate alloc;
+
+#[cfg(test)]
+extern crate std;
+
+pub mod error;
+pub use error::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod config;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use config::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
 pub mod traits;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
 pub use traits::*;
 
 pub mod programs;
 pub use programs::*;
+
+#[cfg(feature = "oracle")]
+pub mod oracle;
+#[cfg(feature = "oracle")]
+pub use oracle::*;
+
+#[cfg(all(
+    feature = "ledger",
+    any(
+        feature = "drift",
+        feature = "jupiter",
+        feature = "kamino",
+        feature = "marinade",
+        feature = "solayer",
+        feature = "stake_pool"
+    )
+))]
+pub mod ledger;
+#[cfg(all(
+    feature = "ledger",
+    any(
+        feature = "drift",
+        feature = "jupiter",
+        feature = "kamino",
+        feature = "marinade",
+        feature = "solayer",
+        feature = "stake_pool"
+    )
+))]
+pub use ledger::*;
+
+#[cfg(feature = "memo")]
+pub mod memo;
+#[cfg(feature = "memo")]
+pub use memo::*;
+
+#[cfg(all(
+    feature = "processor",
+    any(
+        feature = "drift",
+        feature = "jupiter",
+        feature = "kamino",
+        feature = "marinade",
+        feature = "solayer",
+        feature = "stake_pool"
+    )
+))]
+pub mod processor;
+
+#[cfg(feature = "client")]
+pub mod client;
+
+pub mod util;
+
+// Fixtures are shared by tests of feature-gated modules.
+#[cfg(test)]
+#[allow(dead_code)]
+pub(crate) mod test_utils;
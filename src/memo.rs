@@ -0,0 +1,201 @@
+//! SPL Memo tagging for convenience functions.
+//!
+//! Institutional integrators tie each deposit or withdrawal to an internal identifier (a
+//! transfer ID, a ticket number) by emitting an SPL Memo in the same instruction, so the tag
+//! lands in the exact transaction that moved the funds.
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+use crate::{
+    ClaimWithdraw, ClaimWithdrawContext, RequestWithdraw, RequestWithdrawContext,
+    try_from_claim_withdraw_context, try_from_request_withdraw_context,
+};
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+use crate::{Deposit, DepositContext, try_from_deposit_context};
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+use pinocchio::cpi::Signer;
+use pinocchio::{
+    AccountView, Address, ProgramResult, address::address_eq, cpi::invoke, error::ProgramError,
+    instruction::InstructionView,
+};
+
+pub const SPL_MEMO_PROGRAM_ID: Address = Address::new_from_array([
+    5, 74, 83, 90, 153, 41, 33, 6, 77, 36, 232, 113, 96, 218, 56, 124, 124, 53, 181, 221, 188, 146,
+    187, 129, 228, 31, 168, 64, 65, 5, 68, 141,
+]);
+
+/// Memo attached to an operation.
+pub struct Memo<'a> {
+    /// SPL Memo program
+    pub memo_program: &'a AccountView,
+    /// Caller-supplied tag written to the transaction logs (e.g. an internal transfer ID)
+    pub tag: &'a str,
+}
+
+impl Memo<'_> {
+    /// Emits `tag` through an SPL Memo CPI.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Memo logged
+    /// * `Err(ProgramError::IncorrectProgramId)` - `memo_program` is not the SPL Memo program
+    /// * `Err(ProgramError)` - CPI failure
+    ///
+    /// # Notes
+    /// * No signer accounts are attached, so the memo does not attest who emitted it; the
+    ///   enclosing transaction's signatures do.
+    pub fn emit(&self) -> ProgramResult {
+        if !address_eq(self.memo_program.address(), &SPL_MEMO_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let memo_ix = InstructionView {
+            program_id: self.memo_program.address(),
+            accounts: &[],
+            data: self.tag.as_bytes(),
+        };
+
+        invoke(&memo_ix, &[])
+    }
+}
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+/// Emits `memo`, when one is given.
+fn emit(memo: Option<&Memo>) -> ProgramResult {
+    match memo {
+        Some(memo) => memo.emit(),
+        None => Ok(()),
+    }
+}
+
+/// Convenience function: Parses accounts, deposits with PDA signing, and emits `memo`.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit
+/// * `signer_seeds` - Seeds for PDA signing
+/// * `memo` - Memo to emit after the deposit, if any
+///
+/// # Returns
+/// * `Ok(())` - Deposit executed and memo emitted
+/// * `Err(ProgramError)` - Parsing, the deposit or the memo CPI failed
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub fn deposit_signed_with_memo(
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+    memo: Option<&Memo>,
+) -> ProgramResult {
+    let ctx = try_from_deposit_context(accounts)?;
+    DepositContext::deposit_signed(&ctx, amount, signer_seeds)?;
+    emit(memo)
+}
+
+/// Convenience function: Parses accounts, requests a withdrawal with PDA signing, and emits
+/// `memo`.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `amount` - Amount of the position to withdraw
+/// * `signer_seeds` - Seeds for PDA signing
+/// * `memo` - Memo to emit after the request, if any
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn request_withdraw_signed_with_memo(
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+    memo: Option<&Memo>,
+) -> ProgramResult {
+    let ctx = try_from_request_withdraw_context(accounts)?;
+    RequestWithdrawContext::request_withdraw_signed(&ctx, amount, signer_seeds)?;
+    emit(memo)
+}
+
+/// Convenience function: Parses accounts, claims a matured withdrawal with PDA signing, and
+/// emits `memo`.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `signer_seeds` - Seeds for PDA signing
+/// * `memo` - Memo to emit after the claim, if any
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn claim_withdraw_signed_with_memo(
+    accounts: &[AccountView],
+    signer_seeds: &[Signer],
+    memo: Option<&Memo>,
+) -> ProgramResult {
+    let ctx = try_from_claim_withdraw_context(accounts)?;
+    ClaimWithdrawContext::claim_withdraw_signed(&ctx, signer_seeds)?;
+    emit(memo)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address},
+    };
+
+    fn emit_with(program: Address) -> ProgramResult {
+        let mut memo_program = TestAccount::empty(program);
+        Memo {
+            memo_program: &memo_program.view(),
+            tag: "transfer-42",
+        }
+        .emit()
+    }
+
+    #[test]
+    fn emits_only_through_the_memo_program() {
+        assert_eq!(emit_with(SPL_MEMO_PROGRAM_ID), Ok(()));
+        assert_eq!(emit_with(address(1)), Err(ProgramError::IncorrectProgramId));
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn deposits_abort_on_a_bad_memo_program() {
+        use crate::{programs::jupiter::tests::deposit_accounts, test_utils::views};
+
+        let mut accounts = deposit_accounts();
+        let mut memo_program = TestAccount::empty(address(1));
+        let accounts = views(&mut accounts);
+        let memo = Memo {
+            memo_program: &memo_program.view(),
+            tag: "transfer-42",
+        };
+
+        assert_eq!(deposit_signed_with_memo(&accounts, 100, &[], None), Ok(()));
+        assert_eq!(
+            deposit_signed_with_memo(&accounts, 100, &[], Some(&memo)),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+}
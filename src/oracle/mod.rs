@@ -0,0 +1,93 @@
+mod pyth;
+pub use pyth::*;
+
+mod switchboard;
+pub use switchboard::*;
+use {
+    crate::{BeethovenError, util::BPS_DENOMINATOR},
+    pinocchio::error::ProgramError,
+};
+
+/// A normalized oracle price: `price * 10^exponent` in the quote currency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i128,
+    pub conf: u128,
+    pub exponent: i32,
+}
+
+/// Validation thresholds applied when reading an oracle price.
+///
+/// # Notes
+/// * `max_age` is expressed in the unit native to each oracle: seconds for Pyth,
+///   slots for Switchboard.
+/// * `max_confidence_bps` bounds `conf / |price|` in basis points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleConfig {
+    pub max_age: u64,
+    pub max_confidence_bps: u64,
+}
+
+/// Fails with `StaleOracle` when `published` is more than `max_age` behind `now`.
+#[inline(always)]
+fn check_age(published: i128, now: i128, max_age: u64) -> Result<(), ProgramError> {
+    if now.saturating_sub(published) > max_age as i128 {
+        return Err(BeethovenError::StaleOracle.into());
+    }
+    Ok(())
+}
+
+impl OraclePrice {
+    /// Fails with `OracleConfidenceTooWide` when the confidence interval exceeds
+    /// `max_confidence_bps` of the absolute price.
+    #[inline(always)]
+    fn check_confidence(&self, max_confidence_bps: u64) -> Result<(), ProgramError> {
+        let bound = self
+            .price
+            .unsigned_abs()
+            .saturating_mul(max_confidence_bps as u128);
+        if self.conf.saturating_mul(BPS_DENOMINATOR as u128) > bound {
+            return Err(BeethovenError::OracleConfidenceTooWide.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(price: i128, conf: u128) -> OraclePrice {
+        OraclePrice {
+            price,
+            conf,
+            exponent: -8,
+        }
+    }
+
+    #[test]
+    fn ages_up_to_max_age_are_fresh() {
+        assert_eq!(check_age(100, 160, 60), Ok(()));
+        assert_eq!(
+            check_age(100, 161, 60),
+            Err(BeethovenError::StaleOracle.into())
+        );
+        // Prices published ahead of the local clock are not stale.
+        assert_eq!(check_age(161, 100, 60), Ok(()));
+    }
+
+    #[test]
+    fn confidence_is_bounded_relative_to_the_absolute_price() {
+        // 10 bps of 1_000_000 is 1_000.
+        assert_eq!(price(1_000_000, 1_000).check_confidence(10), Ok(()));
+        assert_eq!(price(-1_000_000, 1_000).check_confidence(10), Ok(()));
+        assert_eq!(
+            price(1_000_000, 1_001).check_confidence(10),
+            Err(BeethovenError::OracleConfidenceTooWide.into())
+        );
+        assert_eq!(
+            price(0, 1).check_confidence(10),
+            Err(BeethovenError::OracleConfidenceTooWide.into())
+        );
+    }
+}
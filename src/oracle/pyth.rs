@@ -0,0 +1,159 @@
+use {
+    super::{OracleConfig, OraclePrice, check_age},
+    crate::{
+        BeethovenError,
+        util::bytes::{check_discriminator, read_array},
+    },
+    pinocchio::{AccountView, Address, error::ProgramError},
+};
+
+/// Pyth Solana Receiver program, owner of `PriceUpdateV2` accounts.
+pub const PYTH_RECEIVER_PROGRAM_ID: Address = Address::new_from_array([
+    12, 183, 250, 187, 82, 247, 166, 72, 187, 91, 49, 125, 154, 1, 139, 144, 87, 203, 2, 71, 116,
+    250, 254, 1, 230, 196, 223, 152, 204, 56, 88, 129,
+]);
+
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Offset of the borsh `VerificationLevel` tag, right after the write authority.
+const VERIFICATION_LEVEL_OFFSET: usize = 8 + 32;
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+/// Reads a verified price from a Pyth `PriceUpdateV2` account.
+///
+/// # Arguments
+/// * `account` - The `PriceUpdateV2` account
+/// * `feed_id` - The expected Pyth price feed id
+/// * `config` - Staleness (in seconds) and confidence thresholds
+/// * `unix_timestamp` - The current `Clock::unix_timestamp`
+///
+/// # Returns
+/// * `Ok(OraclePrice)` - The price, confidence and exponent of the update
+/// * `Err(ProgramError)` - `InvalidOracle`, `StaleOracle` or `OracleConfidenceTooWide`
+///
+/// # Notes
+/// * Only fully verified updates are accepted.
+/// * Any feed can be posted to a receiver account, so the feed id must always be checked.
+pub fn read_pyth_price(
+    account: &AccountView,
+    feed_id: &[u8; 32],
+    config: &OracleConfig,
+    unix_timestamp: i64,
+) -> Result<OraclePrice, ProgramError> {
+    if !account.owned_by(&PYTH_RECEIVER_PROGRAM_ID) {
+        return Err(BeethovenError::InvalidOracle.into());
+    }
+
+    let data = account.try_borrow()?;
+    check_discriminator(&data, &PRICE_UPDATE_V2_DISCRIMINATOR)
+        .map_err(|_| BeethovenError::InvalidOracle)?;
+
+    // `VerificationLevel::Partial { num_signatures: u8 }` is encoded on two bytes.
+    let [level] = read_array::<1>(&data, VERIFICATION_LEVEL_OFFSET)?;
+    if level != VERIFICATION_LEVEL_FULL {
+        return Err(BeethovenError::InvalidOracle.into());
+    }
+
+    // PriceFeedMessage: feed_id, price, conf, exponent, publish_time, ...
+    let message = VERIFICATION_LEVEL_OFFSET + 1;
+    if read_array::<32>(&data, message)? != *feed_id {
+        return Err(BeethovenError::InvalidOracle.into());
+    }
+    let price = i64::from_le_bytes(read_array(&data, message + 32)?);
+    let conf = u64::from_le_bytes(read_array(&data, message + 40)?);
+    let exponent = i32::from_le_bytes(read_array(&data, message + 48)?);
+    let publish_time = i64::from_le_bytes(read_array(&data, message + 52)?);
+
+    check_age(publish_time as i128, unix_timestamp as i128, config.max_age)?;
+
+    let price = OraclePrice {
+        price: price as i128,
+        conf: conf as u128,
+        exponent,
+    };
+    price.check_confidence(config.max_confidence_bps)?;
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, account_data, address},
+    };
+
+    const FEED_ID: [u8; 32] = [9; 32];
+    const CONFIG: OracleConfig = OracleConfig {
+        max_age: 60,
+        max_confidence_bps: 10,
+    };
+    const MESSAGE: usize = VERIFICATION_LEVEL_OFFSET + 1;
+
+    /// A fully verified `PriceUpdateV2` of `FEED_ID` for `price`, published at `publish_time`.
+    fn price_update(price: i64, conf: u64, publish_time: i64) -> TestAccount {
+        // 8 + 32 + 1 + PriceFeedMessage (84) + posted_slot (8)
+        let mut data = account_data(&PRICE_UPDATE_V2_DISCRIMINATOR, 133);
+        data[VERIFICATION_LEVEL_OFFSET] = VERIFICATION_LEVEL_FULL;
+        data[MESSAGE..MESSAGE + 32].copy_from_slice(&FEED_ID);
+        data[MESSAGE + 32..MESSAGE + 40].copy_from_slice(&price.to_le_bytes());
+        data[MESSAGE + 40..MESSAGE + 48].copy_from_slice(&conf.to_le_bytes());
+        data[MESSAGE + 48..MESSAGE + 52].copy_from_slice(&(-8i32).to_le_bytes());
+        data[MESSAGE + 52..MESSAGE + 60].copy_from_slice(&publish_time.to_le_bytes());
+        TestAccount::new(address(1), PYTH_RECEIVER_PROGRAM_ID, &data)
+    }
+
+    fn read(account: &mut TestAccount, unix_timestamp: i64) -> Result<OraclePrice, ProgramError> {
+        read_pyth_price(&account.view(), &FEED_ID, &CONFIG, unix_timestamp)
+    }
+
+    #[test]
+    fn reads_a_fresh_verified_price() {
+        assert_eq!(
+            read(&mut price_update(6_000_000_000, 5_000_000, 1_000), 1_060),
+            Ok(OraclePrice {
+                price: 6_000_000_000,
+                conf: 5_000_000,
+                exponent: -8,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_stale_and_uncertain_prices() {
+        assert_eq!(
+            read(&mut price_update(6_000_000_000, 5_000_000, 1_000), 1_061),
+            Err(BeethovenError::StaleOracle.into())
+        );
+        // 10 bps of 60.00000000 is 0.06000000.
+        assert_eq!(
+            read(&mut price_update(6_000_000_000, 6_000_001, 1_000), 1_000),
+            Err(BeethovenError::OracleConfidenceTooWide.into())
+        );
+        assert!(read(&mut price_update(-6_000_000_000, 6_000_000, 1_000), 1_000).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_feeds_owners_and_partial_updates() {
+        let mut account = price_update(6_000_000_000, 0, 1_000);
+        assert_eq!(
+            read_pyth_price(&account.view(), &[8; 32], &CONFIG, 1_000),
+            Err(BeethovenError::InvalidOracle.into())
+        );
+
+        let mut data = account.view().try_borrow().unwrap().to_vec();
+        let mut foreign = TestAccount::new(address(1), address(2), &data);
+        assert_eq!(
+            read(&mut foreign, 1_000),
+            Err(BeethovenError::InvalidOracle.into())
+        );
+
+        // `VerificationLevel::Partial { num_signatures: 3 }`
+        data[VERIFICATION_LEVEL_OFFSET] = 0;
+        data[VERIFICATION_LEVEL_OFFSET + 1] = 3;
+        let mut partial = TestAccount::new(address(1), PYTH_RECEIVER_PROGRAM_ID, &data);
+        assert_eq!(
+            read(&mut partial, 1_000),
+            Err(BeethovenError::InvalidOracle.into())
+        );
+    }
+}
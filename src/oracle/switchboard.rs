@@ -0,0 +1,146 @@
+use {
+    super::{OracleConfig, OraclePrice, check_age},
+    crate::{
+        BeethovenError,
+        util::bytes::{check_discriminator, read_array, read_u64},
+    },
+    pinocchio::{AccountView, Address, error::ProgramError},
+};
+
+/// Switchboard On-Demand program, owner of `PullFeedAccountData` accounts.
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Address = Address::new_from_array([
+    6, 115, 189, 70, 242, 228, 126, 4, 241, 43, 217, 47, 183, 49, 150, 142, 205, 157, 151, 87, 194,
+    116, 218, 135, 71, 111, 70, 92, 4, 12, 101, 115,
+]);
+
+/// Switchboard results are fixed-point values with 18 decimals.
+pub const SWITCHBOARD_PRECISION_EXPONENT: i32 = -18;
+
+const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+// `CurrentResult` offsets inside `PullFeedAccountData` (discriminator included).
+const RESULT_VALUE_OFFSET: usize = 2264;
+const RESULT_STD_DEV_OFFSET: usize = 2280;
+const RESULT_SLOT_OFFSET: usize = 2368;
+
+/// Reads the current result from a Switchboard On-Demand pull feed.
+///
+/// # Arguments
+/// * `account` - The `PullFeedAccountData` account
+/// * `config` - Staleness (in slots) and confidence thresholds
+/// * `slot` - The current `Clock::slot`
+///
+/// # Returns
+/// * `Ok(OraclePrice)` - The value and standard deviation, with an exponent of `-18`
+/// * `Err(ProgramError)` - `InvalidOracle`, `StaleOracle` or `OracleConfidenceTooWide`
+///
+/// # Notes
+/// * The feed is identified by its address, which the caller must validate.
+/// * The standard deviation of the samples is used as the confidence interval.
+pub fn read_switchboard_price(
+    account: &AccountView,
+    config: &OracleConfig,
+    slot: u64,
+) -> Result<OraclePrice, ProgramError> {
+    if !account.owned_by(&SWITCHBOARD_ON_DEMAND_PROGRAM_ID) {
+        return Err(BeethovenError::InvalidOracle.into());
+    }
+
+    let data = account.try_borrow()?;
+    check_discriminator(&data, &PULL_FEED_DISCRIMINATOR)
+        .map_err(|_| BeethovenError::InvalidOracle)?;
+
+    let value = i128::from_le_bytes(read_array(&data, RESULT_VALUE_OFFSET)?);
+    let std_dev = i128::from_le_bytes(read_array(&data, RESULT_STD_DEV_OFFSET)?);
+    let result_slot = read_u64(&data, RESULT_SLOT_OFFSET)?;
+
+    // A feed that never received a result reports slot 0.
+    if result_slot == 0 {
+        return Err(BeethovenError::InvalidOracle.into());
+    }
+    check_age(result_slot as i128, slot as i128, config.max_age)?;
+
+    let price = OraclePrice {
+        price: value,
+        conf: std_dev.unsigned_abs(),
+        exponent: SWITCHBOARD_PRECISION_EXPONENT,
+    };
+    price.check_confidence(config.max_confidence_bps)?;
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, account_data, address},
+    };
+
+    const CONFIG: OracleConfig = OracleConfig {
+        max_age: 25,
+        max_confidence_bps: 50,
+    };
+    const ONE: i128 = 1_000_000_000_000_000_000;
+
+    /// A pull feed whose current result is `value` +/- `std_dev`, from `result_slot`.
+    fn pull_feed(value: i128, std_dev: i128, result_slot: u64) -> TestAccount {
+        let mut data = account_data(&PULL_FEED_DISCRIMINATOR, 3208);
+        data[RESULT_VALUE_OFFSET..RESULT_VALUE_OFFSET + 16].copy_from_slice(&value.to_le_bytes());
+        data[RESULT_STD_DEV_OFFSET..RESULT_STD_DEV_OFFSET + 16]
+            .copy_from_slice(&std_dev.to_le_bytes());
+        data[RESULT_SLOT_OFFSET..RESULT_SLOT_OFFSET + 8]
+            .copy_from_slice(&result_slot.to_le_bytes());
+        TestAccount::new(address(1), SWITCHBOARD_ON_DEMAND_PROGRAM_ID, &data)
+    }
+
+    fn read(account: &mut TestAccount, slot: u64) -> Result<OraclePrice, ProgramError> {
+        read_switchboard_price(&account.view(), &CONFIG, slot)
+    }
+
+    #[test]
+    fn reads_the_current_result() {
+        assert_eq!(
+            read(&mut pull_feed(150 * ONE, -ONE / 2, 1_000), 1_025),
+            Ok(OraclePrice {
+                price: 150 * ONE,
+                conf: ONE as u128 / 2,
+                exponent: SWITCHBOARD_PRECISION_EXPONENT,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_stale_uncertain_and_empty_feeds() {
+        assert_eq!(
+            read(&mut pull_feed(150 * ONE, 0, 1_000), 1_026),
+            Err(BeethovenError::StaleOracle.into())
+        );
+        // 50 bps of 150 is 0.75.
+        assert_eq!(
+            read(&mut pull_feed(150 * ONE, 3 * ONE / 4 + 1, 1_000), 1_000),
+            Err(BeethovenError::OracleConfidenceTooWide.into())
+        );
+        assert_eq!(
+            read(&mut pull_feed(150 * ONE, 0, 0), 0),
+            Err(BeethovenError::InvalidOracle.into())
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_accounts() {
+        let mut account = pull_feed(150 * ONE, 0, 1_000);
+        let mut data = account.view().try_borrow().unwrap().to_vec();
+        let mut foreign = TestAccount::new(address(1), address(2), &data);
+        assert_eq!(
+            read(&mut foreign, 1_000),
+            Err(BeethovenError::InvalidOracle.into())
+        );
+
+        data[0] ^= 1;
+        let mut other = TestAccount::new(address(1), SWITCHBOARD_ON_DEMAND_PROGRAM_ID, &data);
+        assert_eq!(
+            read(&mut other, 1_000),
+            Err(BeethovenError::InvalidOracle.into())
+        );
+    }
+}
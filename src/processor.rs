@@ -0,0 +1,378 @@
+//! Ready-made instruction processor for thin routing programs built on beethoven.
+//!
+//! Instruction data uses a compact layout:
+//!
+//! | bytes  | field                                   |
+//! |--------|-----------------------------------------|
+//! | 0      | op tag (see [`RouterOp`])               |
+//! | 1..9   | amount, little-endian `u64` (ignored by claims) |
+//! | 9      | bump of the router authority PDA        |
+//!
+//! The accounts start with the [`ROUTER_ACCOUNTS`] prefix authorizing the call, followed by
+//! the protocol accounts expected by the operation, in the same order as for the
+//! convenience functions (the first protocol account selects the protocol).
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+use crate::{
+    ClaimWithdraw, ClaimWithdrawContext, RequestWithdraw, RequestWithdrawContext,
+    try_from_claim_withdraw_context, try_from_request_withdraw_context,
+};
+use {
+    crate::{
+        AccountCheck, AccountError, AccountSpec, Deposit, DepositContext, try_from_deposit_context,
+        util::{bytes::read_array, create_program_address},
+    },
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::{Seed, Signer},
+        error::ProgramError,
+    },
+};
+
+/// Seed of the router authority PDA, signed for with the bump from the instruction data.
+pub const ROUTER_AUTHORITY_SEED: &[u8] = b"authority";
+
+/// Accounts preceding the protocol accounts of every router instruction.
+pub const ROUTER_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("config"),
+    AccountSpec::readonly_signer("operator"),
+    AccountSpec::readonly("authority"),
+];
+
+/// Router configuration, stored in an account owned by the router program.
+///
+/// # Layout
+/// | bytes  | field                                   |
+/// |--------|-----------------------------------------|
+/// | 0..32  | operator allowed to invoke the router   |
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouterConfig {
+    /// Only signer allowed to move funds held by the router authority
+    pub operator: Address,
+}
+
+impl RouterConfig {
+    /// Size of the serialized configuration.
+    pub const LEN: usize = 32;
+
+    /// Parses a configuration out of raw account data.
+    ///
+    /// # Returns
+    /// * `Ok(RouterConfig)` - Parsed configuration
+    /// * `Err(ProgramError::AccountDataTooSmall)` - `data` is shorter than `RouterConfig::LEN`
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            operator: Address::new_from_array(read_array::<32>(data, 0)?),
+        })
+    }
+}
+
+/// Length of a router instruction.
+pub const ROUTER_INSTRUCTION_LEN: usize = 10;
+
+/// Operations understood by [`process_instruction`].
+///
+/// Withdrawals are two-phase exits (`RequestWithdrawContext` / `ClaimWithdrawContext`), so
+/// they are only available with a venue that supports them (`drift`, `jito`, `marinade`).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouterOp {
+    Deposit = 0,
+    /// Opens a withdrawal of `amount`, in the venue's share or receipt units
+    #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+    RequestWithdraw = 1,
+    /// Collects a matured withdrawal; the amount is ignored
+    #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+    ClaimWithdraw = 2,
+}
+
+impl TryFrom<u8> for RouterOp {
+    type Error = ProgramError;
+
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        match tag {
+            0 => Ok(RouterOp::Deposit),
+            #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+            1 => Ok(RouterOp::RequestWithdraw),
+            #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+            2 => Ok(RouterOp::ClaimWithdraw),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Decoded router instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouterInstruction {
+    pub op: RouterOp,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl RouterInstruction {
+    /// Decodes a router instruction from raw instruction data.
+    ///
+    /// # Returns
+    /// * `Ok(RouterInstruction)` - Decoded instruction
+    /// * `Err(ProgramError::InvalidInstructionData)` - Wrong length or unknown op tag
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let data: &[u8; ROUTER_INSTRUCTION_LEN] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let mut amount = [0u8; 8];
+        amount.copy_from_slice(&data[1..9]);
+
+        Ok(Self {
+            op: RouterOp::try_from(data[0])?,
+            amount: u64::from_le_bytes(amount),
+            bump: data[9],
+        })
+    }
+}
+
+/// Entrypoint-compatible processor dispatching router instructions to beethoven.
+///
+/// Deploy it directly with `entrypoint!(process_instruction)`, or call it from your own
+/// entrypoint after custom checks.
+///
+/// # Arguments
+/// * `program_id` - The router program id
+/// * `accounts` - `ROUTER_ACCOUNTS`, then the protocol accounts for the operation
+/// * `instruction_data` - Router instruction data
+///
+/// # Returns
+/// * `Ok(())` - Operation executed
+/// * `Err(ProgramError::IllegalOwner)` - `config` is not owned by `program_id`
+/// * `Err(ProgramError::MissingRequiredSignature)` - `operator` did not sign
+/// * `Err(ProgramError::InvalidAccountData)` - `operator` is not the configured operator, or
+///   `authority` is not the `[ROUTER_AUTHORITY_SEED, bump]` PDA of `program_id`
+/// * `Err(ProgramError)` - Decoding, parsing or the CPI failed
+///
+/// # Notes
+/// * CPIs are signed with `[ROUTER_AUTHORITY_SEED, bump]`, so token accounts owned by the
+///   router authority PDA can be moved without a user signature; the operator check is
+///   what keeps arbitrary callers from spending them.
+pub fn process_instruction(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = RouterInstruction::unpack(instruction_data)?;
+
+    let [config, operator, authority, protocol_accounts @ ..] = accounts else {
+        return Err(AccountError::missing(ROUTER_ACCOUNTS, accounts.len()).into());
+    };
+    check_operator(program_id, config, operator)?;
+
+    let bump = [instruction.bump];
+    if !address_eq(
+        authority.address(),
+        &create_program_address(&[ROUTER_AUTHORITY_SEED, &bump], program_id)?,
+    ) {
+        return Err(AccountError::of(ROUTER_ACCOUNTS, "authority", AccountCheck::Key).into());
+    }
+
+    let seeds = [Seed::from(ROUTER_AUTHORITY_SEED), Seed::from(&bump)];
+    let signers = [Signer::from(&seeds)];
+
+    match instruction.op {
+        RouterOp::Deposit => {
+            let ctx = try_from_deposit_context(protocol_accounts)?;
+            DepositContext::deposit_signed(&ctx, instruction.amount, &signers)
+        }
+
+        #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+        RouterOp::RequestWithdraw => {
+            let ctx = try_from_request_withdraw_context(protocol_accounts)?;
+            RequestWithdrawContext::request_withdraw_signed(&ctx, instruction.amount, &signers)
+        }
+
+        #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+        RouterOp::ClaimWithdraw => {
+            let ctx = try_from_claim_withdraw_context(protocol_accounts)?;
+            ClaimWithdrawContext::claim_withdraw_signed(&ctx, &signers)
+        }
+    }
+}
+
+/// Checks that `operator` signed and is the operator recorded in the router's `config`.
+fn check_operator(
+    program_id: &Address,
+    config: &AccountView,
+    operator: &AccountView,
+) -> Result<(), ProgramError> {
+    if !config.owned_by(program_id) {
+        return Err(AccountError::of(ROUTER_ACCOUNTS, "config", AccountCheck::Owner).into());
+    }
+    if !operator.is_signer() {
+        return Err(AccountError::of(ROUTER_ACCOUNTS, "operator", AccountCheck::Signer).into());
+    }
+
+    let config = RouterConfig::from_bytes(&config.try_borrow()?)?;
+    if !address_eq(operator.address(), &config.operator) {
+        return Err(AccountError::of(ROUTER_ACCOUNTS, "operator", AccountCheck::Key).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            test_utils::{TestAccount, address, views},
+            util::find_program_address,
+        },
+        std::vec::Vec,
+    };
+
+    const PROGRAM_ID: Address = Address::new_from_array([7; 32]);
+
+    fn instruction_data(op: RouterOp, bump: u8) -> [u8; ROUTER_INSTRUCTION_LEN] {
+        let mut data = [0u8; ROUTER_INSTRUCTION_LEN];
+        data[0] = op as u8;
+        data[1..9].copy_from_slice(&1_000u64.to_le_bytes());
+        data[9] = bump;
+        data
+    }
+
+    fn deposit_data(bump: u8) -> [u8; ROUTER_INSTRUCTION_LEN] {
+        instruction_data(RouterOp::Deposit, bump)
+    }
+
+    /// Router accounts for a call by `operator`, configured with `address(1)` as operator.
+    fn router_accounts(config_owner: Address, operator: TestAccount) -> (Vec<TestAccount>, u8) {
+        let (authority, bump) = find_program_address(&[ROUTER_AUTHORITY_SEED], &PROGRAM_ID);
+        let accounts = [
+            TestAccount::new(address(9), config_owner, address(1).as_ref()),
+            operator,
+            TestAccount::empty(authority),
+        ];
+        (accounts.into(), bump)
+    }
+
+    #[test]
+    fn unpack_decodes_deposit() {
+        assert_eq!(
+            RouterInstruction::unpack(&deposit_data(254)),
+            Ok(RouterInstruction {
+                op: RouterOp::Deposit,
+                amount: 1_000,
+                bump: 254,
+            })
+        );
+        assert_eq!(
+            RouterInstruction::unpack(&[3; ROUTER_INSTRUCTION_LEN]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            RouterInstruction::unpack(&[0; 9]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn rejects_config_not_owned_by_router() {
+        let (mut accounts, bump) =
+            router_accounts(address(8), TestAccount::empty(address(1)).signer());
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &views(&mut accounts), &deposit_data(bump)),
+            Err(ProgramError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn rejects_operator_without_signature() {
+        let (mut accounts, bump) = router_accounts(PROGRAM_ID, TestAccount::empty(address(1)));
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &views(&mut accounts), &deposit_data(bump)),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_unauthorized_signer() {
+        let (mut accounts, bump) =
+            router_accounts(PROGRAM_ID, TestAccount::empty(address(2)).signer());
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &views(&mut accounts), &deposit_data(bump)),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn rejects_authority_that_is_not_the_router_pda() {
+        let (mut accounts, bump) =
+            router_accounts(PROGRAM_ID, TestAccount::empty(address(1)).signer());
+        accounts[2] = TestAccount::empty(address(3));
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &views(&mut accounts), &deposit_data(bump)),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn authorized_call_reaches_protocol_parsing() {
+        let (mut accounts, bump) =
+            router_accounts(PROGRAM_ID, TestAccount::empty(address(1)).signer());
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &views(&mut accounts), &deposit_data(bump)),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn rejects_missing_router_accounts() {
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &[], &deposit_data(255)),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+    fn withdrawals_share_the_operator_and_authority_checks() {
+        for op in [RouterOp::RequestWithdraw, RouterOp::ClaimWithdraw] {
+            assert_eq!(
+                RouterInstruction::unpack(&instruction_data(op, 254)).map(|ix| ix.op),
+                Ok(op)
+            );
+
+            let (mut accounts, bump) =
+                router_accounts(PROGRAM_ID, TestAccount::empty(address(2)).signer());
+            assert_eq!(
+                process_instruction(
+                    &PROGRAM_ID,
+                    &views(&mut accounts),
+                    &instruction_data(op, bump)
+                ),
+                Err(ProgramError::InvalidAccountData)
+            );
+
+            let (mut accounts, bump) =
+                router_accounts(PROGRAM_ID, TestAccount::empty(address(1)).signer());
+            accounts[2] = TestAccount::empty(address(3));
+            assert_eq!(
+                process_instruction(
+                    &PROGRAM_ID,
+                    &views(&mut accounts),
+                    &instruction_data(op, bump)
+                ),
+                Err(ProgramError::InvalidAccountData)
+            );
+
+            let (mut accounts, bump) =
+                router_accounts(PROGRAM_ID, TestAccount::empty(address(1)).signer());
+            assert_eq!(
+                process_instruction(
+                    &PROGRAM_ID,
+                    &views(&mut accounts),
+                    &instruction_data(op, bump)
+                ),
+                Err(ProgramError::NotEnoughAccountKeys)
+            );
+        }
+    }
+}
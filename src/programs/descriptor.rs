@@ -0,0 +1,171 @@
+use {
+    crate::{
+        DepositContext,
+        programs::{ProgramIds, Protocol},
+        try_from_deposit_context_for_with_ids,
+    },
+    pinocchio::{AccountView, error::ProgramError},
+};
+
+/// Compact, serializable record of a routing decision.
+///
+/// Stores which protocol a context belongs to and where its accounts sit in the
+/// instruction's account list, so a setup instruction can persist the decision (e.g. in a
+/// PDA) and a later execute instruction can rebuild the context without re-discriminating.
+///
+/// The byte layout matches the borsh encoding of a struct of three `u8` fields
+/// (`protocol`, `start`, `len`), with `protocol` holding the stable `Protocol` tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContextDescriptor {
+    /// Protocol the accounts are parsed as
+    pub protocol: Protocol,
+    /// Index of the context's first account (the protocol program)
+    pub start: u8,
+    /// Number of accounts in the context, trailing accounts included
+    pub len: u8,
+}
+
+impl ContextDescriptor {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 3;
+
+    pub const fn new(protocol: Protocol, start: u8, len: u8) -> Self {
+        Self {
+            protocol,
+            start,
+            len,
+        }
+    }
+
+    /// Serializes the descriptor into its borsh-compatible byte representation.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        [self.protocol as u8, self.start, self.len]
+    }
+
+    /// Deserializes a descriptor from the first `ContextDescriptor::LEN` bytes of `data`.
+    ///
+    /// # Returns
+    /// * `Ok(ContextDescriptor)` - Decoded descriptor
+    /// * `Err(ProgramError::InvalidInstructionData)` - Data too short, or unknown protocol tag
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let [protocol, start, len] = *data
+            .first_chunk::<{ Self::LEN }>()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(Self::new(Protocol::try_from(protocol)?, start, len))
+    }
+
+    /// Returns the accounts the descriptor points to.
+    ///
+    /// # Returns
+    /// * `Ok(&[AccountView])` - The described account range
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - The range is out of bounds
+    pub fn accounts<'info>(
+        &self,
+        accounts: &'info [AccountView],
+    ) -> Result<&'info [AccountView], ProgramError> {
+        let start = self.start as usize;
+        accounts
+            .get(start..start + self.len as usize)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
+}
+
+impl<'info> DepositContext<'info> {
+    /// Rebuilds a deposit context from a stored descriptor.
+    ///
+    /// # Arguments
+    /// * `descriptor` - Routing decision recorded by a previous instruction
+    /// * `accounts` - The current instruction's accounts
+    ///
+    /// # Returns
+    /// * `Ok(DepositContext)` - Typed context for the described protocol
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - The described range is out of bounds
+    /// * `Err(ProgramError)` - The described accounts do not parse as the protocol
+    pub fn from_descriptor(
+        descriptor: &ContextDescriptor,
+        accounts: &'info [AccountView],
+    ) -> Result<Self, ProgramError> {
+        Self::from_descriptor_with_ids(descriptor, accounts, &ProgramIds::MAINNET)
+    }
+
+    /// Same as `from_descriptor`, but validates against the provided `ProgramIds`.
+    pub fn from_descriptor_with_ids(
+        descriptor: &ContextDescriptor,
+        accounts: &'info [AccountView],
+        ids: &ProgramIds,
+    ) -> Result<Self, ProgramError> {
+        try_from_deposit_context_for_with_ids(
+            descriptor.protocol,
+            descriptor.accounts(accounts)?,
+            ids,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test_utils::*};
+
+    #[test]
+    fn bytes_round_trip() {
+        for &protocol in Protocol::ALL {
+            let descriptor = ContextDescriptor::new(protocol, 3, 19);
+            let bytes = descriptor.to_bytes();
+
+            assert_eq!(bytes, [protocol as u8, 3, 19]);
+            assert_eq!(ContextDescriptor::from_bytes(&bytes), Ok(descriptor));
+        }
+    }
+
+    #[test]
+    fn from_bytes_ignores_trailing_data() {
+        let protocol = Protocol::ALL[0];
+        assert_eq!(
+            ContextDescriptor::from_bytes(&[protocol as u8, 1, 2, 0xff]),
+            Ok(ContextDescriptor::new(protocol, 1, 2))
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_short_data_and_unknown_tags() {
+        assert_eq!(
+            ContextDescriptor::from_bytes(&[Protocol::ALL[0] as u8, 0]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            ContextDescriptor::from_bytes(&[u8::MAX, 0, 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn from_descriptor_parses_the_described_range() {
+        use crate::programs::jupiter::tests::deposit_accounts;
+
+        let mut accounts = deposit_accounts();
+        accounts.insert(0, TestAccount::empty(address(200)));
+        accounts.push(TestAccount::empty(address(201)));
+        let accounts = views(&mut accounts);
+
+        let descriptor = ContextDescriptor::new(Protocol::Jupiter, 1, 18);
+        let ctx = DepositContext::from_descriptor(&descriptor, &accounts).unwrap();
+        assert!(matches!(ctx, DepositContext::Jupiter(_)));
+
+        // Starting one account early puts a wallet where the program should be.
+        let shifted = ContextDescriptor::new(Protocol::Jupiter, 0, 18);
+        assert!(DepositContext::from_descriptor(&shifted, &accounts).is_err());
+    }
+
+    #[test]
+    fn accounts_rejects_out_of_bounds_ranges() {
+        let mut accounts = [TestAccount::empty(address(1))];
+        let accounts = views(&mut accounts);
+        let descriptor = ContextDescriptor::new(Protocol::ALL[0], 0, 2);
+        assert!(matches!(
+            descriptor.accounts(&accounts),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use {
+    crate::programs::{ProgramIds, Protocol},
+    pinocchio::{AccountView, error::ProgramError},
+};
+
+/// Strategy deciding which protocol a slice of accounts should be routed to.
+///
+/// Built-in strategies cover the common cases; integrators can implement this trait
+/// for custom routing schemes. Strategies are passed by generic so unused ones cost nothing.
+pub trait Detector {
+    /// Returns the protocol the accounts should be parsed as.
+    ///
+    /// # Arguments
+    /// * `accounts` - Slice of accounts about to be parsed
+    /// * `ids` - Program IDs of the enabled protocols
+    fn detect(&self, accounts: &[AccountView], ids: &ProgramIds) -> Result<Protocol, ProgramError>;
+}
+
+/// Detects the protocol from the first account, which must be the protocol program.
+///
+/// This is the default strategy used by `try_from_deposit_context`.
+pub struct ProgramIdDetector;
+
+impl Detector for ProgramIdDetector {
+    fn detect(&self, accounts: &[AccountView], ids: &ProgramIds) -> Result<Protocol, ProgramError> {
+        let detector_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        ids.protocol_of(detector_account.address())
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+/// Detects the protocol from the owner of the account at `index`.
+pub struct OwnerDetector {
+    /// Index of the account whose owner determines the protocol
+    pub index: usize,
+}
+
+impl Detector for OwnerDetector {
+    fn detect(&self, accounts: &[AccountView], ids: &ProgramIds) -> Result<Protocol, ProgramError> {
+        let detector_account = accounts
+            .get(self.index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        Protocol::ALL
+            .iter()
+            .copied()
+            .find(|protocol| detector_account.owned_by(ids.get(*protocol)))
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+/// Detects the protocol from a compact `u8` tag, typically read from instruction data.
+///
+/// Tags are the stable `Protocol` discriminants.
+pub struct TagDetector(pub u8);
+
+impl Detector for TagDetector {
+    fn detect(
+        &self,
+        _accounts: &[AccountView],
+        _ids: &ProgramIds,
+    ) -> Result<Protocol, ProgramError> {
+        Protocol::try_from(self.0)
+    }
+}
+
+/// Detects the protocol from an 8-byte discriminator, typically read from instruction data.
+///
+/// The caller supplies the discriminator-to-protocol table, so any encoding scheme works.
+pub struct DiscriminatorDetector<'a> {
+    /// Discriminator to look up
+    pub discriminator: [u8; 8],
+    /// Known discriminators and the protocol each one routes to
+    pub table: &'a [([u8; 8], Protocol)],
+}
+
+impl Detector for DiscriminatorDetector<'_> {
+    fn detect(
+        &self,
+        _accounts: &[AccountView],
+        _ids: &ProgramIds,
+    ) -> Result<Protocol, ProgramError> {
+        self.table
+            .iter()
+            .find(|(discriminator, _)| *discriminator == self.discriminator)
+            .map(|(_, protocol)| *protocol)
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+    };
+
+    const IDS: &ProgramIds = &ProgramIds::MAINNET;
+
+    #[test]
+    fn program_id_detector_reads_the_first_account() {
+        for &protocol in Protocol::ALL {
+            let mut accounts = [TestAccount::empty(IDS.get(protocol).clone())];
+            assert_eq!(
+                ProgramIdDetector.detect(&views(&mut accounts), IDS),
+                Ok(protocol)
+            );
+        }
+
+        let mut accounts = [TestAccount::empty(address(1))];
+        assert_eq!(
+            ProgramIdDetector.detect(&views(&mut accounts), IDS),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            ProgramIdDetector.detect(&[], IDS),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn owner_detector_reads_the_owner_at_its_index() {
+        let detector = OwnerDetector { index: 1 };
+        for &protocol in Protocol::ALL {
+            let mut accounts = [
+                TestAccount::empty(address(1)),
+                TestAccount::new(address(2), IDS.get(protocol).clone(), &[0; 8]),
+            ];
+            assert_eq!(detector.detect(&views(&mut accounts), IDS), Ok(protocol));
+        }
+
+        let mut accounts = [
+            TestAccount::empty(address(1)),
+            TestAccount::empty(address(2)),
+        ];
+        let accounts = views(&mut accounts);
+        assert_eq!(
+            detector.detect(&accounts, IDS),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            detector.detect(&accounts[..1], IDS),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn instruction_data_detectors_ignore_the_accounts() {
+        for &protocol in Protocol::ALL {
+            assert_eq!(TagDetector(protocol as u8).detect(&[], IDS), Ok(protocol));
+
+            let table = [([1; 8], protocol)];
+            let found = DiscriminatorDetector {
+                discriminator: [1; 8],
+                table: &table,
+            };
+            let unknown = DiscriminatorDetector {
+                discriminator: [2; 8],
+                table: &table,
+            };
+            assert_eq!(found.detect(&[], IDS), Ok(protocol));
+            assert_eq!(
+                unknown.detect(&[], IDS),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+}
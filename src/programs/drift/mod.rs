@@ -0,0 +1,569 @@
+mod state;
+pub use state::*;
+use {
+    crate::{
+        AccountCheck, AccountError, AccountSpec, Capacity, ClaimWithdraw, Deposit, Preview,
+        RequestWithdraw,
+        util::{
+            bytes::{TOKEN_ACCOUNT_AMOUNT_OFFSET, read_u64},
+            cpi::invoke_signed_with_remaining,
+        },
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+pub const DRIFT_PROGRAM_ID: Address = Address::new_from_array([
+    9, 84, 219, 190, 158, 201, 96, 201, 138, 122, 41, 63, 226, 19, 54, 150, 111, 225, 128, 209, 81,
+    174, 75, 129, 121, 86, 31, 137, 133, 74, 83, 246,
+]);
+const ADD_INSURANCE_FUND_STAKE_DISCRIMINATOR: [u8; 8] = [251, 144, 115, 11, 222, 47, 62, 236];
+const REQUEST_REMOVE_INSURANCE_FUND_STAKE_DISCRIMINATOR: [u8; 8] =
+    [142, 70, 204, 92, 73, 106, 180, 52];
+const REMOVE_INSURANCE_FUND_STAKE_DISCRIMINATOR: [u8; 8] = [128, 166, 142, 9, 254, 187, 143, 174];
+
+/// Account order expected by `DriftInsuranceFundStakeAccounts::try_from`.
+pub const DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("drift_program"),
+    AccountSpec::readonly("state"),
+    AccountSpec::writable("spot_market"),
+    AccountSpec::writable("insurance_fund_stake"),
+    AccountSpec::writable("user_stats"),
+    AccountSpec::readonly_signer("authority"),
+    AccountSpec::writable("spot_market_vault"),
+    AccountSpec::writable("insurance_fund_vault"),
+    AccountSpec::readonly("drift_signer"),
+    AccountSpec::writable("user_token_account"),
+    AccountSpec::readonly("token_program"),
+];
+
+/// Drift insurance fund staking integration
+pub struct DriftInsuranceFund;
+
+/// Account context for Drift's insurance fund stake instructions.
+///
+/// The same accounts serve `add_insurance_fund_stake` (via `Deposit`),
+/// `request_remove_insurance_fund_stake` and `remove_insurance_fund_stake`.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 11 accounts are present.
+pub struct DriftInsuranceFundStakeAccounts<'info> {
+    /// Drift Program
+    pub drift_program: &'info AccountView,
+    /// Drift global state
+    pub state: &'info AccountView,
+    /// Spot market whose insurance fund is staked into (writable)
+    pub spot_market: &'info AccountView,
+    /// The authority's insurance fund stake account (writable)
+    pub insurance_fund_stake: &'info AccountView,
+    /// The authority's user stats account (writable)
+    pub user_stats: &'info AccountView,
+    /// Owner of the stake (must be signer)
+    pub authority: &'info AccountView,
+    /// Spot market vault (writable)
+    pub spot_market_vault: &'info AccountView,
+    /// Insurance fund vault (writable)
+    pub insurance_fund_vault: &'info AccountView,
+    /// Drift signer PDA
+    pub drift_signer: &'info AccountView,
+    /// User's token account staked from or withdrawn to (writable)
+    pub user_token_account: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for DriftInsuranceFundStakeAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `DriftInsuranceFundStakeAccounts`.
+    ///
+    /// # Arguments
+    /// * `accounts` - Slice containing at least 11 accounts in the correct order
+    ///
+    /// # Returns
+    /// * `Ok(DriftInsuranceFundStakeAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 11 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `DRIFT_PROGRAM_ID`
+    ///
+    /// # Notes
+    /// * Extra accounts (e.g. Token-2022 mints) are captured in `remaining` and forwarded
+    ///   to the CPI
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &DRIFT_PROGRAM_ID)
+    }
+}
+
+impl<'info> DriftInsuranceFundStakeAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`
+    /// instead of `DRIFT_PROGRAM_ID` (e.g. for devnet or staging deployments).
+    ///
+    /// # Returns
+    /// * `Ok(DriftInsuranceFundStakeAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 11 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `program_id`
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            drift_program,
+            state,
+            spot_market,
+            insurance_fund_stake,
+            user_stats,
+            authority,
+            spot_market_vault,
+            insurance_fund_vault,
+            drift_signer,
+            user_token_account,
+            token_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(
+                AccountError::missing(DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS, accounts.len()).into(),
+            );
+        };
+
+        if !address_eq(drift_program.address(), program_id) {
+            return Err(AccountError::new(
+                DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            drift_program,
+            state,
+            spot_market,
+            insurance_fund_stake,
+            user_stats,
+            authority,
+            spot_market_vault,
+            insurance_fund_vault,
+            drift_signer,
+            user_token_account,
+            token_program,
+            remaining,
+        })
+    }
+
+    /// Reads the spot market index from the insurance fund stake account.
+    ///
+    /// # Returns
+    /// * `Ok(u16)` - Market index the stake belongs to
+    /// * `Err(ProgramError::IllegalOwner)` - Stake account is not owned by the Drift program
+    /// * `Err(ProgramError)` - Stake data could not be read
+    pub fn market_index(&self) -> Result<u16, ProgramError> {
+        if !self
+            .insurance_fund_stake
+            .owned_by(self.drift_program.address())
+        {
+            return Err(AccountError::of(
+                DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                "insurance_fund_stake",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = self.insurance_fund_stake.try_borrow()?;
+        Ok(DriftInsuranceFundStakeState::from_bytes(&data)?.market_index)
+    }
+}
+
+impl<'info> Deposit<'info> for DriftInsuranceFund {
+    type Accounts = DriftInsuranceFundStakeAccounts<'info>;
+
+    /// Stakes `amount` into the spot market's insurance fund via `add_insurance_fund_stake`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `DriftInsuranceFundStakeAccounts`)
+    /// * `amount` - Amount of tokens to stake
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    ///
+    /// # Returns
+    /// * `Ok(())` - Stake completed successfully
+    /// * `Err(ProgramError)` - Invalid accounts or CPI failure
+    fn deposit_signed(
+        ctx: &DriftInsuranceFundStakeAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let market_index = ctx.market_index()?;
+
+        let accounts = [
+            InstructionAccount::readonly(ctx.state.address()),
+            InstructionAccount::writable(ctx.spot_market.address()),
+            InstructionAccount::writable(ctx.insurance_fund_stake.address()),
+            InstructionAccount::writable(ctx.user_stats.address()),
+            InstructionAccount::readonly_signer(ctx.authority.address()),
+            InstructionAccount::writable(ctx.spot_market_vault.address()),
+            InstructionAccount::writable(ctx.insurance_fund_vault.address()),
+            InstructionAccount::readonly(ctx.drift_signer.address()),
+            InstructionAccount::writable(ctx.user_token_account.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.state,
+            ctx.spot_market,
+            ctx.insurance_fund_stake,
+            ctx.user_stats,
+            ctx.authority,
+            ctx.spot_market_vault,
+            ctx.insurance_fund_vault,
+            ctx.drift_signer,
+            ctx.user_token_account,
+            ctx.token_program,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + market_index (2 bytes) + amount (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 18]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(ADD_INSURANCE_FUND_STAKE_DISCRIMINATOR.as_ptr(), ptr, 8);
+            core::ptr::copy_nonoverlapping(market_index.to_le_bytes().as_ptr(), ptr.add(8), 2);
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(10), 8);
+        }
+
+        let stake_ix = InstructionView {
+            program_id: ctx.drift_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 18)
+            },
+        };
+
+        invoke_signed_with_remaining(&stake_ix, &account_infos, ctx.remaining, signer_seeds)?;
+
+        Ok(())
+    }
+
+    fn deposit(ctx: &DriftInsuranceFundStakeAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::deposit_signed(ctx, amount, &[])
+    }
+}
+
+impl DriftInsuranceFund {
+    /// Starts the unstaking period for `amount` of staked tokens via
+    /// `request_remove_insurance_fund_stake`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `DriftInsuranceFundStakeAccounts`)
+    /// * `amount` - Amount of tokens to unstake
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    pub fn request_remove_signed(
+        ctx: &DriftInsuranceFundStakeAccounts,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let market_index = ctx.market_index()?;
+
+        let accounts = [
+            InstructionAccount::writable(ctx.spot_market.address()),
+            InstructionAccount::writable(ctx.insurance_fund_stake.address()),
+            InstructionAccount::writable(ctx.user_stats.address()),
+            InstructionAccount::readonly_signer(ctx.authority.address()),
+            InstructionAccount::writable(ctx.insurance_fund_vault.address()),
+        ];
+
+        let account_infos = [
+            ctx.spot_market,
+            ctx.insurance_fund_stake,
+            ctx.user_stats,
+            ctx.authority,
+            ctx.insurance_fund_vault,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + market_index (2 bytes) + amount (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 18]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(
+                REQUEST_REMOVE_INSURANCE_FUND_STAKE_DISCRIMINATOR.as_ptr(),
+                ptr,
+                8,
+            );
+            core::ptr::copy_nonoverlapping(market_index.to_le_bytes().as_ptr(), ptr.add(8), 2);
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(10), 8);
+        }
+
+        let request_ix = InstructionView {
+            program_id: ctx.drift_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 18)
+            },
+        };
+
+        invoke_signed_with_remaining(&request_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    /// Starts the unstaking period without signing (user is direct signer).
+    pub fn request_remove(ctx: &DriftInsuranceFundStakeAccounts, amount: u64) -> ProgramResult {
+        Self::request_remove_signed(ctx, amount, &[])
+    }
+
+    /// Withdraws a previously requested unstake once its unstaking period has elapsed, via
+    /// `remove_insurance_fund_stake`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `DriftInsuranceFundStakeAccounts`)
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    pub fn remove_signed(
+        ctx: &DriftInsuranceFundStakeAccounts,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let market_index = ctx.market_index()?;
+
+        let accounts = [
+            InstructionAccount::readonly(ctx.state.address()),
+            InstructionAccount::writable(ctx.spot_market.address()),
+            InstructionAccount::writable(ctx.insurance_fund_stake.address()),
+            InstructionAccount::writable(ctx.user_stats.address()),
+            InstructionAccount::readonly_signer(ctx.authority.address()),
+            InstructionAccount::writable(ctx.insurance_fund_vault.address()),
+            InstructionAccount::readonly(ctx.drift_signer.address()),
+            InstructionAccount::writable(ctx.user_token_account.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.state,
+            ctx.spot_market,
+            ctx.insurance_fund_stake,
+            ctx.user_stats,
+            ctx.authority,
+            ctx.insurance_fund_vault,
+            ctx.drift_signer,
+            ctx.user_token_account,
+            ctx.token_program,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + market_index (2 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 10]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(
+                REMOVE_INSURANCE_FUND_STAKE_DISCRIMINATOR.as_ptr(),
+                ptr,
+                8,
+            );
+            core::ptr::copy_nonoverlapping(market_index.to_le_bytes().as_ptr(), ptr.add(8), 2);
+        }
+
+        let remove_ix = InstructionView {
+            program_id: ctx.drift_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 10)
+            },
+        };
+
+        invoke_signed_with_remaining(&remove_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    /// Withdraws a matured unstake without signing (user is direct signer).
+    pub fn remove(ctx: &DriftInsuranceFundStakeAccounts) -> ProgramResult {
+        Self::remove_signed(ctx, &[])
+    }
+}
+
+impl<'info> RequestWithdraw<'info> for DriftInsuranceFund {
+    type Accounts = DriftInsuranceFundStakeAccounts<'info>;
+
+    /// Starts the insurance fund cooldown for `amount` (see `request_remove_signed`).
+    fn request_withdraw_signed(
+        ctx: &DriftInsuranceFundStakeAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        Self::request_remove_signed(ctx, amount, signer_seeds)
+    }
+
+    fn request_withdraw(
+        ctx: &DriftInsuranceFundStakeAccounts<'info>,
+        amount: u64,
+    ) -> ProgramResult {
+        Self::request_remove_signed(ctx, amount, &[])
+    }
+}
+
+impl<'info> ClaimWithdraw<'info> for DriftInsuranceFund {
+    type Accounts = DriftInsuranceFundStakeAccounts<'info>;
+
+    /// Withdraws the matured unstake request (see `remove_signed`).
+    fn claim_withdraw_signed(
+        ctx: &DriftInsuranceFundStakeAccounts<'info>,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        Self::remove_signed(ctx, signer_seeds)
+    }
+
+    fn claim_withdraw(ctx: &DriftInsuranceFundStakeAccounts<'info>) -> ProgramResult {
+        Self::remove_signed(ctx, &[])
+    }
+}
+
+impl<'info> Preview<'info> for DriftInsuranceFund {
+    type Accounts = DriftInsuranceFundStakeAccounts<'info>;
+
+    /// Returns the insurance fund shares expected for staking `amount`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Expected insurance fund shares
+    /// * `Err(ProgramError::IllegalOwner)` - Spot market is not owned by the Drift program
+    /// * `Err(ProgramError)` - Spot market or vault data could not be read
+    fn preview_deposit(
+        ctx: &DriftInsuranceFundStakeAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.spot_market.owned_by(ctx.drift_program.address()) {
+            return Err(AccountError::of(
+                DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                "spot_market",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let vault_amount = read_u64(
+            &ctx.insurance_fund_vault.try_borrow()?,
+            TOKEN_ACCOUNT_AMOUNT_OFFSET,
+        )?;
+        let data = ctx.spot_market.try_borrow()?;
+        DriftSpotMarketState::from_bytes(&data)?.amount_to_if_shares(amount, vault_amount)
+    }
+}
+
+impl<'info> Capacity<'info> for DriftInsuranceFund {
+    type Accounts = DriftInsuranceFundStakeAccounts<'info>;
+
+    /// Always succeeds: Drift insurance funds carry no stake cap.
+    fn check_capacity(
+        _ctx: &DriftInsuranceFundStakeAccounts<'info>,
+        _amount: u64,
+    ) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Always `u64::MAX`: Drift insurance funds carry no stake cap.
+    fn available_deposit_capacity(
+        _ctx: &DriftInsuranceFundStakeAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        Ok(u64::MAX)
+    }
+
+    /// Returns the token balance of the insurance fund vault, which unstakes are paid from.
+    fn available_liquidity_for_withdraw(
+        ctx: &DriftInsuranceFundStakeAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        read_u64(
+            &ctx.insurance_fund_vault.try_borrow()?,
+            TOKEN_ACCOUNT_AMOUNT_OFFSET,
+        )
+    }
+
+    /// Always `true`: insurance fund pauses are not decoded; a paused fund surfaces as a
+    /// CPI failure.
+    fn is_operational(_ctx: &DriftInsuranceFundStakeAccounts<'info>) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{state::tests::*, *},
+        crate::test_utils::*,
+        std::vec::Vec,
+    };
+
+    // Indices into `DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS`.
+    const SPOT_MARKET: usize = 2;
+    const INSURANCE_FUND_STAKE: usize = 3;
+    const INSURANCE_FUND_VAULT: usize = 7;
+
+    fn stake_accounts(vault_amount: u64) -> Vec<TestAccount> {
+        let mut vault = [0u8; 165];
+        vault[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&vault_amount.to_le_bytes());
+
+        (0..DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS.len() as u8)
+            .map(|i| match i as usize {
+                0 => TestAccount::empty(DRIFT_PROGRAM_ID),
+                SPOT_MARKET => {
+                    TestAccount::new(address(i), DRIFT_PROGRAM_ID, &spot_market_data(2_000))
+                }
+                INSURANCE_FUND_STAKE => {
+                    TestAccount::new(address(i), DRIFT_PROGRAM_ID, &insurance_fund_stake_data(7))
+                }
+                INSURANCE_FUND_VAULT => TestAccount::new(address(i), address(99), &vault),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_the_market_index_and_previews_shares() {
+        let mut accounts = stake_accounts(1_000);
+        let accounts = views(&mut accounts);
+        let ctx = DriftInsuranceFundStakeAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(ctx.market_index(), Ok(7));
+        assert_eq!(DriftInsuranceFund::preview_deposit(&ctx, 500), Ok(1_000));
+        assert_eq!(
+            DriftInsuranceFund::available_liquidity_for_withdraw(&ctx),
+            Ok(1_000)
+        );
+    }
+
+    #[test]
+    fn rejects_state_owned_by_another_program() {
+        let mut accounts = stake_accounts(1_000);
+        accounts[SPOT_MARKET] = TestAccount::new(
+            address(SPOT_MARKET as u8),
+            address(42),
+            &spot_market_data(2_000),
+        );
+        accounts[INSURANCE_FUND_STAKE] = TestAccount::new(
+            address(INSURANCE_FUND_STAKE as u8),
+            address(42),
+            &insurance_fund_stake_data(7),
+        );
+        let accounts = views(&mut accounts);
+        let ctx = DriftInsuranceFundStakeAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(ctx.market_index(), Err(ProgramError::IllegalOwner));
+        assert_eq!(
+            DriftInsuranceFund::preview_deposit(&ctx, 500),
+            Err(ProgramError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn parse_checks_the_program_and_account_count() {
+        let mut accounts = stake_accounts(0);
+        let views = views(&mut accounts);
+        assert!(matches!(
+            DriftInsuranceFundStakeAccounts::try_from(&views[..10]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+        assert!(matches!(
+            DriftInsuranceFundStakeAccounts::try_from_with_program_id(&views, &address(42)),
+            Err(ProgramError::IncorrectProgramId)
+        ));
+    }
+}
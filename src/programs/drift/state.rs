@@ -0,0 +1,165 @@
+use {
+    crate::util::{Rounding, bytes::*, mul_div, to_u64},
+    pinocchio::error::ProgramError,
+};
+
+const INSURANCE_FUND_STAKE_DISCRIMINATOR: [u8; 8] = [110, 202, 14, 42, 95, 73, 90, 95];
+const SPOT_MARKET_DISCRIMINATOR: [u8; 8] = [100, 177, 8, 107, 168, 65, 65, 39];
+
+// Byte offsets into Drift's zero-copy `InsuranceFundStake` account (discriminator included).
+const INSURANCE_FUND_STAKE_MARKET_INDEX_OFFSET: usize = 120;
+
+// Byte offsets into Drift's zero-copy `SpotMarket` account (discriminator included).
+const SPOT_MARKET_INSURANCE_FUND_TOTAL_SHARES_OFFSET: usize = 336;
+
+/// Subset of Drift's `InsuranceFundStake` account needed to build instructions.
+pub struct DriftInsuranceFundStakeState {
+    /// Spot market whose insurance fund the stake belongs to
+    pub market_index: u16,
+}
+
+impl DriftInsuranceFundStakeState {
+    /// Parses the relevant fields out of raw `InsuranceFundStake` account data.
+    ///
+    /// # Returns
+    /// * `Ok(DriftInsuranceFundStakeState)` - Parsed stake state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Drift `InsuranceFundStake`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &INSURANCE_FUND_STAKE_DISCRIMINATOR)?;
+
+        Ok(Self {
+            market_index: u16::from_le_bytes(read_array(
+                data,
+                INSURANCE_FUND_STAKE_MARKET_INDEX_OFFSET,
+            )?),
+        })
+    }
+}
+
+/// Subset of Drift's `SpotMarket` account needed to value insurance fund stakes.
+pub struct DriftSpotMarketState {
+    /// Total shares issued by the market's insurance fund
+    pub insurance_fund_total_shares: u128,
+}
+
+impl DriftSpotMarketState {
+    /// Parses the relevant fields out of raw `SpotMarket` account data.
+    ///
+    /// # Returns
+    /// * `Ok(DriftSpotMarketState)` - Parsed spot market state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Drift `SpotMarket`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &SPOT_MARKET_DISCRIMINATOR)?;
+
+        Ok(Self {
+            insurance_fund_total_shares: read_u128(
+                data,
+                SPOT_MARKET_INSURANCE_FUND_TOTAL_SHARES_OFFSET,
+            )?,
+        })
+    }
+
+    /// Insurance fund shares minted for staking `amount`, rounded down.
+    ///
+    /// Mirrors Drift's share math: 1:1 for an empty fund, otherwise
+    /// `total_shares / vault_balance`.
+    ///
+    /// # Arguments
+    /// * `amount` - Amount of tokens to stake
+    /// * `vault_amount` - Current balance of the insurance fund vault
+    pub fn amount_to_if_shares(&self, amount: u64, vault_amount: u64) -> Result<u64, ProgramError> {
+        if self.insurance_fund_total_shares == 0 || vault_amount == 0 {
+            return Ok(amount);
+        }
+
+        to_u64(mul_div(
+            amount as u128,
+            self.insurance_fund_total_shares,
+            vault_amount as u128,
+            Rounding::Down,
+        )?)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {
+        super::*,
+        crate::test_utils::{PROPERTY_CASES, Rng, account_data},
+        std::vec::Vec,
+    };
+
+    /// `InsuranceFundStake` bytes of spot market `market_index`.
+    pub(crate) fn insurance_fund_stake_data(market_index: u16) -> Vec<u8> {
+        let mut data = account_data(&INSURANCE_FUND_STAKE_DISCRIMINATOR, 136);
+        data[INSURANCE_FUND_STAKE_MARKET_INDEX_OFFSET
+            ..INSURANCE_FUND_STAKE_MARKET_INDEX_OFFSET + 2]
+            .copy_from_slice(&market_index.to_le_bytes());
+        data
+    }
+
+    /// `SpotMarket` bytes whose insurance fund issued `total_shares`.
+    pub(crate) fn spot_market_data(total_shares: u128) -> Vec<u8> {
+        let mut data = account_data(&SPOT_MARKET_DISCRIMINATOR, 776);
+        data[SPOT_MARKET_INSURANCE_FUND_TOTAL_SHARES_OFFSET
+            ..SPOT_MARKET_INSURANCE_FUND_TOTAL_SHARES_OFFSET + 16]
+            .copy_from_slice(&total_shares.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn stake_reads_its_market_index() {
+        let stake =
+            DriftInsuranceFundStakeState::from_bytes(&insurance_fund_stake_data(3)).unwrap();
+        assert_eq!(stake.market_index, 3);
+    }
+
+    #[test]
+    fn parsers_reject_other_and_short_accounts() {
+        assert!(matches!(
+            DriftInsuranceFundStakeState::from_bytes(&spot_market_data(0)),
+            Err(ProgramError::InvalidAccountData)
+        ));
+        assert!(matches!(
+            DriftSpotMarketState::from_bytes(&insurance_fund_stake_data(0)),
+            Err(ProgramError::InvalidAccountData)
+        ));
+        assert!(matches!(
+            DriftSpotMarketState::from_bytes(&spot_market_data(0)[..340]),
+            Err(ProgramError::AccountDataTooSmall)
+        ));
+    }
+
+    #[test]
+    fn shares_are_minted_pro_rata_and_rounded_down() {
+        let market = DriftSpotMarketState::from_bytes(&spot_market_data(1_000_000)).unwrap();
+        // 1,000,000 shares backed by 1,500,000 tokens.
+        assert_eq!(market.amount_to_if_shares(1_000, 1_500_000), Ok(666));
+        assert_eq!(market.amount_to_if_shares(0, 1_500_000), Ok(0));
+    }
+
+    #[test]
+    fn empty_funds_mint_one_share_per_token() {
+        let empty = DriftSpotMarketState::from_bytes(&spot_market_data(0)).unwrap();
+        assert_eq!(empty.amount_to_if_shares(1_000, 1_500_000), Ok(1_000));
+
+        let drained = DriftSpotMarketState::from_bytes(&spot_market_data(1_000_000)).unwrap();
+        assert_eq!(drained.amount_to_if_shares(1_000, 0), Ok(1_000));
+    }
+
+    #[test]
+    fn minted_shares_are_never_worth_more_than_the_stake() {
+        let mut rng = Rng::new(15);
+        for _ in 0..PROPERTY_CASES {
+            let total_shares = rng.amount().max(1) as u128;
+            let market = DriftSpotMarketState::from_bytes(&spot_market_data(total_shares)).unwrap();
+            let (x, vault_amount) = (rng.amount(), rng.amount().max(1));
+
+            if let Ok(shares) = market.amount_to_if_shares(x, vault_amount) {
+                assert!(shares as u128 * vault_amount as u128 <= x as u128 * total_shares);
+            }
+        }
+    }
+}
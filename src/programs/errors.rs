@@ -0,0 +1,140 @@
+use {
+    crate::{
+        BeethovenError,
+        programs::Protocol,
+        util::{log::LogLine, metrics::metered},
+    },
+    pinocchio::{ProgramResult, error::ProgramError},
+};
+
+/// Returns the error translation table of `protocol`.
+///
+/// Protocols without a table yet return an empty slice.
+pub fn error_table(protocol: Protocol) -> &'static [(u32, BeethovenError)] {
+    match protocol {
+        #[cfg(feature = "kamino")]
+        Protocol::Kamino => crate::programs::kamino::KAMINO_ERROR_TABLE,
+
+        #[cfg(feature = "jupiter")]
+        Protocol::Jupiter => crate::programs::jupiter::JUPITER_ERROR_TABLE,
+
+        #[cfg(feature = "jito")]
+        Protocol::Jito => crate::programs::jito::JITO_ERROR_TABLE,
+
+        #[allow(unreachable_patterns)]
+        _ => &[],
+    }
+}
+
+/// Translates a protocol's custom error code into a unified beethoven error.
+///
+/// # Returns
+/// * `Some(BeethovenError)` - The code has a unified meaning
+/// * `None` - The code is unknown for this protocol
+///
+/// # Notes
+/// * A CPI that fails aborts the whole transaction, so on-chain callers only see errors the
+///   protocol returns without aborting. The tables are primarily meant for monitoring and
+///   off-chain clients decoding failed transactions.
+pub fn translate_error(protocol: Protocol, code: u32) -> Option<BeethovenError> {
+    error_table(protocol)
+        .iter()
+        .find(|(protocol_code, _)| *protocol_code == code)
+        .map(|(_, error)| *error)
+}
+
+/// Re-wraps a protocol `ProgramError` into its unified beethoven error, logging the mapping.
+///
+/// Errors without a translation are returned unchanged.
+pub fn rewrap_error(protocol: Protocol, error: ProgramError) -> ProgramError {
+    let ProgramError::Custom(code) = error else {
+        return error;
+    };
+    let Some(unified) = translate_error(protocol, code) else {
+        return error;
+    };
+
+    LogLine::new()
+        .push(b"beethoven: protocol ")
+        .push(protocol.name().as_bytes())
+        .push(b" error ")
+        .push_u64(code as u64)
+        .push(b": ")
+        .push(unified.name().as_bytes())
+        .log();
+    unified.into()
+}
+
+/// Runs a protocol CPI under `metered`, re-wrapping a translatable failure with
+/// `rewrap_error`.
+///
+/// Used by the context dispatchers, so every protocol call reports unified errors.
+#[inline(always)]
+pub(crate) fn dispatch(protocol: Protocol, op: impl FnOnce() -> ProgramResult) -> ProgramResult {
+    metered(protocol.name(), op).map_err(|error| rewrap_error(protocol, error))
+}
+
+#[cfg(all(test, any(feature = "jito", feature = "jupiter", feature = "kamino")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "kamino")]
+    #[test]
+    fn kamino_codes_translate() {
+        assert_eq!(
+            translate_error(Protocol::Kamino, 6091),
+            Some(BeethovenError::DepositCapExceeded)
+        );
+        assert_eq!(
+            translate_error(Protocol::Kamino, 6043),
+            Some(BeethovenError::VenuePaused)
+        );
+        assert_eq!(translate_error(Protocol::Kamino, 6000), None);
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn jupiter_codes_translate() {
+        assert_eq!(
+            translate_error(Protocol::Jupiter, 6042),
+            Some(BeethovenError::VenuePaused)
+        );
+        assert_eq!(
+            translate_error(Protocol::Jupiter, 6028),
+            Some(BeethovenError::InsufficientLiquidity)
+        );
+        assert_eq!(translate_error(Protocol::Jupiter, 6000), None);
+    }
+
+    #[cfg(feature = "jito")]
+    #[test]
+    fn jito_codes_translate() {
+        assert_eq!(
+            translate_error(Protocol::Jito, 1030),
+            Some(BeethovenError::WithdrawalNotReady)
+        );
+        assert_eq!(
+            translate_error(Protocol::Jito, 1051),
+            Some(BeethovenError::VenuePaused)
+        );
+        assert_eq!(translate_error(Protocol::Jito, 6000), None);
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn dispatch_rewraps_translated_failures() {
+        assert_eq!(
+            dispatch(Protocol::Jupiter, || Err(ProgramError::Custom(6027))),
+            Err(BeethovenError::VenuePaused.into())
+        );
+        assert_eq!(
+            dispatch(Protocol::Jupiter, || Err(ProgramError::Custom(6000))),
+            Err(ProgramError::Custom(6000))
+        );
+        assert_eq!(
+            dispatch(Protocol::Jupiter, || Err(ProgramError::InvalidArgument)),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(dispatch(Protocol::Jupiter, || Ok(())), Ok(()));
+    }
+}
@@ -0,0 +1,212 @@
+use {
+    crate::{
+        BeethovenError, DepositContext,
+        programs::{Detector, ProgramIdDetector, ProgramIds, Protocol},
+        try_from_deposit_context_for_with_ids,
+        util::{
+            ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID,
+            TOKEN_PROGRAM_ID, log::LogLine,
+        },
+    },
+    pinocchio::{AccountView, Address, ProgramResult, address::address_eq, error::ProgramError},
+};
+
+/// Programs every protocol may invoke: token programs, associated token and system programs.
+pub const TRUSTED_PROGRAMS: &[Address] = &[
+    TOKEN_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    SYSTEM_PROGRAM_ID,
+];
+
+/// Checks that every executable account in `accounts` is a trusted program.
+///
+/// Routers forwarding client-supplied accounts into a CPI should run this so a swapped-in
+/// program cannot be reached through the protocol's trailing or optional accounts.
+/// Sysvars and data accounts are not executable and always pass.
+///
+/// # Arguments
+/// * `accounts` - Accounts about to be forwarded
+/// * `protocol_program` - Program ID of the protocol being invoked
+/// * `extra` - Additional programs the protocol relies on (e.g. Kamino Farms)
+///
+/// # Returns
+/// * `Ok(())` - Every executable account is `protocol_program`, in `TRUSTED_PROGRAMS` or in `extra`
+/// * `Err(BeethovenError::UntrustedProgram)` - Another executable account was found; its index
+///   is logged
+pub fn check_executable_accounts(
+    accounts: &[AccountView],
+    protocol_program: &Address,
+    extra: &[Address],
+) -> ProgramResult {
+    check_executables(accounts, |address| {
+        address_eq(address, protocol_program) || listed(address, extra)
+    })
+}
+
+/// Same as `check_executable_accounts`, but also trusts the programs `protocol` invokes
+/// itself (see `ProgramIds::dependency_programs`), so callers only list their own additions
+/// in `extra`.
+///
+/// # Returns
+/// * `Ok(())` - Every executable account is the protocol, one of its dependencies, in
+///   `TRUSTED_PROGRAMS` or in `extra`
+/// * `Err(BeethovenError::UntrustedProgram)` - Another executable account was found; its index
+///   is logged
+pub fn check_protocol_executable_accounts(
+    accounts: &[AccountView],
+    protocol: Protocol,
+    ids: &ProgramIds,
+    extra: &[Address],
+) -> ProgramResult {
+    let dependencies = ids.dependency_programs(protocol);
+    check_executables(accounts, |address| {
+        address_eq(address, ids.get(protocol))
+            || listed(address, dependencies)
+            || listed(address, extra)
+    })
+}
+
+/// Whether `address` is in `TRUSTED_PROGRAMS` or `programs`.
+fn listed(address: &Address, programs: &[Address]) -> bool {
+    TRUSTED_PROGRAMS
+        .iter()
+        .chain(programs)
+        .any(|program| address_eq(address, program))
+}
+
+/// Rejects the first executable account `trusted` does not accept, logging its index.
+fn check_executables(
+    accounts: &[AccountView],
+    trusted: impl Fn(&Address) -> bool,
+) -> ProgramResult {
+    match accounts
+        .iter()
+        .position(|account| account.executable() && !trusted(account.address()))
+    {
+        Some(index) => {
+            LogLine::new()
+                .push(b"beethoven: account #")
+                .push_u64(index as u64)
+                .push(b": untrusted program")
+                .log();
+            Err(BeethovenError::UntrustedProgram.into())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Same as `try_from_deposit_context_with`, but first rejects untrusted executable accounts
+/// with `check_protocol_executable_accounts`.
+///
+/// # Returns
+/// * `Ok(DepositContext)` - Typed context for the detected protocol
+/// * `Err(BeethovenError::UntrustedProgram)` - An executable account is not trusted
+/// * `Err(ProgramError)` - Detection or parsing failed
+pub fn try_from_deposit_context_guarded<'info, D: Detector>(
+    detector: &D,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+    extra: &[Address],
+) -> Result<DepositContext<'info>, ProgramError> {
+    let protocol = detector.detect(accounts, ids)?;
+    check_protocol_executable_accounts(accounts, protocol, ids, extra)?;
+    try_from_deposit_context_for_with_ids(protocol, accounts, ids)
+}
+
+/// `try_from_deposit_context_guarded` with the default `ProgramIdDetector` and mainnet IDs.
+pub fn try_from_deposit_context_trusted<'info>(
+    accounts: &'info [AccountView],
+    extra: &[Address],
+) -> Result<DepositContext<'info>, ProgramError> {
+    try_from_deposit_context_guarded(&ProgramIdDetector, accounts, &ProgramIds::MAINNET, extra)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        std::vec::Vec,
+    };
+
+    const PROTOCOL: Address = Address::new_from_array([9; 32]);
+
+    /// Runs the check over a data account followed by the `programs` as executables.
+    fn check(programs: &[Address], extra: &[Address]) -> ProgramResult {
+        let mut accounts: Vec<TestAccount> = [TestAccount::empty(address(1))]
+            .into_iter()
+            .chain(
+                programs
+                    .iter()
+                    .map(|program| TestAccount::empty(program.clone()).executable()),
+            )
+            .collect();
+        check_executable_accounts(&views(&mut accounts), &PROTOCOL, extra)
+    }
+
+    #[test]
+    fn accepts_the_protocol_and_trusted_programs() {
+        assert_eq!(
+            check(&[PROTOCOL, TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID], &[]),
+            Ok(())
+        );
+        assert_eq!(check(&[address(7)], &[address(7)]), Ok(()));
+        // Only executables are checked.
+        assert_eq!(check(&[], &[]), Ok(()));
+    }
+
+    #[test]
+    fn rejects_other_executables() {
+        assert_eq!(
+            check(&[PROTOCOL, address(7)], &[address(8)]),
+            Err(BeethovenError::UntrustedProgram.into())
+        );
+    }
+
+    /// Marks the accounts at `indices` as executable programs, as the runtime passes them.
+    #[cfg(any(feature = "jupiter", feature = "kamino"))]
+    fn mark_executable(accounts: &mut [TestAccount], indices: &[usize]) {
+        for &index in indices {
+            let program = accounts[index].view().address().clone();
+            accounts[index] = TestAccount::empty(program).executable();
+        }
+    }
+
+    #[cfg(feature = "kamino")]
+    #[test]
+    fn trusted_parse_accepts_a_kamino_deposit_with_farms() {
+        use crate::programs::kamino::{KAMINO_FARMS_PROGRAM_ID, tests::deposit_accounts};
+
+        let mut accounts = deposit_accounts(0, 0, 0);
+        accounts[12] = TestAccount::empty(TOKEN_PROGRAM_ID);
+        accounts[13] = TestAccount::empty(TOKEN_2022_PROGRAM_ID);
+        accounts[17] = TestAccount::empty(KAMINO_FARMS_PROGRAM_ID);
+        mark_executable(&mut accounts, &[0, 12, 13, 17]);
+        let views = views(&mut accounts);
+
+        assert!(try_from_deposit_context_trusted(&views, &[]).is_ok());
+        // Without its dependencies, the Farms program is not trusted.
+        assert_eq!(
+            check_executable_accounts(&views, &ProgramIds::MAINNET.kamino_lend, &[]),
+            Err(BeethovenError::UntrustedProgram.into())
+        );
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn trusted_parse_accepts_a_jupiter_deposit_and_rejects_injected_programs() {
+        let mut accounts = crate::programs::jupiter::tests::deposit_accounts();
+        accounts[15] = TestAccount::empty(TOKEN_PROGRAM_ID);
+        accounts[16] = TestAccount::empty(ASSOCIATED_TOKEN_PROGRAM_ID);
+        accounts[17] = TestAccount::empty(SYSTEM_PROGRAM_ID);
+        mark_executable(&mut accounts, &[0, 13, 15, 16, 17]);
+        assert!(try_from_deposit_context_trusted(&views(&mut accounts), &[]).is_ok());
+
+        accounts.push(TestAccount::empty(address(7)).executable());
+        assert_eq!(
+            try_from_deposit_context_trusted(&views(&mut accounts), &[]).err(),
+            Some(BeethovenError::UntrustedProgram.into())
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use {
+    crate::programs::Protocol,
+    pinocchio::{Address, address::address_eq},
+};
+
+/// Program IDs used to discriminate and invoke each enabled protocol.
+///
+/// [`ProgramIds::MAINNET`] mirrors the `*_PROGRAM_ID` constants exposed by each protocol
+/// module. Integrators targeting devnet or staging deployments can override individual
+/// fields instead of forking the crate:
+///
+/// ```ignore
+/// const DEVNET: ProgramIds = ProgramIds {
+///     kamino_lend: KAMINO_DEVNET_PROGRAM_ID,
+///     ..ProgramIds::MAINNET
+/// };
+///
+/// let ctx = try_from_deposit_context_with_ids(accounts, &DEVNET)?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramIds {
+    /// Kamino Lend program
+    #[cfg(feature = "kamino")]
+    pub kamino_lend: Address,
+
+    /// Kamino Farms program, invoked by Kamino Lend
+    #[cfg(feature = "kamino")]
+    pub kamino_farms: Address,
+
+    /// Scope oracle program, owning the price accounts of Kamino reserves
+    #[cfg(feature = "kamino")]
+    pub kamino_scope: Address,
+
+    /// Jupiter Earn program
+    #[cfg(feature = "jupiter")]
+    pub jupiter_earn: Address,
+
+    /// Jupiter Liquidity program, owning the liquidity layer accounts Earn vaults supply to
+    #[cfg(feature = "jupiter")]
+    pub jupiter_liquidity: Address,
+
+    /// Drift program
+    #[cfg(feature = "drift")]
+    pub drift: Address,
+
+    /// Solayer restaking program
+    #[cfg(feature = "solayer")]
+    pub solayer_restaking: Address,
+
+    /// Marinade liquid staking program
+    #[cfg(feature = "marinade")]
+    pub marinade: Address,
+
+    /// SPL stake pool program
+    #[cfg(feature = "stake_pool")]
+    pub stake_pool: Address,
+
+    /// Jito vault program
+    #[cfg(feature = "jito")]
+    pub jito_vault: Address,
+}
+
+impl ProgramIds {
+    /// Program IDs of the mainnet deployments.
+    pub const MAINNET: Self = Self {
+        #[cfg(feature = "kamino")]
+        kamino_lend: crate::programs::kamino::KAMINO_LEND_PROGRAM_ID,
+
+        #[cfg(feature = "kamino")]
+        kamino_farms: crate::programs::kamino::KAMINO_FARMS_PROGRAM_ID,
+
+        #[cfg(feature = "kamino")]
+        kamino_scope: crate::programs::kamino::SCOPE_PROGRAM_ID,
+
+        #[cfg(feature = "jupiter")]
+        jupiter_earn: crate::programs::jupiter::JUPITER_EARN_PROGRAM_ID,
+
+        #[cfg(feature = "jupiter")]
+        jupiter_liquidity: crate::programs::jupiter::JUPITER_LIQUIDITY_PROGRAM_ID,
+
+        #[cfg(feature = "drift")]
+        drift: crate::programs::drift::DRIFT_PROGRAM_ID,
+
+        #[cfg(feature = "solayer")]
+        solayer_restaking: crate::programs::solayer::SOLAYER_RESTAKING_PROGRAM_ID,
+
+        #[cfg(feature = "marinade")]
+        marinade: crate::programs::marinade::MARINADE_PROGRAM_ID,
+
+        #[cfg(feature = "stake_pool")]
+        stake_pool: crate::programs::stake_pool::SPL_STAKE_POOL_PROGRAM_ID,
+
+        #[cfg(feature = "jito")]
+        jito_vault: crate::programs::jito::JITO_VAULT_PROGRAM_ID,
+    };
+
+    /// Returns the program ID configured for `protocol`.
+    pub fn get(&self, protocol: Protocol) -> &Address {
+        match protocol {
+            #[cfg(feature = "kamino")]
+            Protocol::Kamino => &self.kamino_lend,
+
+            #[cfg(feature = "jupiter")]
+            Protocol::Jupiter => &self.jupiter_earn,
+
+            #[cfg(feature = "drift")]
+            Protocol::Drift => &self.drift,
+
+            #[cfg(feature = "solayer")]
+            Protocol::Solayer => &self.solayer_restaking,
+
+            #[cfg(feature = "marinade")]
+            Protocol::Marinade => &self.marinade,
+
+            #[cfg(feature = "stake_pool")]
+            Protocol::StakePool => &self.stake_pool,
+
+            #[cfg(feature = "jito")]
+            Protocol::Jito => &self.jito_vault,
+        }
+    }
+
+    /// Returns the programs `protocol` itself invokes, besides the token and system programs.
+    ///
+    /// These are passed as executable accounts in the protocol's layouts (e.g. the Farms
+    /// program in Kamino deposits), so `check_executable_accounts` callers trust them too.
+    pub fn dependency_programs(&self, protocol: Protocol) -> &[Address] {
+        match protocol {
+            #[cfg(feature = "kamino")]
+            Protocol::Kamino => core::slice::from_ref(&self.kamino_farms),
+
+            #[cfg(feature = "jupiter")]
+            Protocol::Jupiter => core::slice::from_ref(&self.jupiter_liquidity),
+
+            #[allow(unreachable_patterns)]
+            _ => &[],
+        }
+    }
+
+    /// Returns the protocol whose configured program ID is `program_id`, if any.
+    pub fn protocol_of(&self, program_id: &Address) -> Option<Protocol> {
+        Protocol::ALL
+            .iter()
+            .copied()
+            .find(|protocol| address_eq(program_id, self.get(*protocol)))
+    }
+}
+
+impl Default for ProgramIds {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
@@ -0,0 +1,12 @@
+use crate::BeethovenError;
+
+/// Jito vault program (`VaultError`) codes translated into unified beethoven errors.
+///
+/// Codes are the explicit `VaultError` discriminants, numbered from 1000.
+pub const JITO_ERROR_TABLE: &[(u32, BeethovenError)] = &[
+    (1002, BeethovenError::InsufficientLiquidity), // VaultInsufficientFunds
+    (1010, BeethovenError::DepositCapExceeded),    // VaultCapacityExceeded
+    (1020, BeethovenError::RefreshRequired),       // VaultUpdateNeeded
+    (1030, BeethovenError::WithdrawalNotReady),    // VaultStakerWithdrawalTicketNotWithdrawable
+    (1051, BeethovenError::VenuePaused),           // VaultIsPaused
+];
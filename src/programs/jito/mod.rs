@@ -0,0 +1,451 @@
+mod errors;
+pub use errors::*;
+
+mod pda;
+pub use pda::*;
+
+mod ticket;
+pub use ticket::*;
+use {
+    crate::{
+        AccountCheck, AccountError, AccountSpec, ClaimWithdraw, RequestWithdraw,
+        util::cpi::invoke_signed_with_remaining,
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+pub const JITO_VAULT_PROGRAM_ID: Address = Address::new_from_array([
+    7, 82, 151, 3, 233, 209, 72, 36, 13, 237, 19, 215, 83, 88, 206, 101, 40, 120, 109, 65, 221,
+    187, 195, 114, 118, 11, 178, 161, 116, 80, 255, 125,
+]);
+// `VaultInstruction` variant indices (one-byte Borsh enum tags).
+const ENQUEUE_WITHDRAWAL_TAG: u8 = 12;
+const BURN_WITHDRAWAL_TICKET_TAG: u8 = 14;
+
+/// Account order expected by `JitoEnqueueWithdrawalAccounts::try_from`.
+///
+/// Followed by the vault's mint burn admin (signer) when the vault has one.
+pub const JITO_ENQUEUE_WITHDRAWAL_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("jito_vault_program"),
+    AccountSpec::readonly("config"),
+    AccountSpec::writable("vault"),
+    AccountSpec::writable("vault_staker_withdrawal_ticket"),
+    AccountSpec::writable("vault_staker_withdrawal_ticket_token_account"),
+    AccountSpec::writable_signer("staker"),
+    AccountSpec::writable("staker_vrt_token_account"),
+    AccountSpec::readonly_signer("base"),
+    AccountSpec::readonly("token_program"),
+    AccountSpec::readonly("system_program"),
+];
+
+/// Account order expected by `JitoBurnWithdrawalTicketAccounts::try_from`.
+///
+/// Followed by the vault's mint burn admin (signer) when the vault has one.
+pub const JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("jito_vault_program"),
+    AccountSpec::readonly("config"),
+    AccountSpec::writable("vault"),
+    AccountSpec::writable("vault_token_account"),
+    AccountSpec::writable("vrt_mint"),
+    AccountSpec::writable("staker"),
+    AccountSpec::writable("staker_token_account"),
+    AccountSpec::writable("vault_staker_withdrawal_ticket"),
+    AccountSpec::writable("vault_staker_withdrawal_ticket_token_account"),
+    AccountSpec::writable("vault_fee_token_account"),
+    AccountSpec::writable("program_fee_token_account"),
+    AccountSpec::readonly("token_program"),
+    AccountSpec::readonly("system_program"),
+];
+
+/// Jito restaking vault integration
+pub struct JitoVault;
+
+/// Account context for the Jito vault program's EnqueueWithdrawal instruction.
+///
+/// Moves VRT into a new withdrawal ticket, burnable for the vault's supported token once
+/// one full vault epoch has passed.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 10 accounts are present.
+pub struct JitoEnqueueWithdrawalAccounts<'info> {
+    /// Jito Vault Program
+    pub jito_vault_program: &'info AccountView,
+    /// Vault program config
+    pub config: &'info AccountView,
+    /// Vault withdrawn from (writable)
+    pub vault: &'info AccountView,
+    /// Withdrawal ticket PDA to open, derived from `vault` and `base` (writable)
+    pub vault_staker_withdrawal_ticket: &'info AccountView,
+    /// VRT associated token account of the ticket, created beforehand (writable)
+    pub vault_staker_withdrawal_ticket_token_account: &'info AccountView,
+    /// Staker owning the VRT, pays the ticket rent (writable, signer)
+    pub staker: &'info AccountView,
+    /// Staker's VRT token account (writable)
+    pub staker_vrt_token_account: &'info AccountView,
+    /// Fresh signer the ticket PDA is derived from (signer)
+    pub base: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for JitoEnqueueWithdrawalAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `JitoEnqueueWithdrawalAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(JitoEnqueueWithdrawalAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 10 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `JITO_VAULT_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &JITO_VAULT_PROGRAM_ID)
+    }
+}
+
+impl<'info> JitoEnqueueWithdrawalAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            jito_vault_program,
+            config,
+            vault,
+            vault_staker_withdrawal_ticket,
+            vault_staker_withdrawal_ticket_token_account,
+            staker,
+            staker_vrt_token_account,
+            base,
+            token_program,
+            system_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(
+                AccountError::missing(JITO_ENQUEUE_WITHDRAWAL_ACCOUNTS, accounts.len()).into(),
+            );
+        };
+
+        if !address_eq(jito_vault_program.address(), program_id) {
+            return Err(AccountError::new(
+                JITO_ENQUEUE_WITHDRAWAL_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            jito_vault_program,
+            config,
+            vault,
+            vault_staker_withdrawal_ticket,
+            vault_staker_withdrawal_ticket_token_account,
+            staker,
+            staker_vrt_token_account,
+            base,
+            token_program,
+            system_program,
+            remaining,
+        })
+    }
+}
+
+/// Account context for the Jito vault program's BurnWithdrawalTicket instruction.
+///
+/// Burns the ticket's VRT, pays the supported token out to the staker and closes the ticket.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 13 accounts are present.
+pub struct JitoBurnWithdrawalTicketAccounts<'info> {
+    /// Jito Vault Program
+    pub jito_vault_program: &'info AccountView,
+    /// Vault program config
+    pub config: &'info AccountView,
+    /// Vault withdrawn from (writable)
+    pub vault: &'info AccountView,
+    /// Vault's supported token account paying out (writable)
+    pub vault_token_account: &'info AccountView,
+    /// Vault's VRT mint (writable)
+    pub vrt_mint: &'info AccountView,
+    /// Staker the ticket belongs to, receives the ticket rent (writable)
+    pub staker: &'info AccountView,
+    /// Staker's supported token account receiving the withdrawal (writable)
+    pub staker_token_account: &'info AccountView,
+    /// The withdrawal ticket to burn (writable)
+    pub vault_staker_withdrawal_ticket: &'info AccountView,
+    /// VRT token account of the ticket (writable)
+    pub vault_staker_withdrawal_ticket_token_account: &'info AccountView,
+    /// Vault fee wallet's VRT token account (writable)
+    pub vault_fee_token_account: &'info AccountView,
+    /// Program fee wallet's VRT token account (writable)
+    pub program_fee_token_account: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for JitoBurnWithdrawalTicketAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `JitoBurnWithdrawalTicketAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(JitoBurnWithdrawalTicketAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 13 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `JITO_VAULT_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &JITO_VAULT_PROGRAM_ID)
+    }
+}
+
+impl<'info> JitoBurnWithdrawalTicketAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            jito_vault_program,
+            config,
+            vault,
+            vault_token_account,
+            vrt_mint,
+            staker,
+            staker_token_account,
+            vault_staker_withdrawal_ticket,
+            vault_staker_withdrawal_ticket_token_account,
+            vault_fee_token_account,
+            program_fee_token_account,
+            token_program,
+            system_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(
+                JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS,
+                accounts.len(),
+            )
+            .into());
+        };
+
+        if !address_eq(jito_vault_program.address(), program_id) {
+            return Err(AccountError::new(
+                JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            jito_vault_program,
+            config,
+            vault,
+            vault_token_account,
+            vrt_mint,
+            staker,
+            staker_token_account,
+            vault_staker_withdrawal_ticket,
+            vault_staker_withdrawal_ticket_token_account,
+            vault_fee_token_account,
+            program_fee_token_account,
+            token_program,
+            system_program,
+            remaining,
+        })
+    }
+}
+
+impl<'info> RequestWithdraw<'info> for JitoVault {
+    type Accounts = JitoEnqueueWithdrawalAccounts<'info>;
+
+    /// Moves `amount` VRT into a new withdrawal ticket via `EnqueueWithdrawal`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `JitoEnqueueWithdrawalAccounts`)
+    /// * `amount` - Amount of VRT to withdraw
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing (`staker`, `base`)
+    fn request_withdraw_signed(
+        ctx: &JitoEnqueueWithdrawalAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::readonly(ctx.config.address()),
+            InstructionAccount::writable(ctx.vault.address()),
+            InstructionAccount::writable(ctx.vault_staker_withdrawal_ticket.address()),
+            InstructionAccount::writable(
+                ctx.vault_staker_withdrawal_ticket_token_account.address(),
+            ),
+            InstructionAccount::writable_signer(ctx.staker.address()),
+            InstructionAccount::writable(ctx.staker_vrt_token_account.address()),
+            InstructionAccount::readonly_signer(ctx.base.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+            InstructionAccount::readonly(ctx.system_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.config,
+            ctx.vault,
+            ctx.vault_staker_withdrawal_ticket,
+            ctx.vault_staker_withdrawal_ticket_token_account,
+            ctx.staker,
+            ctx.staker_vrt_token_account,
+            ctx.base,
+            ctx.token_program,
+            ctx.system_program,
+        ];
+
+        // Build instruction data: tag (1 byte) + amount (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 9]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            *ptr = ENQUEUE_WITHDRAWAL_TAG;
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(1), 8);
+        }
+
+        let enqueue_ix = InstructionView {
+            program_id: ctx.jito_vault_program.address(),
+            accounts: &accounts,
+            data: unsafe { core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 9) },
+        };
+
+        invoke_signed_with_remaining(&enqueue_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    fn request_withdraw(ctx: &JitoEnqueueWithdrawalAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::request_withdraw_signed(ctx, amount, &[])
+    }
+}
+
+impl<'info> ClaimWithdraw<'info> for JitoVault {
+    type Accounts = JitoBurnWithdrawalTicketAccounts<'info>;
+
+    /// Redeems a withdrawable ticket for the vault's supported token via
+    /// `BurnWithdrawalTicket`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `JitoBurnWithdrawalTicketAccounts`)
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    ///
+    /// # Notes
+    /// * The vault program rejects tickets enqueued less than one full epoch ago, aborting the
+    ///   transaction. Use `JitoVault::check_ticket_ready` first to defer instead.
+    /// * The vault must have been updated for the current epoch (`UpdateVaultBalance` and the
+    ///   state tracker cranks), otherwise the burn fails with `VaultUpdateNeeded`.
+    fn claim_withdraw_signed(
+        ctx: &JitoBurnWithdrawalTicketAccounts<'info>,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::readonly(ctx.config.address()),
+            InstructionAccount::writable(ctx.vault.address()),
+            InstructionAccount::writable(ctx.vault_token_account.address()),
+            InstructionAccount::writable(ctx.vrt_mint.address()),
+            InstructionAccount::writable(ctx.staker.address()),
+            InstructionAccount::writable(ctx.staker_token_account.address()),
+            InstructionAccount::writable(ctx.vault_staker_withdrawal_ticket.address()),
+            InstructionAccount::writable(
+                ctx.vault_staker_withdrawal_ticket_token_account.address(),
+            ),
+            InstructionAccount::writable(ctx.vault_fee_token_account.address()),
+            InstructionAccount::writable(ctx.program_fee_token_account.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+            InstructionAccount::readonly(ctx.system_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.config,
+            ctx.vault,
+            ctx.vault_token_account,
+            ctx.vrt_mint,
+            ctx.staker,
+            ctx.staker_token_account,
+            ctx.vault_staker_withdrawal_ticket,
+            ctx.vault_staker_withdrawal_ticket_token_account,
+            ctx.vault_fee_token_account,
+            ctx.program_fee_token_account,
+            ctx.token_program,
+            ctx.system_program,
+        ];
+
+        let burn_ix = InstructionView {
+            program_id: ctx.jito_vault_program.address(),
+            accounts: &accounts,
+            data: &[BURN_WITHDRAWAL_TICKET_TAG],
+        };
+
+        invoke_signed_with_remaining(&burn_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    fn claim_withdraw(ctx: &JitoBurnWithdrawalTicketAccounts<'info>) -> ProgramResult {
+        Self::claim_withdraw_signed(ctx, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        std::vec::Vec,
+    };
+
+    fn accounts(len: usize) -> Vec<TestAccount> {
+        (0..len as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(JITO_VAULT_PROGRAM_ID),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn enqueue_forwards_the_burn_signer() {
+        let mut accounts = accounts(11);
+        let accounts = views(&mut accounts);
+        let ctx = JitoEnqueueWithdrawalAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(ctx.base.address(), &address(7));
+        assert_eq!(ctx.remaining.len(), 1);
+    }
+
+    #[test]
+    fn burn_names_the_missing_account() {
+        let mut accounts = accounts(12);
+        let accounts = views(&mut accounts);
+        assert!(matches!(
+            JitoBurnWithdrawalTicketAccounts::try_from(&accounts[..]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+    }
+
+    #[test]
+    fn burn_rejects_another_program() {
+        let mut accounts = accounts(13);
+        accounts[0] = TestAccount::empty(address(99));
+        let accounts = views(&mut accounts);
+        assert!(matches!(
+            JitoBurnWithdrawalTicketAccounts::try_from(&accounts[..]),
+            Err(ProgramError::IncorrectProgramId)
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+//! Seeds of the PDAs the Jito vault program derives for withdrawals.
+//!
+//! The seed functions work everywhere (on-chain validation, client builders). The `find_*`
+//! functions derive the addresses on-chain, and off-chain with the `curve25519` feature
+//! (enabled by `client`).
+
+use pinocchio::Address;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+use {super::JITO_VAULT_PROGRAM_ID, crate::util::find_program_address};
+
+const CONFIG_SEED: &[u8] = b"config";
+const WITHDRAWAL_TICKET_SEED: &[u8] = b"vault_staker_withdrawal_ticket";
+
+/// Seeds of the vault program's config PDA.
+pub fn config_seeds() -> [&'static [u8]; 1] {
+    [CONFIG_SEED]
+}
+
+/// Seeds of the withdrawal ticket PDA of `vault` opened with the `base` signer.
+///
+/// `base` is a fresh signer per ticket, so one staker can hold several tickets at once.
+pub fn withdrawal_ticket_seeds<'a>(vault: &'a Address, base: &'a Address) -> [&'a [u8]; 3] {
+    [WITHDRAWAL_TICKET_SEED, vault.as_ref(), base.as_ref()]
+}
+
+/// Finds the vault program's config PDA and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_config() -> (Address, u8) {
+    find_program_address(&config_seeds(), &JITO_VAULT_PROGRAM_ID)
+}
+
+/// Finds the withdrawal ticket PDA of `vault` opened with `base`, and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_withdrawal_ticket(vault: &Address, base: &Address) -> (Address, u8) {
+    find_program_address(
+        &withdrawal_ticket_seeds(vault, base),
+        &JITO_VAULT_PROGRAM_ID,
+    )
+}
+
+#[cfg(all(test, feature = "curve25519"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_matches_mainnet() {
+        // UwuSgAq4zByffCGCrWH87DsjfsewYjuqHfJEpzw1Jq3
+        let config = Address::new_from_array([
+            7, 40, 195, 31, 203, 167, 100, 250, 35, 147, 86, 38, 89, 61, 40, 56, 100, 177, 95, 22,
+            253, 142, 44, 155, 5, 168, 106, 201, 173, 11, 215, 134,
+        ]);
+        assert_eq!(find_config(), (config, 255));
+    }
+
+    #[test]
+    fn withdrawal_ticket_is_derived_from_vault_and_base() {
+        let vault = Address::new_from_array([1; 32]);
+        let base = Address::new_from_array([2; 32]);
+        let ticket = Address::new_from_array([
+            12, 252, 208, 116, 192, 69, 11, 141, 208, 199, 187, 137, 16, 126, 43, 1, 71, 90, 170,
+            75, 156, 117, 106, 236, 166, 236, 253, 93, 203, 17, 36, 157,
+        ]);
+        assert_eq!(find_withdrawal_ticket(&vault, &base), (ticket.clone(), 255));
+        assert_ne!(find_withdrawal_ticket(&vault, &vault).0, ticket);
+    }
+}
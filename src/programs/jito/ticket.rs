@@ -0,0 +1,235 @@
+use {
+    super::{JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS, JitoBurnWithdrawalTicketAccounts, JitoVault},
+    crate::{AccountCheck, AccountError, BeethovenError, util::bytes::*},
+    pinocchio::{
+        Address,
+        address::address_eq,
+        error::ProgramError,
+        sysvars::{Sysvar, clock::Clock},
+    },
+};
+
+// Jito accounts start with an 8-byte little-endian discriminator.
+const CONFIG_DISCRIMINATOR: [u8; 8] = [1, 0, 0, 0, 0, 0, 0, 0];
+const WITHDRAWAL_TICKET_DISCRIMINATOR: [u8; 8] = [7, 0, 0, 0, 0, 0, 0, 0];
+
+// Byte offsets into the vault program's `Config` account (discriminator included).
+const CONFIG_EPOCH_LENGTH_OFFSET: usize = 72;
+
+// Byte offsets into `VaultStakerWithdrawalTicket` (discriminator included).
+const TICKET_VAULT_OFFSET: usize = 8;
+const TICKET_STAKER_OFFSET: usize = 40;
+const TICKET_BASE_OFFSET: usize = 72;
+const TICKET_VRT_AMOUNT_OFFSET: usize = 104;
+const TICKET_SLOT_UNSTAKED_OFFSET: usize = 112;
+
+/// Size of a Jito `VaultStakerWithdrawalTicket` account, in bytes.
+pub const JITO_WITHDRAWAL_TICKET_ACCOUNT_LEN: usize = 384;
+
+/// Jito vault program `Config`, reduced to the fields withdrawals depend on.
+pub struct JitoConfigState {
+    /// Length of a vault epoch, in slots
+    pub epoch_length: u64,
+}
+
+impl JitoConfigState {
+    /// Parses raw `Config` account data.
+    ///
+    /// # Returns
+    /// * `Ok(JitoConfigState)` - Parsed config
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a vault program config, or its
+    ///   epoch length is 0
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &CONFIG_DISCRIMINATOR)?;
+
+        let epoch_length = read_u64(data, CONFIG_EPOCH_LENGTH_OFFSET)?;
+        if epoch_length == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self { epoch_length })
+    }
+}
+
+/// Jito's `VaultStakerWithdrawalTicket`: VRT queued for withdrawal by `staker`.
+pub struct JitoWithdrawalTicketState {
+    /// Vault the ticket withdraws from
+    pub vault: Address,
+    /// Staker the withdrawn tokens are paid to
+    pub staker: Address,
+    /// Base signer the ticket PDA is derived from
+    pub base: Address,
+    /// VRT escrowed by the ticket
+    pub vrt_amount: u64,
+    /// Slot the withdrawal was enqueued in
+    pub slot_unstaked: u64,
+}
+
+impl JitoWithdrawalTicketState {
+    /// Parses raw `VaultStakerWithdrawalTicket` account data.
+    ///
+    /// # Returns
+    /// * `Ok(JitoWithdrawalTicketState)` - Parsed ticket
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Jito withdrawal ticket
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &WITHDRAWAL_TICKET_DISCRIMINATOR)?;
+
+        Ok(Self {
+            vault: read_address(data, TICKET_VAULT_OFFSET)?,
+            staker: read_address(data, TICKET_STAKER_OFFSET)?,
+            base: read_address(data, TICKET_BASE_OFFSET)?,
+            vrt_amount: read_u64(data, TICKET_VRT_AMOUNT_OFFSET)?,
+            slot_unstaked: read_u64(data, TICKET_SLOT_UNSTAKED_OFFSET)?,
+        })
+    }
+
+    /// Returns `true` when the vault program accepts burning the ticket at `slot`.
+    ///
+    /// A ticket is burnable once one full epoch has passed since the epoch it was enqueued
+    /// in, i.e. from the start of the second epoch after it.
+    pub fn is_withdrawable(&self, slot: u64, config: &JitoConfigState) -> bool {
+        let epoch_unstaked = self.slot_unstaked / config.epoch_length;
+        slot / config.epoch_length > epoch_unstaked.saturating_add(1)
+    }
+}
+
+impl JitoVault {
+    /// Reads the ticket and vault program config of a claim context.
+    ///
+    /// # Returns
+    /// * `Ok((JitoWithdrawalTicketState, JitoConfigState))` - Parsed ticket and config
+    /// * `Err(ProgramError::IllegalOwner)` - Ticket or config is not owned by the vault program
+    /// * `Err(ProgramError)` - Ticket or config data could not be read
+    pub fn ticket(
+        ctx: &JitoBurnWithdrawalTicketAccounts,
+    ) -> Result<(JitoWithdrawalTicketState, JitoConfigState), ProgramError> {
+        let program_id = ctx.jito_vault_program.address();
+        for (account, name) in [
+            (
+                ctx.vault_staker_withdrawal_ticket,
+                "vault_staker_withdrawal_ticket",
+            ),
+            (ctx.config, "config"),
+        ] {
+            if !account.owned_by(program_id) {
+                return Err(AccountError::of(
+                    JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS,
+                    name,
+                    AccountCheck::Owner,
+                )
+                .into());
+            }
+        }
+
+        let ticket = JitoWithdrawalTicketState::from_bytes(
+            &ctx.vault_staker_withdrawal_ticket.try_borrow()?,
+        )?;
+        let config = JitoConfigState::from_bytes(&ctx.config.try_borrow()?)?;
+        Ok((ticket, config))
+    }
+
+    /// Checks, against the Clock sysvar, that the ticket of `ctx` can be burned now.
+    ///
+    /// Lets callers skip or defer a claim instead of having the burn CPI abort the
+    /// transaction.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - VRT the ticket redeems
+    /// * `Err(BeethovenError::WithdrawalNotReady)` - The ticket is not withdrawable yet
+    /// * `Err(ProgramError::InvalidAccountData)` - `staker` or `vault` do not match the ticket
+    /// * `Err(ProgramError)` - Ticket, config or Clock sysvar could not be read
+    pub fn check_ticket_ready(ctx: &JitoBurnWithdrawalTicketAccounts) -> Result<u64, ProgramError> {
+        let (ticket, config) = Self::ticket(ctx)?;
+
+        for (expected, account, name) in [
+            (&ticket.staker, ctx.staker, "staker"),
+            (&ticket.vault, ctx.vault, "vault"),
+        ] {
+            if !address_eq(expected, account.address()) {
+                return Err(AccountError::of(
+                    JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS,
+                    name,
+                    AccountCheck::Key,
+                )
+                .into());
+            }
+        }
+
+        if !ticket.is_withdrawable(Clock::get()?.slot, &config) {
+            return Err(BeethovenError::WithdrawalNotReady.into());
+        }
+        Ok(ticket.vrt_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{account_data, address},
+    };
+
+    fn ticket_data(slot_unstaked: u64) -> std::vec::Vec<u8> {
+        let mut data = account_data(
+            &WITHDRAWAL_TICKET_DISCRIMINATOR,
+            JITO_WITHDRAWAL_TICKET_ACCOUNT_LEN,
+        );
+        data[TICKET_VAULT_OFFSET..TICKET_STAKER_OFFSET].copy_from_slice(address(1).as_ref());
+        data[TICKET_STAKER_OFFSET..TICKET_BASE_OFFSET].copy_from_slice(address(2).as_ref());
+        data[TICKET_BASE_OFFSET..TICKET_VRT_AMOUNT_OFFSET].copy_from_slice(address(3).as_ref());
+        data[TICKET_VRT_AMOUNT_OFFSET..TICKET_SLOT_UNSTAKED_OFFSET]
+            .copy_from_slice(&5_000_000u64.to_le_bytes());
+        data[TICKET_SLOT_UNSTAKED_OFFSET..TICKET_SLOT_UNSTAKED_OFFSET + 8]
+            .copy_from_slice(&slot_unstaked.to_le_bytes());
+        // Bump
+        data[120] = 254;
+        data
+    }
+
+    fn config(epoch_length: u64) -> JitoConfigState {
+        let mut data = account_data(&CONFIG_DISCRIMINATOR, 390);
+        data[CONFIG_EPOCH_LENGTH_OFFSET..CONFIG_EPOCH_LENGTH_OFFSET + 8]
+            .copy_from_slice(&epoch_length.to_le_bytes());
+        JitoConfigState::from_bytes(&data).unwrap()
+    }
+
+    #[test]
+    fn ticket_reads_its_fields() {
+        let ticket = JitoWithdrawalTicketState::from_bytes(&ticket_data(1_234)).unwrap();
+        assert_eq!(ticket.vault, address(1));
+        assert_eq!(ticket.staker, address(2));
+        assert_eq!(ticket.base, address(3));
+        assert_eq!(ticket.vrt_amount, 5_000_000);
+        assert_eq!(ticket.slot_unstaked, 1_234);
+    }
+
+    #[test]
+    fn ticket_rejects_other_accounts() {
+        let mut data = ticket_data(0);
+        data[0] = 1;
+        assert!(matches!(
+            JitoWithdrawalTicketState::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        ));
+    }
+
+    #[test]
+    fn ticket_is_withdrawable_after_one_full_epoch() {
+        // Enqueued mid-epoch 10 of 432,000-slot epochs: burnable from epoch 12.
+        let config = config(432_000);
+        let ticket = JitoWithdrawalTicketState::from_bytes(&ticket_data(10 * 432_000 + 5)).unwrap();
+
+        assert!(!ticket.is_withdrawable(11 * 432_000 + 431_999, &config));
+        assert!(ticket.is_withdrawable(12 * 432_000, &config));
+    }
+
+    #[test]
+    fn config_rejects_a_zero_epoch_length() {
+        let data = account_data(&CONFIG_DISCRIMINATOR, 390);
+        assert!(matches!(
+            JitoConfigState::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        ));
+    }
+}
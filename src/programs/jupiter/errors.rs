@@ -0,0 +1,14 @@
+use crate::BeethovenError;
+
+/// Jupiter liquidity layer (`ErrorCodes`) codes translated into unified beethoven errors.
+///
+/// Jupiter Earn moves funds through the liquidity layer, so a failed deposit or withdrawal
+/// surfaces the liquidity program's code. Codes follow its Anchor error numbering
+/// (`6000 + variant index`).
+pub const JUPITER_ERROR_TABLE: &[(u32, BeethovenError)] = &[
+    (6027, BeethovenError::VenuePaused),           // UserPaused
+    (6028, BeethovenError::InsufficientLiquidity), // WithdrawalLimitReached
+    (6035, BeethovenError::InsufficientLiquidity), // MaxUtilizationReached
+    (6036, BeethovenError::DepositCapExceeded),    // ValueOverflowTotalSupply
+    (6042, BeethovenError::VenuePaused),           // ProtocolLockdown
+];
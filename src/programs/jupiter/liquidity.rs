@@ -0,0 +1,442 @@
+use {
+    super::JUPITER_EXCHANGE_PRICES_PRECISION,
+    crate::util::{BPS_DENOMINATOR, Rounding, bytes::*, mul_div, to_u64},
+    pinocchio::{Address, error::ProgramError},
+};
+
+pub const JUPITER_LIQUIDITY_PROGRAM_ID: Address = Address::new_from_array([
+    10, 254, 30, 151, 228, 86, 203, 185, 46, 100, 42, 37, 181, 11, 166, 57, 111, 242, 72, 22, 75,
+    202, 116, 105, 99, 168, 69, 146, 172, 214, 204, 195,
+]);
+
+const LIQUIDITY_DISCRIMINATOR: [u8; 8] = [54, 252, 249, 226, 137, 172, 121, 58];
+const TOKEN_RESERVE_DISCRIMINATOR: [u8; 8] = [21, 18, 59, 135, 120, 20, 31, 12];
+const USER_SUPPLY_POSITION_DISCRIMINATOR: [u8; 8] = [202, 219, 136, 118, 61, 177, 21, 146];
+
+// Byte offsets into the liquidity program's borsh `Liquidity` account (discriminator included).
+const LIQUIDITY_STATUS_OFFSET: usize = 72;
+
+// Byte offsets into the packed `TokenReserve` account (discriminator included).
+const RESERVE_MINT_OFFSET: usize = 8;
+const RESERVE_VAULT_OFFSET: usize = 40;
+const RESERVE_BORROW_RATE_OFFSET: usize = 72;
+const RESERVE_FEE_ON_INTEREST_OFFSET: usize = 74;
+const RESERVE_LAST_UTILIZATION_OFFSET: usize = 76;
+const RESERVE_SUPPLY_EXCHANGE_PRICE_OFFSET: usize = 86;
+const RESERVE_BORROW_EXCHANGE_PRICE_OFFSET: usize = 94;
+const RESERVE_MAX_UTILIZATION_OFFSET: usize = 102;
+const RESERVE_TOTAL_SUPPLY_WITH_INTEREST_OFFSET: usize = 104;
+const RESERVE_TOTAL_SUPPLY_INTEREST_FREE_OFFSET: usize = 112;
+const RESERVE_TOTAL_BORROW_WITH_INTEREST_OFFSET: usize = 120;
+const RESERVE_TOTAL_BORROW_INTEREST_FREE_OFFSET: usize = 128;
+
+// Byte offsets into the packed `UserSupplyPosition` account (discriminator included).
+const POSITION_PROTOCOL_OFFSET: usize = 8;
+const POSITION_MINT_OFFSET: usize = 40;
+const POSITION_WITH_INTEREST_OFFSET: usize = 72;
+const POSITION_AMOUNT_OFFSET: usize = 73;
+const POSITION_WITHDRAWAL_LIMIT_OFFSET: usize = 81;
+const POSITION_LAST_UPDATE_OFFSET: usize = 97;
+const POSITION_EXPAND_PCT_OFFSET: usize = 105;
+const POSITION_EXPAND_DURATION_OFFSET: usize = 107;
+const POSITION_BASE_WITHDRAWAL_LIMIT_OFFSET: usize = 115;
+const POSITION_STATUS_OFFSET: usize = 123;
+
+/// Largest total supply or borrow the liquidity layer accepts for a token (`2^60 - 1`).
+pub const JUPITER_MAX_TOKEN_AMOUNT_CAP: u64 = (1 << 60) - 1;
+
+/// Jupiter liquidity layer's global `Liquidity` account.
+pub struct JupiterLiquidityState {
+    /// Whether the liquidity layer is in lockdown, rejecting every operation
+    pub locked: bool,
+}
+
+impl JupiterLiquidityState {
+    /// Parses the relevant fields out of raw `Liquidity` account data.
+    ///
+    /// # Returns
+    /// * `Ok(JupiterLiquidityState)` - Parsed state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Jupiter `Liquidity`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &LIQUIDITY_DISCRIMINATOR)?;
+
+        Ok(Self {
+            locked: read_array::<1>(data, LIQUIDITY_STATUS_OFFSET)?[0] != 0,
+        })
+    }
+}
+
+/// Jupiter liquidity layer's `TokenReserve` account: supply, borrows and rates of one token.
+///
+/// # Notes
+/// * Totals "with interest" are raw amounts, scaled by the matching exchange price; totals
+///   "interest free" are token amounts.
+pub struct JupiterTokenReserveState {
+    /// Token mint
+    pub mint: Address,
+    /// Token account holding the supplied liquidity
+    pub vault: Address,
+    /// Borrow rate, in basis points
+    pub borrow_rate: u16,
+    /// Share of borrow interest kept as revenue, in basis points
+    pub fee_on_interest: u16,
+    /// Utilization at the last update, in basis points
+    pub last_utilization: u16,
+    /// Supply exchange price, scaled by `JUPITER_EXCHANGE_PRICES_PRECISION`
+    pub supply_exchange_price: u64,
+    /// Borrow exchange price, scaled by `JUPITER_EXCHANGE_PRICES_PRECISION`
+    pub borrow_exchange_price: u64,
+    /// Utilization above which borrows are rejected, in basis points
+    pub max_utilization: u16,
+    /// Raw supply earning interest
+    pub total_supply_with_interest: u64,
+    /// Supply not earning interest
+    pub total_supply_interest_free: u64,
+    /// Raw borrows paying interest
+    pub total_borrow_with_interest: u64,
+    /// Borrows not paying interest
+    pub total_borrow_interest_free: u64,
+}
+
+impl JupiterTokenReserveState {
+    /// Parses the relevant fields out of raw `TokenReserve` account data.
+    ///
+    /// # Returns
+    /// * `Ok(JupiterTokenReserveState)` - Parsed reserve
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Jupiter `TokenReserve`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &TOKEN_RESERVE_DISCRIMINATOR)?;
+
+        Ok(Self {
+            mint: read_address(data, RESERVE_MINT_OFFSET)?,
+            vault: read_address(data, RESERVE_VAULT_OFFSET)?,
+            borrow_rate: read_u16(data, RESERVE_BORROW_RATE_OFFSET)?,
+            fee_on_interest: read_u16(data, RESERVE_FEE_ON_INTEREST_OFFSET)?,
+            last_utilization: read_u16(data, RESERVE_LAST_UTILIZATION_OFFSET)?,
+            supply_exchange_price: read_u64(data, RESERVE_SUPPLY_EXCHANGE_PRICE_OFFSET)?,
+            borrow_exchange_price: read_u64(data, RESERVE_BORROW_EXCHANGE_PRICE_OFFSET)?,
+            max_utilization: read_u16(data, RESERVE_MAX_UTILIZATION_OFFSET)?,
+            total_supply_with_interest: read_u64(data, RESERVE_TOTAL_SUPPLY_WITH_INTEREST_OFFSET)?,
+            total_supply_interest_free: read_u64(data, RESERVE_TOTAL_SUPPLY_INTEREST_FREE_OFFSET)?,
+            total_borrow_with_interest: read_u64(data, RESERVE_TOTAL_BORROW_WITH_INTEREST_OFFSET)?,
+            total_borrow_interest_free: read_u64(data, RESERVE_TOTAL_BORROW_INTEREST_FREE_OFFSET)?,
+        })
+    }
+
+    /// Total supplied tokens, as of the last exchange price update.
+    pub fn total_supply(&self, rounding: Rounding) -> Result<u64, ProgramError> {
+        total(
+            self.total_supply_with_interest,
+            self.supply_exchange_price,
+            self.total_supply_interest_free,
+            rounding,
+        )
+    }
+
+    /// Total borrowed tokens, as of the last exchange price update.
+    pub fn total_borrow(&self, rounding: Rounding) -> Result<u64, ProgramError> {
+        total(
+            self.total_borrow_with_interest,
+            self.borrow_exchange_price,
+            self.total_borrow_interest_free,
+            rounding,
+        )
+    }
+
+    /// Tokens held by the liquidity layer and not lent out, rounded down.
+    pub fn available_liquidity(&self) -> Result<u64, ProgramError> {
+        Ok(self
+            .total_supply(Rounding::Down)?
+            .saturating_sub(self.total_borrow(Rounding::Up)?))
+    }
+}
+
+/// A protocol's `UserSupplyPosition` on the Jupiter liquidity layer (e.g. an Earn vault's).
+pub struct JupiterUserSupplyPositionState {
+    /// Protocol owning the position
+    pub protocol: Address,
+    /// Supplied token mint
+    pub mint: Address,
+    /// Whether `amount` is a raw amount earning interest
+    pub with_interest: bool,
+    /// Supplied amount, raw when `with_interest`
+    pub amount: u64,
+    /// Withdrawal limit as of `last_update`, in the same unit as `amount`
+    pub withdrawal_limit: u128,
+    /// Timestamp of the last limit update
+    pub last_update: u64,
+    /// Share of the position the limit expands to, in basis points
+    pub expand_pct: u16,
+    /// Seconds for the limit to fully expand
+    pub expand_duration: u64,
+    /// Supply below which withdrawals are not limited
+    pub base_withdrawal_limit: u64,
+    /// Pause status, non-zero while the position is paused
+    pub status: u8,
+}
+
+impl JupiterUserSupplyPositionState {
+    /// Parses the relevant fields out of raw `UserSupplyPosition` account data.
+    ///
+    /// # Returns
+    /// * `Ok(JupiterUserSupplyPositionState)` - Parsed position
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Jupiter `UserSupplyPosition`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &USER_SUPPLY_POSITION_DISCRIMINATOR)?;
+
+        Ok(Self {
+            protocol: read_address(data, POSITION_PROTOCOL_OFFSET)?,
+            mint: read_address(data, POSITION_MINT_OFFSET)?,
+            with_interest: read_array::<1>(data, POSITION_WITH_INTEREST_OFFSET)?[0] != 0,
+            amount: read_u64(data, POSITION_AMOUNT_OFFSET)?,
+            withdrawal_limit: read_u128(data, POSITION_WITHDRAWAL_LIMIT_OFFSET)?,
+            last_update: read_u64(data, POSITION_LAST_UPDATE_OFFSET)?,
+            expand_pct: read_u16(data, POSITION_EXPAND_PCT_OFFSET)?,
+            expand_duration: read_u64(data, POSITION_EXPAND_DURATION_OFFSET)?,
+            base_withdrawal_limit: read_u64(data, POSITION_BASE_WITHDRAWAL_LIMIT_OFFSET)?,
+            status: read_array::<1>(data, POSITION_STATUS_OFFSET)?[0],
+        })
+    }
+
+    /// Whether the liquidity layer paused the position.
+    pub fn is_paused(&self) -> bool {
+        self.status != 0
+    }
+
+    /// Supplied tokens, rounded down.
+    pub fn supply(&self, reserve: &JupiterTokenReserveState) -> Result<u64, ProgramError> {
+        self.to_tokens(self.amount as u128, reserve)
+    }
+
+    /// Tokens the position can withdraw before reaching its withdrawal limit, rounded down.
+    ///
+    /// # Notes
+    /// * The limit expands toward `expand_pct` of the position over `expand_duration` after
+    ///   `last_update`. Without a clock this is not credited: the stored limit (or the fully
+    ///   expanded one, if higher) is used, so the result never overstates the withdrawable
+    ///   amount.
+    /// * A zero `withdrawal_limit` means the position is not limited.
+    pub fn withdrawable(&self, reserve: &JupiterTokenReserveState) -> Result<u64, ProgramError> {
+        let amount = self.amount as u128;
+        let limit = if self.withdrawal_limit == 0 {
+            0
+        } else {
+            let expandable = mul_div(
+                amount,
+                self.expand_pct as u128,
+                BPS_DENOMINATOR as u128,
+                Rounding::Down,
+            )?;
+            self.withdrawal_limit.max(amount.saturating_sub(expandable))
+        };
+
+        self.to_tokens(amount.saturating_sub(limit), reserve)
+    }
+
+    /// Converts an amount in the position's unit into tokens, rounded down.
+    fn to_tokens(
+        &self,
+        amount: u128,
+        reserve: &JupiterTokenReserveState,
+    ) -> Result<u64, ProgramError> {
+        if !self.with_interest {
+            return to_u64(amount);
+        }
+
+        to_u64(mul_div(
+            amount,
+            reserve.supply_exchange_price as u128,
+            JUPITER_EXCHANGE_PRICES_PRECISION as u128,
+            Rounding::Down,
+        )?)
+    }
+}
+
+/// Sums a raw amount scaled by `exchange_price` and an interest-free amount.
+fn total(
+    with_interest: u64,
+    exchange_price: u64,
+    interest_free: u64,
+    rounding: Rounding,
+) -> Result<u64, ProgramError> {
+    let with_interest = mul_div(
+        with_interest as u128,
+        exchange_price as u128,
+        JUPITER_EXCHANGE_PRICES_PRECISION as u128,
+        rounding,
+    )?;
+    to_u64(with_interest + interest_free as u128)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {super::*, crate::test_utils::address, std::vec::Vec};
+
+    /// Packs fields in IDL declaration order, independently of the offset constants.
+    struct Packed(Vec<u8>);
+
+    impl Packed {
+        fn new(discriminator: &[u8; 8]) -> Self {
+            Self(discriminator.to_vec())
+        }
+
+        fn push(mut self, bytes: &[u8]) -> Self {
+            self.0.extend_from_slice(bytes);
+            self
+        }
+    }
+
+    /// Reserve with exchange prices of 1.05 (supply) and 1.08 (borrow), at 81% utilization.
+    pub(crate) fn token_reserve_data() -> Vec<u8> {
+        Packed::new(&TOKEN_RESERVE_DISCRIMINATOR)
+            .push(address(1).as_ref()) // mint
+            .push(address(2).as_ref()) // vault
+            .push(&612u16.to_le_bytes()) // borrow_rate
+            .push(&1_000u16.to_le_bytes()) // fee_on_interest
+            .push(&8_136u16.to_le_bytes()) // last_utilization
+            .push(&1_760_000_000u64.to_le_bytes()) // last_update_timestamp
+            .push(&1_050_000_000_000u64.to_le_bytes()) // supply_exchange_price
+            .push(&1_080_000_000_000u64.to_le_bytes()) // borrow_exchange_price
+            .push(&9_500u16.to_le_bytes()) // max_utilization
+            .push(&400_000_000_000_000u64.to_le_bytes()) // total_supply_with_interest
+            .push(&20_000_000_000_000u64.to_le_bytes()) // total_supply_interest_free
+            .push(&300_000_000_000_000u64.to_le_bytes()) // total_borrow_with_interest
+            .push(&10_000_000_000_000u64.to_le_bytes()) // total_borrow_interest_free
+            .push(&0u64.to_le_bytes()) // total_claim_amount
+            .push(address(3).as_ref()) // interacting_protocol
+            .push(&0u64.to_le_bytes()) // interacting_timestamp
+            .push(&0u64.to_le_bytes()) // interacting_balance
+            .0
+    }
+
+    pub(crate) fn supply_position_data(status: u8) -> Vec<u8> {
+        Packed::new(&USER_SUPPLY_POSITION_DISCRIMINATOR)
+            .push(address(4).as_ref()) // protocol
+            .push(address(1).as_ref()) // mint
+            .push(&[1]) // with_interest
+            .push(&100_000_000_000_000u64.to_le_bytes()) // amount
+            .push(&80_000_000_000_000u128.to_le_bytes()) // withdrawal_limit
+            .push(&1_760_000_000u64.to_le_bytes()) // last_update
+            .push(&2_000u16.to_le_bytes()) // expand_pct
+            .push(&21_600u64.to_le_bytes()) // expand_duration
+            .push(&5_000_000_000u64.to_le_bytes()) // base_withdrawal_limit
+            .push(&[status]) // status
+            .0
+    }
+
+    pub(crate) fn liquidity_data(locked: bool) -> Vec<u8> {
+        Packed::new(&LIQUIDITY_DISCRIMINATOR)
+            .push(address(5).as_ref()) // authority
+            .push(address(6).as_ref()) // revenue_collector
+            .push(&[locked as u8]) // status
+            .push(&[255]) // bump
+            .0
+    }
+
+    #[test]
+    fn token_reserve_offsets_match_the_idl() {
+        let data = token_reserve_data();
+        assert_eq!(data.len(), 192);
+
+        let reserve = JupiterTokenReserveState::from_bytes(&data).unwrap();
+        assert_eq!(reserve.mint, address(1));
+        assert_eq!(reserve.vault, address(2));
+        assert_eq!(reserve.borrow_rate, 612);
+        assert_eq!(reserve.fee_on_interest, 1_000);
+        assert_eq!(reserve.last_utilization, 8_136);
+        assert_eq!(reserve.supply_exchange_price, 1_050_000_000_000);
+        assert_eq!(reserve.borrow_exchange_price, 1_080_000_000_000);
+        assert_eq!(reserve.max_utilization, 9_500);
+        assert_eq!(reserve.total_supply_with_interest, 400_000_000_000_000);
+        assert_eq!(reserve.total_supply_interest_free, 20_000_000_000_000);
+        assert_eq!(reserve.total_borrow_with_interest, 300_000_000_000_000);
+        assert_eq!(reserve.total_borrow_interest_free, 10_000_000_000_000);
+    }
+
+    #[test]
+    fn token_reserve_totals_apply_exchange_prices() {
+        let reserve = JupiterTokenReserveState::from_bytes(&token_reserve_data()).unwrap();
+        // 400e12 * 1.05 + 20e12 and 300e12 * 1.08 + 10e12
+        assert_eq!(
+            reserve.total_supply(Rounding::Down),
+            Ok(440_000_000_000_000)
+        );
+        assert_eq!(reserve.total_borrow(Rounding::Up), Ok(334_000_000_000_000));
+        assert_eq!(reserve.available_liquidity(), Ok(106_000_000_000_000));
+    }
+
+    #[test]
+    fn supply_position_offsets_match_the_idl() {
+        let data = supply_position_data(0);
+        assert_eq!(data.len(), 124);
+
+        let position = JupiterUserSupplyPositionState::from_bytes(&data).unwrap();
+        assert_eq!(position.protocol, address(4));
+        assert_eq!(position.mint, address(1));
+        assert!(position.with_interest);
+        assert_eq!(position.amount, 100_000_000_000_000);
+        assert_eq!(position.withdrawal_limit, 80_000_000_000_000);
+        assert_eq!(position.last_update, 1_760_000_000);
+        assert_eq!(position.expand_pct, 2_000);
+        assert_eq!(position.expand_duration, 21_600);
+        assert_eq!(position.base_withdrawal_limit, 5_000_000_000);
+        assert!(!position.is_paused());
+        assert!(
+            JupiterUserSupplyPositionState::from_bytes(&supply_position_data(1))
+                .unwrap()
+                .is_paused()
+        );
+    }
+
+    #[test]
+    fn supply_position_converts_raw_amounts() {
+        let reserve = JupiterTokenReserveState::from_bytes(&token_reserve_data()).unwrap();
+        let position =
+            JupiterUserSupplyPositionState::from_bytes(&supply_position_data(0)).unwrap();
+        assert_eq!(position.supply(&reserve), Ok(105_000_000_000_000));
+    }
+
+    #[test]
+    fn withdrawable_stops_at_the_withdrawal_limit() {
+        let reserve = JupiterTokenReserveState::from_bytes(&token_reserve_data()).unwrap();
+        let mut position =
+            JupiterUserSupplyPositionState::from_bytes(&supply_position_data(0)).unwrap();
+        // 100e12 raw supplied, 80e12 must stay: 20e12 raw at a 1.05 supply price.
+        assert_eq!(position.withdrawable(&reserve), Ok(21_000_000_000_000));
+
+        // The limit never drops below the fully expanded one, whatever was stored.
+        position.withdrawal_limit = 10_000_000_000_000;
+        assert_eq!(position.withdrawable(&reserve), Ok(21_000_000_000_000));
+
+        position.withdrawal_limit = 0;
+        assert_eq!(position.withdrawable(&reserve), Ok(105_000_000_000_000));
+    }
+
+    #[test]
+    fn liquidity_status_is_the_lockdown_flag() {
+        let data = liquidity_data(false);
+        assert_eq!(data.len(), 74);
+        assert!(!JupiterLiquidityState::from_bytes(&data).unwrap().locked);
+        assert!(
+            JupiterLiquidityState::from_bytes(&liquidity_data(true))
+                .unwrap()
+                .locked
+        );
+    }
+
+    #[test]
+    fn parsers_reject_other_accounts() {
+        let reserve = token_reserve_data();
+        assert!(matches!(
+            JupiterUserSupplyPositionState::from_bytes(&reserve),
+            Err(ProgramError::InvalidAccountData)
+        ));
+        assert!(matches!(
+            JupiterTokenReserveState::from_bytes(&reserve[..100]),
+            Err(ProgramError::AccountDataTooSmall)
+        ));
+    }
+}
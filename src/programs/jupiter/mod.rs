@@ -1,17 +1,58 @@
+mod errors;
+pub use errors::*;
+
+mod liquidity;
+pub use liquidity::*;
+
+mod pda;
+pub use pda::*;
+
+mod state;
+pub use state::*;
 use {
-    crate::Deposit,
+    crate::{
+        AccountCheck, AccountError, AccountSpec, BeethovenError, Capacity, Deposit, Preview,
+        programs::ProgramIds,
+        util::{Rounding, cpi::invoke_signed_with_remaining},
+    },
     core::mem::MaybeUninit,
     pinocchio::{
         AccountView, Address, ProgramResult,
-        cpi::{Signer, invoke_signed},
+        address::address_eq,
+        cpi::Signer,
         error::ProgramError,
         instruction::{InstructionAccount, InstructionView},
     },
 };
 
-pub const JUPITER_EARN_PROGRAM_ID: Address = Address::new_from_array([0u8; 32]); // TODO: Replace with actual Jupiter Earn program ID
+pub const JUPITER_EARN_PROGRAM_ID: Address = Address::new_from_array([
+    10, 254, 27, 145, 46, 72, 94, 149, 253, 21, 235, 41, 55, 223, 252, 75, 55, 163, 22, 208, 166,
+    56, 18, 255, 2, 186, 73, 180, 198, 193, 141, 30,
+]);
 pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
 
+/// Account order expected by `JupiterEarnDepositAccounts::try_from`.
+pub const JUPITER_EARN_DEPOSIT_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("lending_program"),
+    AccountSpec::writable_signer("signer"),
+    AccountSpec::writable("depositor_token_account"),
+    AccountSpec::writable("recipient_token_account"),
+    AccountSpec::readonly("mint"),
+    AccountSpec::readonly("lending_admin"),
+    AccountSpec::writable("lending"),
+    AccountSpec::writable("f_token_mint"),
+    AccountSpec::writable("supply_token_reserves_liquidity"),
+    AccountSpec::writable("lending_supply_position_on_liquidity"),
+    AccountSpec::readonly("rate_model"),
+    AccountSpec::writable("vault"),
+    AccountSpec::writable("liquidity"),
+    AccountSpec::writable("liquidity_program"),
+    AccountSpec::readonly("rewards_rate_model"),
+    AccountSpec::readonly("token_program"),
+    AccountSpec::readonly("associated_token_program"),
+    AccountSpec::readonly("system_program"),
+];
+
 /// Jupiter earn protocol integration
 pub struct JupiterEarn;
 
@@ -53,6 +94,8 @@ pub struct JupiterEarnDepositAccounts<'info> {
     pub associated_token_program: &'info AccountView,
     /// System program
     pub system_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
 }
 
 impl<'info> TryFrom<&'info [AccountView]> for JupiterEarnDepositAccounts<'info> {
@@ -66,16 +109,63 @@ impl<'info> TryFrom<&'info [AccountView]> for JupiterEarnDepositAccounts<'info>
     /// # Returns
     /// * `Ok(JupiterEarnDepositAccounts)` - Successfully parsed account context
     /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 18 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `JUPITER_EARN_PROGRAM_ID`
+    /// * `Err(ProgramError::InvalidAccountData)` - `liquidity_program` is not
+    ///   `JUPITER_LIQUIDITY_PROGRAM_ID`
     ///
     /// # Notes
-    /// * No upper bound is enforced - extra accounts are ignored (useful for `remaining_accounts`)
+    /// * No upper bound is enforced - extra accounts are captured in `remaining` and forwarded
+    ///   to the CPI, so protocols can require additional trailing accounts
     /// * Mutability and signer constraints are NOT validated here; Jupiter's program will
     ///   enforce them during CPI, providing clearer error messages
-    /// * The `..` pattern allows passing more than 18 accounts without error
     fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &JUPITER_EARN_PROGRAM_ID)
+    }
+}
+
+impl<'info> JupiterEarnDepositAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`
+    /// instead of `JUPITER_EARN_PROGRAM_ID` (e.g. for devnet or staging deployments).
+    ///
+    /// # Returns
+    /// * `Ok(JupiterEarnDepositAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 18 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `program_id`
+    /// * `Err(ProgramError::InvalidAccountData)` - `liquidity_program` is not
+    ///   `JUPITER_LIQUIDITY_PROGRAM_ID`
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        Self::parse(accounts, program_id, &JUPITER_LIQUIDITY_PROGRAM_ID)
+    }
+
+    /// Same as `try_from`, but validates the lending and liquidity programs against `ids`
+    /// instead of the mainnet deployments.
+    ///
+    /// # Returns
+    /// * `Ok(JupiterEarnDepositAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 18 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `ids.jupiter_earn`
+    /// * `Err(ProgramError::InvalidAccountData)` - `liquidity_program` is not
+    ///   `ids.jupiter_liquidity`
+    pub fn try_from_with_ids(
+        accounts: &'info [AccountView],
+        ids: &ProgramIds,
+    ) -> Result<Self, ProgramError> {
+        Self::parse(accounts, &ids.jupiter_earn, &ids.jupiter_liquidity)
+    }
+
+    fn parse(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+        liquidity_program_id: &Address,
+    ) -> Result<Self, ProgramError> {
         // Require minimum of 18 accounts to prevent undefined behavior
         if accounts.len() < 18 {
-            return Err(ProgramError::NotEnoughAccountKeys);
+            return Err(
+                AccountError::missing(JUPITER_EARN_DEPOSIT_ACCOUNTS, accounts.len()).into(),
+            );
         }
 
         let [
@@ -97,12 +187,33 @@ impl<'info> TryFrom<&'info [AccountView]> for JupiterEarnDepositAccounts<'info>
             token_program,
             associated_token_program,
             system_program,
-            ..,
+            remaining @ ..,
         ] = accounts
         else {
-            return Err(ProgramError::NotEnoughAccountKeys);
+            return Err(
+                AccountError::missing(JUPITER_EARN_DEPOSIT_ACCOUNTS, accounts.len()).into(),
+            );
         };
 
+        if !address_eq(lending_program.address(), program_id) {
+            return Err(AccountError::new(
+                JUPITER_EARN_DEPOSIT_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        // The liquidity layer accounts are only trusted once owned by this program.
+        if !address_eq(liquidity_program.address(), liquidity_program_id) {
+            return Err(AccountError::of(
+                JUPITER_EARN_DEPOSIT_ACCOUNTS,
+                "liquidity_program",
+                AccountCheck::Key,
+            )
+            .into());
+        }
+
         Ok(JupiterEarnDepositAccounts {
             signer,
             depositor_token_account,
@@ -122,8 +233,63 @@ impl<'info> TryFrom<&'info [AccountView]> for JupiterEarnDepositAccounts<'info>
             associated_token_program,
             system_program,
             lending_program,
+            remaining,
         })
     }
+
+    /// Reads the lending account, after checking it is owned by the lending program.
+    ///
+    /// # Returns
+    /// * `Ok(JupiterLendingState)` - Parsed lending state
+    /// * `Err(ProgramError::IllegalOwner)` - Lending account is not owned by the lending program
+    /// * `Err(ProgramError)` - Lending data could not be read
+    pub fn lending_state(&self) -> Result<JupiterLendingState, ProgramError> {
+        if !self.lending.owned_by(self.lending_program.address()) {
+            return Err(AccountError::of(
+                JUPITER_EARN_DEPOSIT_ACCOUNTS,
+                "lending",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        JupiterLendingState::from_bytes(&self.lending.try_borrow()?)
+    }
+
+    /// Reads the vault's token reserve and supply position on the liquidity layer.
+    ///
+    /// Both accounts must be owned by `liquidity_program` (checked against the configured
+    /// Jupiter Liquidity program when parsing) and be the ones the lending account records,
+    /// so substituted accounts cannot report made-up limits.
+    ///
+    /// # Returns
+    /// * `Ok((JupiterTokenReserveState, JupiterUserSupplyPositionState))` - Parsed accounts
+    /// * `Err(ProgramError::IllegalOwner)` - An account has the wrong owner
+    /// * `Err(ProgramError::InvalidAccountData)` - An account is not the one the lending
+    ///   account records
+    /// * `Err(ProgramError)` - Account data could not be read
+    pub fn liquidity_layer(
+        &self,
+    ) -> Result<(JupiterTokenReserveState, JupiterUserSupplyPositionState), ProgramError> {
+        let lending = self.lending_state()?;
+        let reserve = liquidity_account(
+            self.liquidity_program,
+            self.supply_token_reserves_liquidity,
+            "supply_token_reserves_liquidity",
+            &lending.token_reserves_liquidity,
+        )?;
+        let position = liquidity_account(
+            self.liquidity_program,
+            self.lending_supply_position_on_liquidity,
+            "lending_supply_position_on_liquidity",
+            &lending.supply_position_on_liquidity,
+        )?;
+
+        Ok((
+            JupiterTokenReserveState::from_bytes(&reserve.try_borrow()?)?,
+            JupiterUserSupplyPositionState::from_bytes(&position.try_borrow()?)?,
+        ))
+    }
 }
 
 impl<'info> Deposit<'info> for JupiterEarn {
@@ -197,14 +363,14 @@ impl<'info> Deposit<'info> for JupiterEarn {
         }
 
         let deposit_ix = InstructionView {
-            program_id: &JUPITER_EARN_PROGRAM_ID,
+            program_id: ctx.lending_program.address(),
             accounts: &accounts,
             data: unsafe {
                 core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 16)
             },
         };
 
-        invoke_signed(&deposit_ix, &account_infos, signer_seeds)?;
+        invoke_signed_with_remaining(&deposit_ix, &account_infos, ctx.remaining, signer_seeds)?;
 
         Ok(())
     }
@@ -213,3 +379,324 @@ impl<'info> Deposit<'info> for JupiterEarn {
         Self::deposit_signed(ctx, amount, &[])
     }
 }
+
+impl<'info> Preview<'info> for JupiterEarn {
+    type Accounts = JupiterEarnDepositAccounts<'info>;
+
+    /// Returns the fTokens expected for depositing `amount` into the lending account.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Expected fToken amount
+    /// * `Err(ProgramError::IllegalOwner)` - Lending account is not owned by the Jupiter Earn program
+    /// * `Err(ProgramError)` - Lending data could not be read
+    fn preview_deposit(
+        ctx: &JupiterEarnDepositAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        ctx.lending_state()?.assets_to_shares(amount)
+    }
+}
+
+impl<'info> Capacity<'info> for JupiterEarn {
+    type Accounts = JupiterEarnDepositAccounts<'info>;
+
+    /// Checks `amount` against the liquidity layer's supply cap for the token.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The deposit fits
+    /// * `Err(BeethovenError::DepositCapExceeded)` - The deposit would exceed the cap
+    /// * `Err(ProgramError)` - Liquidity layer accounts are invalid (see `liquidity_layer`)
+    fn check_capacity(ctx: &JupiterEarnDepositAccounts<'info>, amount: u64) -> ProgramResult {
+        if amount > Self::available_deposit_capacity(ctx)? {
+            return Err(BeethovenError::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the supply left before the token's total supply on the liquidity layer reaches
+    /// `JUPITER_MAX_TOKEN_AMOUNT_CAP`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Remaining supply, with the current total rounded up
+    /// * `Err(ProgramError)` - Liquidity layer accounts are invalid (see `liquidity_layer`)
+    fn available_deposit_capacity(
+        ctx: &JupiterEarnDepositAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        let (reserve, _) = ctx.liquidity_layer()?;
+        Ok(JUPITER_MAX_TOKEN_AMOUNT_CAP.saturating_sub(reserve.total_supply(Rounding::Up)?))
+    }
+    /// Returns what the vault can withdraw from the liquidity layer right now.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The lower of the vault's withdrawable supply (see
+    ///   `JupiterUserSupplyPositionState::withdrawable`) and the token's unborrowed liquidity
+    /// * `Err(ProgramError)` - Liquidity layer accounts are invalid (see `liquidity_layer`)
+    fn available_liquidity_for_withdraw(
+        ctx: &JupiterEarnDepositAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        let (reserve, position) = ctx.liquidity_layer()?;
+        Ok(position
+            .withdrawable(&reserve)?
+            .min(reserve.available_liquidity()?))
+    }
+
+    /// Returns `true` when the liquidity layer is not in lockdown and the vault's supply
+    /// position is not paused.
+    ///
+    /// Accounts that fail the `liquidity_layer` checks, or a `liquidity` account not owned
+    /// by the liquidity program, are reported as not operational.
+    fn is_operational(ctx: &JupiterEarnDepositAccounts<'info>) -> bool {
+        let position_open = ctx
+            .liquidity_layer()
+            .is_ok_and(|(_, position)| !position.is_paused());
+        let liquidity_open = ctx.liquidity.owned_by(ctx.liquidity_program.address())
+            && ctx
+                .liquidity
+                .try_borrow()
+                .ok()
+                .and_then(|data| JupiterLiquidityState::from_bytes(&data).ok())
+                .is_some_and(|state| !state.locked);
+
+        position_open && liquidity_open
+    }
+}
+
+/// Returns `account` once it is owned by `liquidity_program` and is the `expected` address.
+fn liquidity_account<'a>(
+    liquidity_program: &AccountView,
+    account: &'a AccountView,
+    name: &'static str,
+    expected: &Address,
+) -> Result<&'a AccountView, ProgramError> {
+    if !account.owned_by(liquidity_program.address()) {
+        return Err(
+            AccountError::of(JUPITER_EARN_DEPOSIT_ACCOUNTS, name, AccountCheck::Owner).into(),
+        );
+    }
+    if !address_eq(account.address(), expected) {
+        return Err(
+            AccountError::of(JUPITER_EARN_DEPOSIT_ACCOUNTS, name, AccountCheck::Key).into(),
+        );
+    }
+    Ok(account)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    #[cfg(feature = "client")]
+    pub(crate) use super::state::tests::lending_data_of;
+    use {
+        super::{liquidity::tests::*, state::tests::lending_data_for, *},
+        crate::test_utils::*,
+        std::vec::Vec,
+    };
+
+    // Indices into `JUPITER_EARN_DEPOSIT_ACCOUNTS`.
+    const LENDING: usize = 6;
+    const RESERVE: usize = 8;
+    const POSITION: usize = 9;
+    const LIQUIDITY: usize = 12;
+    const LIQUIDITY_PROGRAM: usize = 13;
+
+    /// Jupiter Earn deposit accounts whose lending, liquidity and position accounts parse.
+    pub(crate) fn deposit_accounts() -> Vec<TestAccount> {
+        deposit_accounts_for(&ProgramIds::MAINNET)
+    }
+
+    /// `deposit_accounts` for the Jupiter deployments in `ids`.
+    fn deposit_accounts_for(ids: &ProgramIds) -> Vec<TestAccount> {
+        let (earn, liquidity) = (&ids.jupiter_earn, &ids.jupiter_liquidity);
+        let (reserve, position) = (address(RESERVE as u8), address(POSITION as u8));
+        let mut accounts: Vec<TestAccount> =
+            (0..18u8).map(|i| TestAccount::empty(address(i))).collect();
+        accounts[0] = TestAccount::empty(earn.clone());
+        accounts[LIQUIDITY_PROGRAM] = TestAccount::empty(liquidity.clone());
+        accounts[LENDING] = TestAccount::new(
+            address(LENDING as u8),
+            earn.clone(),
+            &lending_data_for(&reserve, &position),
+        );
+        accounts[RESERVE] = TestAccount::new(reserve, liquidity.clone(), &token_reserve_data());
+        accounts[POSITION] =
+            TestAccount::new(position, liquidity.clone(), &supply_position_data(0));
+        accounts[LIQUIDITY] = TestAccount::new(
+            address(LIQUIDITY as u8),
+            liquidity.clone(),
+            &liquidity_data(false),
+        );
+        accounts
+    }
+
+    /// `deposit_accounts` with the vault's supply position paused.
+    pub(crate) fn paused_deposit_accounts() -> Vec<TestAccount> {
+        let mut accounts = deposit_accounts();
+        accounts[POSITION] = TestAccount::new(
+            address(POSITION as u8),
+            JUPITER_LIQUIDITY_PROGRAM_ID,
+            &supply_position_data(1),
+        );
+        accounts
+    }
+
+    /// The deposit CPI as a router would hand-roll it: no account checks, fixed indices.
+    fn hand_built_deposit(accounts: &[AccountView], amount: u64) -> ProgramResult {
+        let metas = [
+            InstructionAccount::writable_signer(accounts[1].address()),
+            InstructionAccount::writable(accounts[2].address()),
+            InstructionAccount::writable(accounts[3].address()),
+            InstructionAccount::readonly(accounts[4].address()),
+            InstructionAccount::readonly(accounts[5].address()),
+            InstructionAccount::writable(accounts[6].address()),
+            InstructionAccount::writable(accounts[7].address()),
+            InstructionAccount::writable(accounts[8].address()),
+            InstructionAccount::writable(accounts[9].address()),
+            InstructionAccount::readonly(accounts[10].address()),
+            InstructionAccount::writable(accounts[11].address()),
+            InstructionAccount::writable(accounts[12].address()),
+            InstructionAccount::writable(accounts[13].address()),
+            InstructionAccount::readonly(accounts[14].address()),
+            InstructionAccount::readonly(accounts[15].address()),
+            InstructionAccount::readonly(accounts[16].address()),
+            InstructionAccount::readonly(accounts[17].address()),
+        ];
+        let account_infos: [&AccountView; 17] = core::array::from_fn(|i| &accounts[i + 1]);
+
+        let mut data = [0u8; 16];
+        data[..8].copy_from_slice(&DEPOSIT_DISCRIMINATOR);
+        data[8..].copy_from_slice(&amount.to_le_bytes());
+
+        let ix = InstructionView {
+            program_id: accounts[0].address(),
+            accounts: &metas,
+            data: &data,
+        };
+        pinocchio::cpi::invoke_signed(&ix, &account_infos, &[])
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `make bench`"]
+    fn bench_deposit_context_against_the_typed_deposit() {
+        let mut accounts = deposit_accounts();
+        let views = views(&mut accounts);
+
+        bench("jupiter try_from_deposit_context + deposit", || {
+            let ctx = crate::try_from_deposit_context(core::hint::black_box(&views)).unwrap();
+            crate::DepositContext::deposit(&ctx, core::hint::black_box(1_000)).unwrap();
+        });
+        bench(
+            "jupiter JupiterEarnDepositAccounts::try_from + deposit",
+            || {
+                let ctx =
+                    JupiterEarnDepositAccounts::try_from(core::hint::black_box(views.as_slice()))
+                        .unwrap();
+                JupiterEarn::deposit(&ctx, core::hint::black_box(1_000)).unwrap();
+            },
+        );
+        bench("jupiter hand-built InstructionView", || {
+            hand_built_deposit(core::hint::black_box(&views), core::hint::black_box(1_000))
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn deposit_capacity_is_the_supply_headroom() {
+        let mut accounts = deposit_accounts();
+        let views = views(&mut accounts);
+        let ctx = JupiterEarnDepositAccounts::try_from(views.as_slice()).unwrap();
+
+        let capacity = JUPITER_MAX_TOKEN_AMOUNT_CAP - 440_000_000_000_000;
+        assert_eq!(JupiterEarn::available_deposit_capacity(&ctx), Ok(capacity));
+        assert_eq!(JupiterEarn::check_capacity(&ctx, capacity), Ok(()));
+        assert_eq!(
+            JupiterEarn::check_capacity(&ctx, capacity + 1),
+            Err(BeethovenError::DepositCapExceeded.into())
+        );
+    }
+
+    #[test]
+    fn withdraw_liquidity_is_bounded_by_the_position_limit() {
+        let mut accounts = deposit_accounts();
+        let views = views(&mut accounts);
+        let ctx = JupiterEarnDepositAccounts::try_from(views.as_slice()).unwrap();
+        assert_eq!(
+            JupiterEarn::available_liquidity_for_withdraw(&ctx),
+            Ok(21_000_000_000_000)
+        );
+    }
+
+    fn is_operational(accounts: &mut [TestAccount]) -> bool {
+        let views = views(accounts);
+        JupiterEarn::is_operational(
+            &JupiterEarnDepositAccounts::try_from(views.as_slice()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn is_operational_reads_lockdown_and_pause_flags() {
+        assert!(is_operational(&mut deposit_accounts()));
+
+        let mut accounts = deposit_accounts();
+        accounts[LIQUIDITY] = TestAccount::new(
+            address(LIQUIDITY as u8),
+            JUPITER_LIQUIDITY_PROGRAM_ID,
+            &liquidity_data(true),
+        );
+        assert!(!is_operational(&mut accounts));
+
+        assert!(!is_operational(&mut paused_deposit_accounts()));
+    }
+
+    #[test]
+    fn liquidity_layer_follows_the_configured_liquidity_program() {
+        let mut ids = ProgramIds::MAINNET;
+        ids.jupiter_earn = address(40);
+        ids.jupiter_liquidity = address(41);
+        let mut accounts = deposit_accounts_for(&ids);
+        let views = views(&mut accounts);
+
+        let ctx = JupiterEarnDepositAccounts::try_from_with_ids(&views, &ids).unwrap();
+        assert!(JupiterEarn::is_operational(&ctx));
+        assert_eq!(
+            JupiterEarn::available_liquidity_for_withdraw(&ctx),
+            Ok(21_000_000_000_000)
+        );
+
+        // The mainnet liquidity program does not own these accounts.
+        assert_eq!(
+            JupiterEarnDepositAccounts::try_from_with_program_id(&views, &ids.jupiter_earn).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn liquidity_layer_rejects_unrecorded_accounts() {
+        let mut accounts = deposit_accounts();
+        accounts[RESERVE] = TestAccount::new(
+            address(42),
+            JUPITER_LIQUIDITY_PROGRAM_ID,
+            &token_reserve_data(),
+        );
+        let views = views(&mut accounts);
+        let ctx = JupiterEarnDepositAccounts::try_from(views.as_slice()).unwrap();
+        assert_eq!(
+            JupiterEarn::available_deposit_capacity(&ctx),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn liquidity_layer_rejects_foreign_owners() {
+        let mut accounts = deposit_accounts();
+        accounts[POSITION] = TestAccount::new(
+            address(POSITION as u8),
+            address(42),
+            &supply_position_data(0),
+        );
+        let views = views(&mut accounts);
+        let ctx = JupiterEarnDepositAccounts::try_from(views.as_slice()).unwrap();
+        assert_eq!(
+            JupiterEarn::check_capacity(&ctx, 1),
+            Err(ProgramError::IllegalOwner)
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Seeds of the PDAs Jupiter Earn derives for its vaults and positions.
+//!
+//! The seed functions work everywhere (on-chain validation, client builders). The `find_*`
+//! functions derive the addresses on-chain, and off-chain with the `curve25519` feature
+//! (enabled by `client`).
+
+use pinocchio::Address;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+use {
+    super::{JUPITER_EARN_PROGRAM_ID, JUPITER_LIQUIDITY_PROGRAM_ID},
+    crate::util::find_program_address,
+};
+
+const LENDING_ADMIN_SEED: &[u8] = b"lending_admin";
+const F_TOKEN_MINT_SEED: &[u8] = b"f_token_mint";
+const LENDING_SEED: &[u8] = b"lending";
+const USER_SUPPLY_POSITION_SEED: &[u8] = b"user_supply_position";
+
+/// Seeds of the lending admin PDA (Jupiter Earn program).
+pub fn lending_admin_seeds() -> [&'static [u8]; 1] {
+    [LENDING_ADMIN_SEED]
+}
+
+/// Seeds of the fToken mint PDA for `mint` (Jupiter Earn program).
+pub fn f_token_mint_seeds(mint: &Address) -> [&[u8]; 2] {
+    [F_TOKEN_MINT_SEED, mint.as_ref()]
+}
+
+/// Seeds of the lending (vault) PDA for `mint` (Jupiter Earn program).
+pub fn lending_seeds<'a>(mint: &'a Address, f_token_mint: &'a Address) -> [&'a [u8]; 3] {
+    [LENDING_SEED, mint.as_ref(), f_token_mint.as_ref()]
+}
+
+/// Seeds of the vault's supply position on the liquidity layer (Jupiter Liquidity program).
+pub fn lending_supply_position_seeds<'a>(mint: &'a Address, lending: &'a Address) -> [&'a [u8]; 3] {
+    [USER_SUPPLY_POSITION_SEED, mint.as_ref(), lending.as_ref()]
+}
+
+/// Finds the lending admin PDA and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_lending_admin() -> (Address, u8) {
+    find_program_address(&lending_admin_seeds(), &JUPITER_EARN_PROGRAM_ID)
+}
+
+/// Finds the fToken mint PDA for `mint` and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_f_token_mint(mint: &Address) -> (Address, u8) {
+    find_program_address(&f_token_mint_seeds(mint), &JUPITER_EARN_PROGRAM_ID)
+}
+
+/// Finds the lending (vault) PDA for `mint` and `f_token_mint`, and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_lending(mint: &Address, f_token_mint: &Address) -> (Address, u8) {
+    find_program_address(&lending_seeds(mint, f_token_mint), &JUPITER_EARN_PROGRAM_ID)
+}
+
+/// Finds the vault's supply position on the liquidity layer and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_lending_supply_position(mint: &Address, lending: &Address) -> (Address, u8) {
+    find_program_address(
+        &lending_supply_position_seeds(mint, lending),
+        &JUPITER_LIQUIDITY_PROGRAM_ID,
+    )
+}
+
+#[cfg(all(test, feature = "curve25519"))]
+mod tests {
+    use super::*;
+
+    // EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v
+    const USDC: Address = Address::new_from_array([
+        198, 250, 122, 243, 190, 219, 173, 58, 61, 101, 243, 106, 171, 201, 116, 49, 177, 187, 228,
+        194, 210, 246, 224, 228, 124, 166, 2, 3, 69, 47, 93, 97,
+    ]);
+
+    #[test]
+    fn lending_admin_matches_mainnet() {
+        // 5nmGjA4s7ATzpBQXC5RNceRpaJ7pYw2wKsNBWyuSAZV6
+        let admin = Address::new_from_array([
+            71, 39, 241, 16, 249, 173, 156, 130, 143, 118, 246, 70, 174, 61, 102, 37, 108, 153, 39,
+            17, 190, 249, 247, 109, 79, 106, 132, 17, 192, 8, 19, 213,
+        ]);
+        assert_eq!(find_lending_admin(), (admin, 252));
+    }
+
+    #[test]
+    fn usdc_vault_matches_mainnet() {
+        // jlUSDC: 9BEcn9aPEmhSPbPQeFGjidRiEKki46fVQDyPpSQXPA2D
+        let f_token_mint = Address::new_from_array([
+            121, 124, 179, 60, 173, 108, 12, 128, 115, 178, 29, 164, 196, 55, 100, 5, 133, 237, 57,
+            246, 176, 70, 114, 200, 122, 81, 214, 5, 140, 183, 38, 250,
+        ]);
+        // 2vVYHYM8VYnvZqQWpTJSj8o8DBf1wM8pVs3bsTgYZiqJ
+        let lending = Address::new_from_array([
+            28, 143, 189, 73, 239, 79, 235, 190, 237, 9, 255, 36, 206, 215, 174, 248, 80, 108, 106,
+            122, 88, 214, 177, 188, 76, 207, 32, 75, 24, 100, 200, 133,
+        ]);
+        assert_eq!(find_f_token_mint(&USDC), (f_token_mint.clone(), 255));
+        assert_eq!(find_lending(&USDC, &f_token_mint), (lending, 255));
+    }
+}
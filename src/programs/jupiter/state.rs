@@ -0,0 +1,185 @@
+use {
+    crate::util::{Rounding, bytes::*, mul_div_u64},
+    pinocchio::{Address, error::ProgramError},
+};
+
+const LENDING_DISCRIMINATOR: [u8; 8] = [135, 199, 82, 16, 249, 131, 182, 241];
+
+// Byte offsets into Jupiter Earn's borsh `Lending` account (discriminator included).
+const LENDING_MINT_OFFSET: usize = 8;
+const LENDING_F_TOKEN_MINT_OFFSET: usize = 40;
+const LENDING_ID_OFFSET: usize = 72;
+const LENDING_DECIMALS_OFFSET: usize = 74;
+const LENDING_REWARDS_RATE_MODEL_OFFSET: usize = 75;
+const LENDING_LIQUIDITY_EXCHANGE_PRICE_OFFSET: usize = 107;
+const LENDING_TOKEN_EXCHANGE_PRICE_OFFSET: usize = 115;
+const LENDING_TOKEN_RESERVES_LIQUIDITY_OFFSET: usize = 131;
+const LENDING_SUPPLY_POSITION_ON_LIQUIDITY_OFFSET: usize = 163;
+
+/// Precision of Jupiter Earn exchange prices.
+pub const JUPITER_EXCHANGE_PRICES_PRECISION: u64 = 1_000_000_000_000;
+
+/// Jupiter Earn's `Lending` account: one vault of an underlying asset.
+///
+/// # Notes
+/// * An asset can have several vaults (distinct `lending_id`s). Supply totals, utilization
+///   and fees live on the liquidity layer accounts referenced by `token_reserves_liquidity`
+///   (`JupiterTokenReserveState`) and `supply_position_on_liquidity`
+///   (`JupiterUserSupplyPositionState`), not on the vault itself.
+pub struct JupiterLendingState {
+    /// Underlying token mint
+    pub mint: Address,
+    /// fToken (share) mint
+    pub f_token_mint: Address,
+    /// Vault id, distinguishing vaults of the same asset
+    pub lending_id: u16,
+    /// Decimals of `mint` (and of the fToken)
+    pub decimals: u8,
+    /// Rewards rate model of the vault
+    pub rewards_rate_model: Address,
+    /// Liquidity layer exchange price, scaled by `JUPITER_EXCHANGE_PRICES_PRECISION`
+    pub liquidity_exchange_price: u64,
+    /// Underlying tokens per fToken, scaled by `JUPITER_EXCHANGE_PRICES_PRECISION`
+    pub token_exchange_price: u64,
+    /// Token reserves account on the liquidity layer
+    pub token_reserves_liquidity: Address,
+    /// The vault's supply position on the liquidity layer
+    pub supply_position_on_liquidity: Address,
+}
+
+impl JupiterLendingState {
+    /// Parses the relevant fields out of raw `Lending` account data.
+    ///
+    /// # Returns
+    /// * `Ok(JupiterLendingState)` - Parsed lending state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Jupiter Earn `Lending`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &LENDING_DISCRIMINATOR)?;
+
+        Ok(Self {
+            mint: read_address(data, LENDING_MINT_OFFSET)?,
+            f_token_mint: read_address(data, LENDING_F_TOKEN_MINT_OFFSET)?,
+            lending_id: read_u16(data, LENDING_ID_OFFSET)?,
+            decimals: read_array::<1>(data, LENDING_DECIMALS_OFFSET)?[0],
+            rewards_rate_model: read_address(data, LENDING_REWARDS_RATE_MODEL_OFFSET)?,
+            liquidity_exchange_price: read_u64(data, LENDING_LIQUIDITY_EXCHANGE_PRICE_OFFSET)?,
+            token_exchange_price: read_u64(data, LENDING_TOKEN_EXCHANGE_PRICE_OFFSET)?,
+            token_reserves_liquidity: read_address(data, LENDING_TOKEN_RESERVES_LIQUIDITY_OFFSET)?,
+            supply_position_on_liquidity: read_address(
+                data,
+                LENDING_SUPPLY_POSITION_ON_LIQUIDITY_OFFSET,
+            )?,
+        })
+    }
+
+    /// fTokens minted for depositing `amount` of underlying tokens, rounded down.
+    pub fn assets_to_shares(&self, amount: u64) -> Result<u64, ProgramError> {
+        if self.token_exchange_price == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        mul_div_u64(
+            amount,
+            JUPITER_EXCHANGE_PRICES_PRECISION,
+            self.token_exchange_price,
+            Rounding::Down,
+        )
+    }
+
+    /// Underlying tokens redeemable for `shares` fTokens, rounded down.
+    ///
+    /// With the fToken mint supply as `shares`, this is the vault's total assets.
+    pub fn shares_to_assets(&self, shares: u64) -> Result<u64, ProgramError> {
+        mul_div_u64(
+            shares,
+            self.token_exchange_price,
+            JUPITER_EXCHANGE_PRICES_PRECISION,
+            Rounding::Down,
+        )
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {
+        super::*,
+        crate::test_utils::{PROPERTY_CASES, Rng, account_data},
+        std::vec::Vec,
+    };
+
+    pub(crate) fn lending_data(token_exchange_price: u64) -> Vec<u8> {
+        let mut data = account_data(&LENDING_DISCRIMINATOR, 200);
+        data[LENDING_DECIMALS_OFFSET] = 6;
+        data[LENDING_TOKEN_EXCHANGE_PRICE_OFFSET..LENDING_TOKEN_EXCHANGE_PRICE_OFFSET + 8]
+            .copy_from_slice(&token_exchange_price.to_le_bytes());
+        data
+    }
+
+    /// `Lending` bytes recording `token_reserves_liquidity` and `supply_position_on_liquidity`.
+    pub(crate) fn lending_data_for(reserve: &Address, position: &Address) -> Vec<u8> {
+        let mut data = lending_data(1_043_512_345_678);
+        data[LENDING_TOKEN_RESERVES_LIQUIDITY_OFFSET..LENDING_TOKEN_RESERVES_LIQUIDITY_OFFSET + 32]
+            .copy_from_slice(reserve.as_ref());
+        data[LENDING_SUPPLY_POSITION_ON_LIQUIDITY_OFFSET
+            ..LENDING_SUPPLY_POSITION_ON_LIQUIDITY_OFFSET + 32]
+            .copy_from_slice(position.as_ref());
+        data
+    }
+
+    /// `Lending` bytes for vault `lending_id` of `mint`.
+    pub(crate) fn lending_data_of(mint: &Address, lending_id: u16) -> Vec<u8> {
+        let mut data = lending_data(JUPITER_EXCHANGE_PRICES_PRECISION);
+        data[LENDING_MINT_OFFSET..LENDING_MINT_OFFSET + 32].copy_from_slice(mint.as_ref());
+        data[LENDING_ID_OFFSET..LENDING_ID_OFFSET + 2].copy_from_slice(&lending_id.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn assets_to_shares_matches_snapshot() {
+        // fToken worth 1.043512345678 underlying
+        let lending = JupiterLendingState::from_bytes(&lending_data(1_043_512_345_678)).unwrap();
+        assert_eq!(lending.decimals, 6);
+        assert_eq!(lending.assets_to_shares(1_000_000_000), Ok(958_302_030));
+        assert_eq!(lending.shares_to_assets(958_302_030), Ok(999_999_999));
+    }
+
+    #[test]
+    fn assets_to_shares_rejects_uninitialized_price() {
+        let lending = JupiterLendingState::from_bytes(&lending_data(0)).unwrap();
+        assert_eq!(
+            lending.assets_to_shares(1),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn reads_the_vault_mint_and_id() {
+        let mint = Address::new_from_array([1; 32]);
+        let lending = JupiterLendingState::from_bytes(&lending_data_of(&mint, 513)).unwrap();
+        assert_eq!(
+            (&lending.mint, lending.lending_id, lending.decimals),
+            (&mint, 513, 6)
+        );
+        assert_eq!(lending.assets_to_shares(1_000), Ok(1_000));
+    }
+
+    #[test]
+    fn round_trips_never_pay_out_more_than_was_paid_in() {
+        let mut rng = Rng::new(10);
+        for _ in 0..PROPERTY_CASES {
+            let price = rng.amount().max(1);
+            let lending = JupiterLendingState::from_bytes(&lending_data(price)).unwrap();
+            let (x, s) = (rng.amount(), rng.amount());
+
+            if let Ok(shares) = lending.assets_to_shares(x) {
+                let assets = lending.shares_to_assets(shares);
+                assert!(assets.is_ok_and(|assets| assets <= x));
+            }
+            if let Ok(assets) = lending.shares_to_assets(s) {
+                let shares = lending.assets_to_shares(assets);
+                assert!(shares.is_ok_and(|shares| shares <= s));
+            }
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use {
+    super::{
+        KAMINO_LEND_PROGRAM_ID, Kamino, KaminoReserveState, refresh::is_stale,
+        reserve_is_operational,
+    },
+    crate::{
+        AccountCheck, AccountError, AccountSpec, BeethovenError, Capacity, Deposit, Preview,
+        util::cpi::invoke_signed_with_remaining,
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+        sysvars::{Sysvar, clock::Clock},
+    },
+};
+
+const DEPOSIT_RESERVE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [169, 201, 30, 126, 6, 205, 102, 68];
+
+/// Account order expected by `KaminoDepositLiquidityAccounts::try_from`.
+pub const KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("kamino_lending_program"),
+    AccountSpec::readonly_signer("owner"),
+    AccountSpec::writable("reserve"),
+    AccountSpec::readonly("lending_market"),
+    AccountSpec::readonly("lending_market_authority"),
+    AccountSpec::readonly("reserve_liquidity_mint"),
+    AccountSpec::writable("reserve_liquidity_supply"),
+    AccountSpec::writable("reserve_collateral_mint"),
+    AccountSpec::writable("user_source_liquidity"),
+    AccountSpec::writable("user_destination_collateral"),
+    AccountSpec::readonly("collateral_token_program"),
+    AccountSpec::readonly("liquidity_token_program"),
+    AccountSpec::readonly("instruction_sysvar_account"),
+    AccountSpec::readonly("scope_oracle"),
+];
+
+/// Kamino lending protocol integration for liquidity-only deposits.
+///
+/// Deposits into a reserve and mints collateral (cTokens) straight to the user, without an
+/// obligation. Suited to vaults that never borrow, saving the obligation and farm accounts.
+pub struct KaminoLiquidity;
+
+/// Account context for Kamino's DepositReserveLiquidity instruction.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 14 accounts are present.
+///
+/// The reserve sits at index 2, where the obligation sits in `KaminoDepositAccounts`; this
+/// is how discrimination tells the two layouts apart.
+pub struct KaminoDepositLiquidityAccounts<'info> {
+    /// Kamino Lending Program (used for optional accounts)
+    pub kamino_lending_program: &'info AccountView,
+    /// Owner of the source liquidity (must be signer)
+    pub owner: &'info AccountView,
+    /// The reserve account being deposited into (writable)
+    pub reserve: &'info AccountView,
+    /// The lending market this operation belongs to
+    pub lending_market: &'info AccountView,
+    /// Lending market authority PDA
+    pub lending_market_authority: &'info AccountView,
+    /// Mint of the reserve's liquidity token
+    pub reserve_liquidity_mint: &'info AccountView,
+    /// Reserve's liquidity supply account (writable)
+    pub reserve_liquidity_supply: &'info AccountView,
+    /// Reserve's collateral token mint (writable)
+    pub reserve_collateral_mint: &'info AccountView,
+    /// User's source liquidity token account (writable)
+    pub user_source_liquidity: &'info AccountView,
+    /// User's destination collateral token account (writable)
+    pub user_destination_collateral: &'info AccountView,
+    /// Token program for collateral operations
+    pub collateral_token_program: &'info AccountView,
+    /// Token program for liquidity operations
+    pub liquidity_token_program: &'info AccountView,
+    /// Sysvar Instructions account for introspection
+    pub instruction_sysvar_account: &'info AccountView,
+    /// Scope Oracle
+    pub scope_oracle: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for KaminoDepositLiquidityAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `KaminoDepositLiquidityAccounts`.
+    ///
+    /// # Arguments
+    /// * `accounts` - Slice containing at least 14 accounts in the correct order
+    ///
+    /// # Returns
+    /// * `Ok(KaminoDepositLiquidityAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 14 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `KAMINO_LEND_PROGRAM_ID`
+    ///
+    /// # Notes
+    /// * No upper bound is enforced - extra accounts are captured in `remaining` and forwarded
+    ///   to the CPI, so protocols can require additional trailing accounts
+    /// * Mutability and signer constraints are NOT validated here; Kamino's program will
+    ///   enforce them during CPI, providing clearer error messages
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &KAMINO_LEND_PROGRAM_ID)
+    }
+}
+
+impl<'info> KaminoDepositLiquidityAccounts<'info> {
+    /// Returns `true` when `accounts` follow the liquidity-only layout, i.e. the account at
+    /// index 2 is a Kamino `Reserve` rather than an `Obligation`.
+    ///
+    /// The account must be owned by `program_id` before its data is trusted, so a spoofed
+    /// account carrying the `Reserve` discriminator cannot select this layout.
+    pub fn matches_layout(accounts: &[AccountView], program_id: &Address) -> bool {
+        accounts
+            .get(2)
+            .filter(|account| account.owned_by(program_id))
+            .and_then(|account| account.try_borrow().ok())
+            .is_some_and(|data| KaminoReserveState::is_reserve(&data))
+    }
+
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            kamino_lending_program,
+            owner,
+            reserve,
+            lending_market,
+            lending_market_authority,
+            reserve_liquidity_mint,
+            reserve_liquidity_supply,
+            reserve_collateral_mint,
+            user_source_liquidity,
+            user_destination_collateral,
+            collateral_token_program,
+            liquidity_token_program,
+            instruction_sysvar_account,
+            scope_oracle,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(
+                AccountError::missing(KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS, accounts.len()).into(),
+            );
+        };
+
+        if !address_eq(kamino_lending_program.address(), program_id) {
+            return Err(AccountError::new(
+                KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(KaminoDepositLiquidityAccounts {
+            kamino_lending_program,
+            owner,
+            reserve,
+            lending_market,
+            lending_market_authority,
+            reserve_liquidity_mint,
+            reserve_liquidity_supply,
+            reserve_collateral_mint,
+            user_source_liquidity,
+            user_destination_collateral,
+            collateral_token_program,
+            liquidity_token_program,
+            instruction_sysvar_account,
+            scope_oracle,
+            remaining,
+        })
+    }
+}
+
+impl<'info> Deposit<'info> for KaminoLiquidity {
+    type Accounts = KaminoDepositLiquidityAccounts<'info>;
+
+    /// Refreshes the reserve when stale, then deposits liquidity and mints collateral to the user via CPI.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context required for the deposit (see `KaminoDepositLiquidityAccounts`)
+    /// * `amount` - Amount of liquidity tokens to deposit
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    fn deposit_signed(
+        ctx: &KaminoDepositLiquidityAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        if is_stale(ctx.reserve, Clock::get()?.slot)? {
+            Kamino::refresh_reserve(
+                ctx.kamino_lending_program,
+                ctx.reserve,
+                ctx.lending_market,
+                ctx.scope_oracle,
+            )?;
+        }
+
+        // Deposit CPI
+        let accounts = [
+            InstructionAccount::readonly_signer(ctx.owner.address()),
+            InstructionAccount::writable(ctx.reserve.address()),
+            InstructionAccount::readonly(ctx.lending_market.address()),
+            InstructionAccount::readonly(ctx.lending_market_authority.address()),
+            InstructionAccount::readonly(ctx.reserve_liquidity_mint.address()),
+            InstructionAccount::writable(ctx.reserve_liquidity_supply.address()),
+            InstructionAccount::writable(ctx.reserve_collateral_mint.address()),
+            InstructionAccount::writable(ctx.user_source_liquidity.address()),
+            InstructionAccount::writable(ctx.user_destination_collateral.address()),
+            InstructionAccount::readonly(ctx.collateral_token_program.address()),
+            InstructionAccount::readonly(ctx.liquidity_token_program.address()),
+            InstructionAccount::readonly(ctx.instruction_sysvar_account.address()),
+        ];
+
+        let account_infos = [
+            ctx.owner,
+            ctx.reserve,
+            ctx.lending_market,
+            ctx.lending_market_authority,
+            ctx.reserve_liquidity_mint,
+            ctx.reserve_liquidity_supply,
+            ctx.reserve_collateral_mint,
+            ctx.user_source_liquidity,
+            ctx.user_destination_collateral,
+            ctx.collateral_token_program,
+            ctx.liquidity_token_program,
+            ctx.instruction_sysvar_account,
+        ];
+
+        let mut instruction_data = MaybeUninit::<[u8; 16]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(
+                DEPOSIT_RESERVE_LIQUIDITY_DISCRIMINATOR.as_ptr(),
+                ptr,
+                8,
+            );
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(8), 8);
+        }
+
+        let deposit_ix = InstructionView {
+            program_id: ctx.kamino_lending_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 16)
+            },
+        };
+
+        invoke_signed_with_remaining(&deposit_ix, &account_infos, ctx.remaining, signer_seeds)?;
+
+        Ok(())
+    }
+
+    fn deposit(ctx: &KaminoDepositLiquidityAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::deposit_signed(ctx, amount, &[])
+    }
+}
+
+impl<'info> Preview<'info> for KaminoLiquidity {
+    type Accounts = KaminoDepositLiquidityAccounts<'info>;
+
+    /// Returns the collateral (cTokens) expected for depositing `amount` into the reserve.
+    fn preview_deposit(
+        ctx: &KaminoDepositLiquidityAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.reserve.owned_by(ctx.kamino_lending_program.address()) {
+            return Err(AccountError::of(
+                KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+                "reserve",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.reserve.try_borrow()?;
+        KaminoReserveState::from_bytes(&data)?.liquidity_to_collateral(amount)
+    }
+}
+
+impl<'info> Capacity<'info> for KaminoLiquidity {
+    type Accounts = KaminoDepositLiquidityAccounts<'info>;
+
+    /// Checks `amount` against the reserve's `deposit_limit`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The deposit fits within the reserve's deposit limit
+    /// * `Err(BeethovenError::DepositCapExceeded)` - The deposit would exceed the limit
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    fn check_capacity(ctx: &KaminoDepositLiquidityAccounts<'info>, amount: u64) -> ProgramResult {
+        if amount > Self::available_deposit_capacity(ctx)? {
+            return Err(BeethovenError::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the liquidity left before the reserve's `deposit_limit`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - `deposit_limit` minus the reserve's total liquidity, floored at zero
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    fn available_deposit_capacity(
+        ctx: &KaminoDepositLiquidityAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.reserve.owned_by(ctx.kamino_lending_program.address()) {
+            return Err(AccountError::of(
+                KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+                "reserve",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.reserve.try_borrow()?;
+        KaminoReserveState::from_bytes(&data)?.deposit_capacity()
+    }
+
+    /// Returns the reserve's available (unborrowed) liquidity.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Liquidity a withdrawal can be paid out of
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    fn available_liquidity_for_withdraw(
+        ctx: &KaminoDepositLiquidityAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.reserve.owned_by(ctx.kamino_lending_program.address()) {
+            return Err(AccountError::of(
+                KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+                "reserve",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.reserve.try_borrow()?;
+        Ok(KaminoReserveState::from_bytes(&data)?.available_amount)
+    }
+
+    /// Returns `true` when the reserve is active and the market is not in emergency mode.
+    fn is_operational(ctx: &KaminoDepositLiquidityAccounts<'info>) -> bool {
+        reserve_is_operational(ctx.kamino_lending_program, ctx.reserve, ctx.lending_market)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            programs::kamino::state::RESERVE_DISCRIMINATOR,
+            test_utils::{TestAccount, account_data, address},
+        },
+    };
+
+    fn layout_with_index_2(owner: Address) -> [TestAccount; 3] {
+        [
+            TestAccount::empty(KAMINO_LEND_PROGRAM_ID),
+            TestAccount::empty(address(1)),
+            TestAccount::new(address(2), owner, &account_data(&RESERVE_DISCRIMINATOR, 8)),
+        ]
+    }
+
+    #[test]
+    fn matches_layout_accepts_program_owned_reserve() {
+        let mut accounts = layout_with_index_2(KAMINO_LEND_PROGRAM_ID);
+        let views = crate::test_utils::views(&mut accounts);
+        assert!(KaminoDepositLiquidityAccounts::matches_layout(
+            &views,
+            &KAMINO_LEND_PROGRAM_ID
+        ));
+    }
+
+    #[test]
+    fn matches_layout_rejects_spoofed_reserve() {
+        let mut accounts = layout_with_index_2(address(9));
+        let views = crate::test_utils::views(&mut accounts);
+        assert!(!KaminoDepositLiquidityAccounts::matches_layout(
+            &views,
+            &KAMINO_LEND_PROGRAM_ID
+        ));
+    }
+
+    #[test]
+    fn matches_layout_rejects_non_reserve_data() {
+        let mut accounts = layout_with_index_2(KAMINO_LEND_PROGRAM_ID);
+        accounts[2] = TestAccount::new(address(2), KAMINO_LEND_PROGRAM_ID, &[0; 8]);
+        let views = crate::test_utils::views(&mut accounts);
+        assert!(!KaminoDepositLiquidityAccounts::matches_layout(
+            &views,
+            &KAMINO_LEND_PROGRAM_ID
+        ));
+        assert!(!KaminoDepositLiquidityAccounts::matches_layout(
+            &views[..2],
+            &KAMINO_LEND_PROGRAM_ID
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use {
+    super::{
+        KAMINO_HEALTH_ACCOUNTS, KAMINO_LEND_PROGRAM_ID, Kamino, KaminoHealthAccounts,
+        KaminoObligationState,
+    },
+    crate::{
+        AccountCheck, AccountError, AccountSpec, BeethovenError,
+        util::cpi::invoke_signed_with_remaining,
+    },
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+const REQUEST_ELEVATION_GROUP_DISCRIMINATOR: [u8; 8] = [36, 119, 251, 129, 34, 240, 7, 147];
+
+/// Account order expected by `KaminoRequestElevationGroupAccounts::try_from`.
+///
+/// Followed by the obligation's deposit reserves, then its borrow reserves.
+pub const KAMINO_REQUEST_ELEVATION_GROUP_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("kamino_lending_program"),
+    AccountSpec::readonly_signer("owner"),
+    AccountSpec::writable("obligation"),
+    AccountSpec::readonly("lending_market"),
+];
+
+/// Account context for Kamino's RequestElevationGroup instruction.
+///
+/// Moves an obligation into an elevation group (eMode), unlocking the group's LTVs for
+/// correlated assets. The obligation must be refreshed earlier in the same transaction.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 4 accounts are present.
+pub struct KaminoRequestElevationGroupAccounts<'info> {
+    /// Kamino Lending Program
+    pub kamino_lending_program: &'info AccountView,
+    /// Obligation owner (signer)
+    pub owner: &'info AccountView,
+    /// The obligation to move (writable)
+    pub obligation: &'info AccountView,
+    /// The lending market
+    pub lending_market: &'info AccountView,
+    /// The obligation's deposit reserves, then its borrow reserves
+    pub reserves: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for KaminoRequestElevationGroupAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `KaminoRequestElevationGroupAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoRequestElevationGroupAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 4 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `KAMINO_LEND_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &KAMINO_LEND_PROGRAM_ID)
+    }
+}
+
+impl<'info> KaminoRequestElevationGroupAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            kamino_lending_program,
+            owner,
+            obligation,
+            lending_market,
+            reserves @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(
+                KAMINO_REQUEST_ELEVATION_GROUP_ACCOUNTS,
+                accounts.len(),
+            )
+            .into());
+        };
+
+        if !address_eq(kamino_lending_program.address(), program_id) {
+            return Err(AccountError::new(
+                KAMINO_REQUEST_ELEVATION_GROUP_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            kamino_lending_program,
+            owner,
+            obligation,
+            lending_market,
+            reserves,
+        })
+    }
+}
+
+impl Kamino {
+    /// Requests that the obligation be moved into `elevation_group`, `0` leaving eMode.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `KaminoRequestElevationGroupAccounts`)
+    /// * `elevation_group` - Target elevation group id, as configured on the lending market
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    ///
+    /// # Returns
+    /// * `Ok(())` - The obligation now borrows under `elevation_group`
+    /// * `Err(ProgramError)` - CPI failure (stale obligation, incompatible positions, ...)
+    pub fn request_elevation_group_signed(
+        ctx: &KaminoRequestElevationGroupAccounts,
+        elevation_group: u8,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::readonly_signer(ctx.owner.address()),
+            InstructionAccount::writable(ctx.obligation.address()),
+            InstructionAccount::readonly(ctx.lending_market.address()),
+        ];
+
+        let account_infos = [ctx.owner, ctx.obligation, ctx.lending_market];
+
+        // Build instruction data: discriminator (8 bytes) + elevation_group (1 byte)
+        let mut instruction_data = [0u8; 9];
+        instruction_data[..8].copy_from_slice(&REQUEST_ELEVATION_GROUP_DISCRIMINATOR);
+        instruction_data[8] = elevation_group;
+
+        let request_ix = InstructionView {
+            program_id: ctx.kamino_lending_program.address(),
+            accounts: &accounts,
+            data: &instruction_data,
+        };
+
+        invoke_signed_with_remaining(&request_ix, &account_infos, ctx.reserves, signer_seeds)
+    }
+
+    pub fn request_elevation_group(
+        ctx: &KaminoRequestElevationGroupAccounts,
+        elevation_group: u8,
+    ) -> ProgramResult {
+        Self::request_elevation_group_signed(ctx, elevation_group, &[])
+    }
+
+    /// Checks that the obligation borrows under `expected`, e.g. before borrowing against
+    /// eMode LTVs.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The obligation is in `expected`
+    /// * `Err(BeethovenError::ElevationGroupMismatch)` - The obligation is in another group
+    /// * `Err(ProgramError::IllegalOwner)` - Obligation is not owned by the Kamino program
+    pub fn check_elevation_group(ctx: &KaminoHealthAccounts, expected: u8) -> ProgramResult {
+        if !ctx
+            .obligation
+            .owned_by(ctx.kamino_lending_program.address())
+        {
+            return Err(AccountError::of(
+                KAMINO_HEALTH_ACCOUNTS,
+                "obligation",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.obligation.try_borrow()?;
+        if KaminoObligationState::from_bytes(&data)?.elevation_group != expected {
+            return Err(BeethovenError::ElevationGroupMismatch.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{super::state::tests::obligation_data, *},
+        crate::test_utils::{TestAccount, address, views},
+    };
+
+    fn check(obligation_owner: Address, expected: u8) -> ProgramResult {
+        let mut accounts = [
+            TestAccount::empty(KAMINO_LEND_PROGRAM_ID),
+            TestAccount::new(
+                address(1),
+                obligation_owner,
+                &obligation_data(100, 50, 50, 80),
+            ),
+        ];
+        let accounts = views(&mut accounts);
+        Kamino::check_elevation_group(&KaminoHealthAccounts::try_from(&accounts[..])?, expected)
+    }
+
+    #[test]
+    fn checks_the_obligation_elevation_group() {
+        assert_eq!(check(KAMINO_LEND_PROGRAM_ID, 2), Ok(()));
+        assert_eq!(
+            check(KAMINO_LEND_PROGRAM_ID, 0),
+            Err(BeethovenError::ElevationGroupMismatch.into())
+        );
+        assert_eq!(check(address(9), 2), Err(ProgramError::IllegalOwner));
+    }
+
+    #[test]
+    fn request_accounts_keep_the_reserves() {
+        let mut accounts = [
+            TestAccount::empty(KAMINO_LEND_PROGRAM_ID),
+            TestAccount::empty(address(1)).signer(),
+            TestAccount::empty(address(2)),
+            TestAccount::empty(address(3)),
+            TestAccount::empty(address(4)),
+            TestAccount::empty(address(5)),
+        ];
+        let accounts = views(&mut accounts);
+        let ctx = KaminoRequestElevationGroupAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(ctx.reserves.len(), 2);
+        assert_eq!(Kamino::request_elevation_group(&ctx, 2), Ok(()));
+
+        assert!(matches!(
+            KaminoRequestElevationGroupAccounts::try_from(&accounts[..3]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+        assert!(matches!(
+            KaminoRequestElevationGroupAccounts::try_from(&accounts[1..]),
+            Err(ProgramError::IncorrectProgramId)
+        ));
+    }
+}
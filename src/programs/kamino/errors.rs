@@ -0,0 +1,17 @@
+use crate::BeethovenError;
+
+/// Kamino Lend (`LendingError`) codes translated into unified beethoven errors.
+///
+/// Codes follow klend's Anchor error numbering (`6000 + variant index`).
+pub const KAMINO_ERROR_TABLE: &[(u32, BeethovenError)] = &[
+    (6009, BeethovenError::InsufficientLiquidity), // InsufficientLiquidity
+    (6010, BeethovenError::RefreshRequired),       // ReserveStale
+    (6018, BeethovenError::RefreshRequired),       // ObligationStale
+    (6040, BeethovenError::StaleOracle),           // PriceTooOld
+    (6043, BeethovenError::VenuePaused),           // GlobalEmergencyMode
+    (6049, BeethovenError::OracleConfidenceTooWide), // PriceConfidenceTooWide
+    (6065, BeethovenError::InsufficientLiquidity), // WithdrawalCapReached
+    (6074, BeethovenError::VenuePaused),           // ReserveDeprecated
+    (6081, BeethovenError::VenuePaused),           // CpiDisabled
+    (6091, BeethovenError::DepositCapExceeded),    // DepositLimitExceeded
+];
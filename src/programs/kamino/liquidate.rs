@@ -0,0 +1,368 @@
+use {
+    super::{KAMINO_LEND_PROGRAM_ID, Kamino},
+    crate::{
+        AccountCheck, AccountError, AccountSpec, Liquidate, util::cpi::invoke_signed_with_remaining,
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+const LIQUIDATE_OBLIGATION_AND_REDEEM_RESERVE_COLLATERAL_V2_DISCRIMINATOR: [u8; 8] =
+    [162, 161, 35, 143, 30, 187, 185, 103];
+
+/// Account order expected by `KaminoLiquidateAccounts::try_from`.
+pub const KAMINO_LIQUIDATE_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("kamino_lending_program"),
+    AccountSpec::readonly_signer("liquidator"),
+    AccountSpec::writable("obligation"),
+    AccountSpec::readonly("lending_market"),
+    AccountSpec::readonly("lending_market_authority"),
+    AccountSpec::writable("repay_reserve"),
+    AccountSpec::readonly("repay_reserve_liquidity_mint"),
+    AccountSpec::writable("repay_reserve_liquidity_supply"),
+    AccountSpec::writable("withdraw_reserve"),
+    AccountSpec::readonly("withdraw_reserve_liquidity_mint"),
+    AccountSpec::writable("withdraw_reserve_collateral_mint"),
+    AccountSpec::writable("withdraw_reserve_collateral_supply"),
+    AccountSpec::writable("withdraw_reserve_liquidity_supply"),
+    AccountSpec::writable("withdraw_reserve_liquidity_fee_receiver"),
+    AccountSpec::writable("user_source_liquidity"),
+    AccountSpec::writable("user_destination_collateral"),
+    AccountSpec::writable("user_destination_liquidity"),
+    AccountSpec::readonly("collateral_token_program"),
+    AccountSpec::readonly("repay_liquidity_token_program"),
+    AccountSpec::readonly("withdraw_liquidity_token_program"),
+    AccountSpec::readonly("instruction_sysvar_account"),
+    AccountSpec::writable("collateral_obligation_farm_user_state"),
+    AccountSpec::writable("collateral_reserve_farm_state"),
+    AccountSpec::writable("debt_obligation_farm_user_state"),
+    AccountSpec::writable("debt_reserve_farm_state"),
+    AccountSpec::readonly("farms_program"),
+];
+
+/// Account context for Kamino's LiquidateObligationAndRedeemReserveCollateralV2 instruction.
+///
+/// Repays debt of an unhealthy obligation and redeems the seized collateral into liquidity.
+/// Reserves and the obligation must be refreshed earlier in the same transaction.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 26 accounts are present.
+pub struct KaminoLiquidateAccounts<'info> {
+    /// Kamino Lending Program (used for optional accounts)
+    pub kamino_lending_program: &'info AccountView,
+    /// Liquidator repaying the debt (must be signer)
+    pub liquidator: &'info AccountView,
+    /// The unhealthy obligation being liquidated (writable)
+    pub obligation: &'info AccountView,
+    /// The lending market this operation belongs to
+    pub lending_market: &'info AccountView,
+    /// Lending market authority PDA
+    pub lending_market_authority: &'info AccountView,
+    /// Reserve of the debt being repaid (writable)
+    pub repay_reserve: &'info AccountView,
+    /// Mint of the repaid liquidity
+    pub repay_reserve_liquidity_mint: &'info AccountView,
+    /// Repay reserve's liquidity supply account (writable)
+    pub repay_reserve_liquidity_supply: &'info AccountView,
+    /// Reserve of the collateral being seized (writable)
+    pub withdraw_reserve: &'info AccountView,
+    /// Mint of the seized reserve's liquidity
+    pub withdraw_reserve_liquidity_mint: &'info AccountView,
+    /// Seized reserve's collateral mint (writable)
+    pub withdraw_reserve_collateral_mint: &'info AccountView,
+    /// Seized reserve's collateral supply account (writable)
+    pub withdraw_reserve_collateral_supply: &'info AccountView,
+    /// Seized reserve's liquidity supply account (writable)
+    pub withdraw_reserve_liquidity_supply: &'info AccountView,
+    /// Seized reserve's liquidity fee receiver (writable)
+    pub withdraw_reserve_liquidity_fee_receiver: &'info AccountView,
+    /// Liquidator's source liquidity token account (writable)
+    pub user_source_liquidity: &'info AccountView,
+    /// Liquidator's destination collateral token account (writable)
+    pub user_destination_collateral: &'info AccountView,
+    /// Liquidator's destination liquidity token account (writable)
+    pub user_destination_liquidity: &'info AccountView,
+    /// Token program for collateral operations
+    pub collateral_token_program: &'info AccountView,
+    /// Token program of the repaid liquidity
+    pub repay_liquidity_token_program: &'info AccountView,
+    /// Token program of the seized liquidity
+    pub withdraw_liquidity_token_program: &'info AccountView,
+    /// Sysvar Instructions account for introspection
+    pub instruction_sysvar_account: &'info AccountView,
+    /// Obligation's farm user state for the collateral reserve (writable, can be program ID)
+    pub collateral_obligation_farm_user_state: &'info AccountView,
+    /// Collateral reserve's farm state (writable, can be program ID)
+    pub collateral_reserve_farm_state: &'info AccountView,
+    /// Obligation's farm user state for the debt reserve (writable, can be program ID)
+    pub debt_obligation_farm_user_state: &'info AccountView,
+    /// Debt reserve's farm state (writable, can be program ID)
+    pub debt_reserve_farm_state: &'info AccountView,
+    /// Farms program
+    pub farms_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for KaminoLiquidateAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `KaminoLiquidateAccounts`.
+    ///
+    /// # Arguments
+    /// * `accounts` - Slice containing at least 26 accounts in the correct order
+    ///
+    /// # Returns
+    /// * `Ok(KaminoLiquidateAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 26 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `KAMINO_LEND_PROGRAM_ID`
+    ///
+    /// # Notes
+    /// * No upper bound is enforced - extra accounts are captured in `remaining` and forwarded
+    ///   to the CPI, so protocols can require additional trailing accounts
+    /// * Mutability and signer constraints are NOT validated here; Kamino's program will
+    ///   enforce them during CPI, providing clearer error messages
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &KAMINO_LEND_PROGRAM_ID)
+    }
+}
+
+impl<'info> KaminoLiquidateAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            kamino_lending_program,
+            liquidator,
+            obligation,
+            lending_market,
+            lending_market_authority,
+            repay_reserve,
+            repay_reserve_liquidity_mint,
+            repay_reserve_liquidity_supply,
+            withdraw_reserve,
+            withdraw_reserve_liquidity_mint,
+            withdraw_reserve_collateral_mint,
+            withdraw_reserve_collateral_supply,
+            withdraw_reserve_liquidity_supply,
+            withdraw_reserve_liquidity_fee_receiver,
+            user_source_liquidity,
+            user_destination_collateral,
+            user_destination_liquidity,
+            collateral_token_program,
+            repay_liquidity_token_program,
+            withdraw_liquidity_token_program,
+            instruction_sysvar_account,
+            collateral_obligation_farm_user_state,
+            collateral_reserve_farm_state,
+            debt_obligation_farm_user_state,
+            debt_reserve_farm_state,
+            farms_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(KAMINO_LIQUIDATE_ACCOUNTS, accounts.len()).into());
+        };
+
+        if !address_eq(kamino_lending_program.address(), program_id) {
+            return Err(
+                AccountError::new(KAMINO_LIQUIDATE_ACCOUNTS, 0, AccountCheck::ProgramId).into(),
+            );
+        }
+
+        Ok(KaminoLiquidateAccounts {
+            kamino_lending_program,
+            liquidator,
+            obligation,
+            lending_market,
+            lending_market_authority,
+            repay_reserve,
+            repay_reserve_liquidity_mint,
+            repay_reserve_liquidity_supply,
+            withdraw_reserve,
+            withdraw_reserve_liquidity_mint,
+            withdraw_reserve_collateral_mint,
+            withdraw_reserve_collateral_supply,
+            withdraw_reserve_liquidity_supply,
+            withdraw_reserve_liquidity_fee_receiver,
+            user_source_liquidity,
+            user_destination_collateral,
+            user_destination_liquidity,
+            collateral_token_program,
+            repay_liquidity_token_program,
+            withdraw_liquidity_token_program,
+            instruction_sysvar_account,
+            collateral_obligation_farm_user_state,
+            collateral_reserve_farm_state,
+            debt_obligation_farm_user_state,
+            debt_reserve_farm_state,
+            farms_program,
+            remaining,
+        })
+    }
+}
+
+impl<'info> Liquidate<'info> for Kamino {
+    type Accounts = KaminoLiquidateAccounts<'info>;
+
+    /// Liquidates a Kamino obligation via CPI and redeems the seized collateral.
+    ///
+    /// The LTV override argument is always 0 (no override), which is the only value
+    /// Kamino accepts from non-admin liquidators.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context required for the liquidation (see `KaminoLiquidateAccounts`)
+    /// * `liquidity_amount` - Amount of debt liquidity to repay
+    /// * `min_received` - Minimum liquidity to receive after redeeming the seized collateral
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    fn liquidate_signed(
+        ctx: &KaminoLiquidateAccounts<'info>,
+        liquidity_amount: u64,
+        min_received: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::readonly_signer(ctx.liquidator.address()),
+            InstructionAccount::writable(ctx.obligation.address()),
+            InstructionAccount::readonly(ctx.lending_market.address()),
+            InstructionAccount::readonly(ctx.lending_market_authority.address()),
+            InstructionAccount::writable(ctx.repay_reserve.address()),
+            InstructionAccount::readonly(ctx.repay_reserve_liquidity_mint.address()),
+            InstructionAccount::writable(ctx.repay_reserve_liquidity_supply.address()),
+            InstructionAccount::writable(ctx.withdraw_reserve.address()),
+            InstructionAccount::readonly(ctx.withdraw_reserve_liquidity_mint.address()),
+            InstructionAccount::writable(ctx.withdraw_reserve_collateral_mint.address()),
+            InstructionAccount::writable(ctx.withdraw_reserve_collateral_supply.address()),
+            InstructionAccount::writable(ctx.withdraw_reserve_liquidity_supply.address()),
+            InstructionAccount::writable(ctx.withdraw_reserve_liquidity_fee_receiver.address()),
+            InstructionAccount::writable(ctx.user_source_liquidity.address()),
+            InstructionAccount::writable(ctx.user_destination_collateral.address()),
+            InstructionAccount::writable(ctx.user_destination_liquidity.address()),
+            InstructionAccount::readonly(ctx.collateral_token_program.address()),
+            InstructionAccount::readonly(ctx.repay_liquidity_token_program.address()),
+            InstructionAccount::readonly(ctx.withdraw_liquidity_token_program.address()),
+            InstructionAccount::readonly(ctx.instruction_sysvar_account.address()),
+            InstructionAccount::writable(ctx.collateral_obligation_farm_user_state.address()),
+            InstructionAccount::writable(ctx.collateral_reserve_farm_state.address()),
+            InstructionAccount::writable(ctx.debt_obligation_farm_user_state.address()),
+            InstructionAccount::writable(ctx.debt_reserve_farm_state.address()),
+            InstructionAccount::readonly(ctx.farms_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.liquidator,
+            ctx.obligation,
+            ctx.lending_market,
+            ctx.lending_market_authority,
+            ctx.repay_reserve,
+            ctx.repay_reserve_liquidity_mint,
+            ctx.repay_reserve_liquidity_supply,
+            ctx.withdraw_reserve,
+            ctx.withdraw_reserve_liquidity_mint,
+            ctx.withdraw_reserve_collateral_mint,
+            ctx.withdraw_reserve_collateral_supply,
+            ctx.withdraw_reserve_liquidity_supply,
+            ctx.withdraw_reserve_liquidity_fee_receiver,
+            ctx.user_source_liquidity,
+            ctx.user_destination_collateral,
+            ctx.user_destination_liquidity,
+            ctx.collateral_token_program,
+            ctx.repay_liquidity_token_program,
+            ctx.withdraw_liquidity_token_program,
+            ctx.instruction_sysvar_account,
+            ctx.collateral_obligation_farm_user_state,
+            ctx.collateral_reserve_farm_state,
+            ctx.debt_obligation_farm_user_state,
+            ctx.debt_reserve_farm_state,
+            ctx.farms_program,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + liquidity_amount (8 bytes)
+        // + min_acceptable_received_liquidity_amount (8 bytes) + max_allowed_ltv_override_percent (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 32]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(
+                LIQUIDATE_OBLIGATION_AND_REDEEM_RESERVE_COLLATERAL_V2_DISCRIMINATOR.as_ptr(),
+                ptr,
+                8,
+            );
+            core::ptr::copy_nonoverlapping(liquidity_amount.to_le_bytes().as_ptr(), ptr.add(8), 8);
+            core::ptr::copy_nonoverlapping(min_received.to_le_bytes().as_ptr(), ptr.add(16), 8);
+            core::ptr::copy_nonoverlapping(0u64.to_le_bytes().as_ptr(), ptr.add(24), 8);
+        }
+
+        let liquidate_ix = InstructionView {
+            program_id: ctx.kamino_lending_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 32)
+            },
+        };
+
+        invoke_signed_with_remaining(&liquidate_ix, &account_infos, ctx.remaining, signer_seeds)?;
+
+        Ok(())
+    }
+
+    fn liquidate(
+        ctx: &KaminoLiquidateAccounts<'info>,
+        liquidity_amount: u64,
+        min_received: u64,
+    ) -> ProgramResult {
+        Self::liquidate_signed(ctx, liquidity_amount, min_received, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        std::vec::Vec,
+    };
+
+    fn accounts(len: u8) -> Vec<TestAccount> {
+        (0..len)
+            .map(|i| match i {
+                0 => TestAccount::empty(KAMINO_LEND_PROGRAM_ID),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_in_layout_order_and_keeps_trailing_accounts() {
+        let mut accounts = accounts(KAMINO_LIQUIDATE_ACCOUNTS.len() as u8 + 2);
+        let accounts = views(&mut accounts);
+        let ctx = KaminoLiquidateAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(ctx.obligation.address(), &address(2));
+        assert_eq!(ctx.farms_program.address(), &address(25));
+        assert_eq!(ctx.remaining.len(), 2);
+        assert_eq!(Kamino::liquidate(&ctx, 1_000, 900), Ok(()));
+    }
+
+    #[test]
+    fn rejects_short_slices_and_other_programs() {
+        let mut accounts = accounts(KAMINO_LIQUIDATE_ACCOUNTS.len() as u8);
+        let accounts = views(&mut accounts);
+        assert!(matches!(
+            KaminoLiquidateAccounts::try_from(&accounts[..25]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+        assert!(matches!(
+            KaminoLiquidateAccounts::try_from_with_program_id(&accounts, &address(9)),
+            Err(ProgramError::IncorrectProgramId)
+        ));
+    }
+}
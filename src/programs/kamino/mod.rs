@@ -1,20 +1,84 @@
+mod deposit_liquidity;
+pub use deposit_liquidity::*;
+
+mod elevation_group;
+pub use elevation_group::*;
+
+mod errors;
+pub use errors::*;
+
+mod liquidate;
+pub use liquidate::*;
+
+mod pda;
+pub use pda::*;
+
+mod refresh;
+pub use refresh::*;
+
+mod state;
+pub use state::*;
 use {
-    crate::Deposit,
+    crate::{
+        AccountCheck, AccountError, AccountSpec, BeethovenError, Capacity, Deposit, Health,
+        HealthInfo, Preview, programs::ProgramIds, util::cpi::invoke_signed_with_remaining,
+    },
     core::mem::MaybeUninit,
     pinocchio::{
         AccountView, Address, ProgramResult,
-        cpi::{Signer, invoke_signed},
+        address::address_eq,
+        cpi::Signer,
         error::ProgramError,
         instruction::{InstructionAccount, InstructionView},
+        sysvars::{Sysvar, clock::Clock},
     },
 };
 
-pub const KAMINO_LEND_PROGRAM_ID: Address = Address::new_from_array([0; 32]);
+pub const KAMINO_LEND_PROGRAM_ID: Address = Address::new_from_array([
+    4, 178, 172, 177, 18, 88, 204, 227, 104, 44, 65, 139, 168, 114, 255, 61, 249, 17, 2, 113, 47,
+    21, 175, 18, 182, 190, 105, 179, 67, 91, 0, 8,
+]);
+/// Scope oracle program, owning the price accounts Kamino reserves refresh from.
+pub const SCOPE_PROGRAM_ID: Address = Address::new_from_array([
+    241, 132, 37, 68, 185, 143, 22, 221, 129, 231, 167, 12, 131, 77, 133, 134, 12, 88, 187, 200,
+    219, 82, 194, 31, 226, 188, 76, 223, 202, 47, 94, 3,
+]);
+/// Kamino Farms program, invoked by Kamino Lend to stake obligation collateral in reserve farms.
+pub const KAMINO_FARMS_PROGRAM_ID: Address = Address::new_from_array([
+    216, 176, 16, 23, 99, 211, 229, 31, 18, 110, 97, 86, 222, 133, 222, 140, 97, 48, 89, 184, 68,
+    104, 208, 218, 63, 232, 162, 162, 37, 28, 199, 1,
+]);
 const REFRESH_RESERVE_DISCRIMINATOR: [u8; 8] = [2, 218, 138, 235, 79, 201, 25, 102];
 const REFRESH_OBLIGATION_DISCRIMINATOR: [u8; 8] = [33, 132, 147, 228, 151, 192, 72, 89];
 const DEPOSIT_RESERVE_LIQUIDITY_AND_OBLIGATION_COLLATERAL_V2_DISCRIMINATOR: [u8; 8] =
     [216, 224, 191, 27, 204, 151, 102, 175];
 
+/// Account order expected by `KaminoDepositAccounts::try_from`.
+///
+/// Followed by the obligation's other reserves (up to 13), then the Scope price account of each
+/// of those reserves, in the same order, then any trailing accounts.
+pub const KAMINO_DEPOSIT_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("kamino_lending_program"),
+    AccountSpec::writable_signer("owner"),
+    AccountSpec::writable("obligation"),
+    AccountSpec::readonly("lending_market"),
+    AccountSpec::readonly("lending_market_authority"),
+    AccountSpec::writable("reserve"),
+    AccountSpec::readonly("reserve_liquidity_mint"),
+    AccountSpec::writable("reserve_liquidity_supply"),
+    AccountSpec::writable("reserve_collateral_mint"),
+    AccountSpec::writable("reserve_destination_deposit_collateral"),
+    AccountSpec::writable("user_source_liquidity"),
+    AccountSpec::readonly("placeholder_user_destination_collateral"),
+    AccountSpec::readonly("collateral_token_program"),
+    AccountSpec::readonly("liquidity_token_program"),
+    AccountSpec::readonly("instruction_sysvar_account"),
+    AccountSpec::writable("obligation_farm_user_state"),
+    AccountSpec::writable("reserve_farm_state"),
+    AccountSpec::readonly("farms_program"),
+    AccountSpec::readonly("scope_oracle"),
+];
+
 /// Kamino lending protocol integration
 pub struct Kamino;
 
@@ -63,10 +127,14 @@ pub struct KaminoDepositAccounts<'info> {
     pub reserve_farm_state: &'info AccountView,
     /// Farms program
     pub farms_program: &'info AccountView,
-    /// Scope Oracle
+    /// Scope price account of the deposit reserve
     pub scope_oracle: &'info AccountView,
     /// Reserve Accounts
     pub reserve_accounts: &'info [AccountView],
+    /// Scope price account of each of `reserve_accounts`, in the same order
+    pub reserve_oracles: &'info [AccountView],
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
 }
 
 impl<'info> TryFrom<&'info [AccountView]> for KaminoDepositAccounts<'info> {
@@ -75,21 +143,69 @@ impl<'info> TryFrom<&'info [AccountView]> for KaminoDepositAccounts<'info> {
     /// Converts a slice of `AccountView` into validated `KaminoDepositAccounts`.
     ///
     /// # Arguments
-    /// * `accounts` - Slice containing at least 17 accounts in the correct order
+    /// * `accounts` - Slice containing at least 19 accounts in the correct order
     ///
     /// # Returns
     /// * `Ok(KaminoDepositAccounts)` - Successfully parsed account context
-    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 17 accounts provided
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 19 accounts provided, or a
+    ///   reserve without its oracle
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `KAMINO_LEND_PROGRAM_ID`
+    /// * `Err(ProgramError::IllegalOwner)` - A reserve oracle is neither owned by
+    ///   `SCOPE_PROGRAM_ID` nor the program ID placeholder
     ///
     /// # Notes
-    /// * No upper bound is enforced - extra accounts are ignored (useful for `remaining_accounts`)
+    /// * No upper bound is enforced - extra accounts are captured in `remaining` and forwarded
+    ///   to the CPI, so protocols can require additional trailing accounts
     /// * Mutability and signer constraints are NOT validated here; Kamino's program will
     ///   enforce them during CPI, providing clearer error messages
-    /// * The `..` pattern allows passing more than 17 accounts without error
     fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &KAMINO_LEND_PROGRAM_ID)
+    }
+}
+
+impl<'info> KaminoDepositAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`
+    /// instead of `KAMINO_LEND_PROGRAM_ID` (e.g. for devnet or staging deployments).
+    ///
+    /// # Returns
+    /// * `Ok(KaminoDepositAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 19 accounts provided, or a
+    ///   reserve without its oracle
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `program_id`
+    /// * `Err(ProgramError::IllegalOwner)` - A reserve oracle is neither owned by
+    ///   `SCOPE_PROGRAM_ID` nor the program ID placeholder
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        Self::parse(accounts, program_id, &SCOPE_PROGRAM_ID)
+    }
+
+    /// Same as `try_from`, but validates the lending program and the reserve oracles' owner
+    /// against `ids` instead of the mainnet deployments.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoDepositAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 19 accounts provided, or a
+    ///   reserve without its oracle
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `ids.kamino_lend`
+    /// * `Err(ProgramError::IllegalOwner)` - A reserve oracle is neither owned by
+    ///   `ids.kamino_scope` nor the program ID placeholder
+    pub fn try_from_with_ids(
+        accounts: &'info [AccountView],
+        ids: &ProgramIds,
+    ) -> Result<Self, ProgramError> {
+        Self::parse(accounts, &ids.kamino_lend, &ids.kamino_scope)
+    }
+
+    fn parse(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+        scope_program_id: &Address,
+    ) -> Result<Self, ProgramError> {
         // Require minimum of 19 accounts to prevent undefined behavior
         if accounts.len() < 19 {
-            return Err(ProgramError::NotEnoughAccountKeys);
+            return Err(AccountError::missing(KAMINO_DEPOSIT_ACCOUNTS, accounts.len()).into());
         }
 
         let [
@@ -115,22 +231,46 @@ impl<'info> TryFrom<&'info [AccountView]> for KaminoDepositAccounts<'info> {
             remaining_accounts @ ..,
         ] = accounts
         else {
-            return Err(ProgramError::NotEnoughAccountKeys);
+            return Err(AccountError::missing(KAMINO_DEPOSIT_ACCOUNTS, accounts.len()).into());
         };
 
+        if !address_eq(kamino_lending_program.address(), program_id) {
+            return Err(
+                AccountError::new(KAMINO_DEPOSIT_ACCOUNTS, 0, AccountCheck::ProgramId).into(),
+            );
+        }
+
         // Since it doesn't make sense to perform 2 deposit instructions back to back, as convention we will assume
         // that all remaining_accounts that are owned by the Kamino lending program are reserves
         // Note: This is not the most efficient way to do this, but I have some skill issues so this is what you get
         let mut total_reserve_accounts = 0;
 
         for reserve in remaining_accounts {
-            if reserve.owned_by(&KAMINO_LEND_PROGRAM_ID) && total_reserve_accounts < 13 {
+            if reserve.owned_by(program_id) && total_reserve_accounts < 13 {
                 total_reserve_accounts += 1;
             } else {
                 break;
             }
         }
 
+        let Some((reserve_oracles, remaining)) =
+            remaining_accounts[total_reserve_accounts..].split_at_checked(total_reserve_accounts)
+        else {
+            return Err(AccountError {
+                index: accounts.len(),
+                name: "reserve_oracles",
+                check: AccountCheck::Missing,
+            }
+            .into());
+        };
+
+        check_reserve_oracles(
+            reserve_oracles,
+            accounts.len() - remaining.len() - reserve_oracles.len(),
+            program_id,
+            scope_program_id,
+        )?;
+
         Ok(KaminoDepositAccounts {
             owner,
             obligation,
@@ -152,10 +292,32 @@ impl<'info> TryFrom<&'info [AccountView]> for KaminoDepositAccounts<'info> {
             scope_oracle,
             kamino_lending_program,
             reserve_accounts: &remaining_accounts[..total_reserve_accounts],
+            reserve_oracles,
+            remaining,
         })
     }
 }
 
+/// Checks that each reserve oracle, starting at account `first_index`, is owned by
+/// `scope_program_id` or is the `program_id` placeholder of a reserve without a Scope feed.
+fn check_reserve_oracles(
+    reserve_oracles: &[AccountView],
+    first_index: usize,
+    program_id: &Address,
+    scope_program_id: &Address,
+) -> Result<(), AccountError> {
+    match reserve_oracles.iter().position(|oracle| {
+        !oracle.owned_by(scope_program_id) && !address_eq(oracle.address(), program_id)
+    }) {
+        Some(position) => Err(AccountError {
+            index: first_index + position,
+            name: "reserve_oracles",
+            check: AccountCheck::Owner,
+        }),
+        None => Ok(()),
+    }
+}
+
 impl<'info> Deposit<'info> for Kamino {
     type Accounts = KaminoDepositAccounts<'info>;
 
@@ -176,111 +338,30 @@ impl<'info> Deposit<'info> for Kamino {
         amount: u64,
         signer_seeds: &[Signer],
     ) -> ProgramResult {
-        // Refresh reserves
-        // - Start by the refreshing the reserve we're depositing into
-        let accounts = [
-            InstructionAccount::writable(ctx.reserve.address()),
-            InstructionAccount::readonly(ctx.kamino_lending_program.address()),
-            InstructionAccount::readonly(ctx.kamino_lending_program.address()),
-            InstructionAccount::readonly(ctx.kamino_lending_program.address()),
-            InstructionAccount::readonly(ctx.scope_oracle.address()),
-        ];
-
-        let account_infos = [
-            ctx.reserve,
-            ctx.kamino_lending_program,
-            ctx.kamino_lending_program,
-            ctx.kamino_lending_program,
-            ctx.scope_oracle,
-        ];
-
-        let instruction = InstructionView {
-            program_id: &KAMINO_LEND_PROGRAM_ID,
-            accounts: &accounts,
-            data: &REFRESH_RESERVE_DISCRIMINATOR,
-        };
-
-        invoke_signed(&instruction, &account_infos, signer_seeds)?;
-
-        // - Now refresh all the other reserves (if any)
-        for reserve in ctx.reserve_accounts {
-            let accounts = [
-                InstructionAccount::writable(reserve.address()),
-                InstructionAccount::readonly(ctx.kamino_lending_program.address()),
-                InstructionAccount::readonly(ctx.kamino_lending_program.address()),
-                InstructionAccount::readonly(ctx.kamino_lending_program.address()),
-                InstructionAccount::readonly(ctx.scope_oracle.address()),
-            ];
-
-            let account_infos = [
-                ctx.reserve,
-                ctx.kamino_lending_program,
-                ctx.kamino_lending_program,
-                ctx.kamino_lending_program,
-                ctx.scope_oracle,
-            ];
-
-            let instruction = InstructionView {
-                program_id: &KAMINO_LEND_PROGRAM_ID,
-                accounts: &accounts,
-                data: &REFRESH_RESERVE_DISCRIMINATOR,
-            };
-
-            invoke_signed(&instruction, &account_infos, signer_seeds)?;
-        }
-
-        // Refresh obligation
-        const MAX_REFRESH_OBLIGATION_ACCOUNTS: usize = 15;
-
-        // Build account metas: obligation + lending_market + all reserves (up to 13)
-        let mut obligation_accounts =
-            MaybeUninit::<[InstructionAccount; MAX_REFRESH_OBLIGATION_ACCOUNTS]>::uninit();
-        let obligation_accounts_ptr = obligation_accounts.as_mut_ptr() as *mut InstructionAccount;
-
-        unsafe {
-            // First account: writable obligation
-            core::ptr::write(
-                obligation_accounts_ptr,
-                InstructionAccount::writable(ctx.obligation.address()),
-            );
-            // Second account: readonly lending_market
-            core::ptr::write(
-                obligation_accounts_ptr.add(1),
-                InstructionAccount::readonly(ctx.lending_market.address()),
-            );
-
-            // Add all reserve accounts (read-only)
-            for (i, reserve) in ctx.reserve_accounts.iter().enumerate() {
-                core::ptr::write(
-                    obligation_accounts_ptr.add(2 + i),
-                    InstructionAccount::readonly(reserve.address()),
-                );
-            }
-        }
-
-        let obligation_accounts_len = 2 + ctx.reserve_accounts.len();
-        let obligation_accounts_slice = unsafe {
-            core::slice::from_raw_parts(obligation_accounts_ptr, obligation_accounts_len)
-        };
-
-        // Build account infos: obligation + lending_market + all reserves
-        // Fill unused slots with obligation to avoid UB (invoke_signed is fine with extra accounts)
-        // Note: I know this is retarded, but I have some skill issues so this is what you get
-        let mut obligation_account_infos = [ctx.obligation; MAX_REFRESH_OBLIGATION_ACCOUNTS];
-        obligation_account_infos[1] = ctx.lending_market;
-
-        for (i, reserve) in ctx.reserve_accounts.iter().enumerate() {
-            obligation_account_infos[2 + i] = reserve;
-        }
+        Self::deposit_signed_at(ctx, amount, signer_seeds, Clock::get()?.slot)
+    }
 
-        let instruction = InstructionView {
-            program_id: &KAMINO_LEND_PROGRAM_ID,
-            accounts: obligation_accounts_slice,
-            data: &REFRESH_OBLIGATION_DISCRIMINATOR,
-        };
+    fn deposit(ctx: &KaminoDepositAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::deposit_signed(ctx, amount, &[])
+    }
+}
 
-        // change to cpi::slice_invoke_signed,
-        invoke_signed(&instruction, &obligation_account_infos, signer_seeds)?;
+impl Kamino {
+    /// Same as `deposit_signed`, refreshing the accounts stale at `slot` instead of reading
+    /// the Clock sysvar (e.g. when the caller already holds the current slot).
+    ///
+    /// # Returns
+    /// * `Ok(())` - Deposit completed successfully
+    /// * `Err(ProgramError)` - Invalid accounts or CPI failure
+    pub fn deposit_signed_at(
+        ctx: &KaminoDepositAccounts,
+        amount: u64,
+        signer_seeds: &[Signer],
+        slot: u64,
+    ) -> ProgramResult {
+        // Refresh the stale reserves, then the obligation
+        let refreshes = Self::required_refreshes(ctx, slot)?;
+        Self::refresh(ctx, &refreshes)?;
 
         // Deposit CPI
         let accounts = [
@@ -335,19 +416,377 @@ impl<'info> Deposit<'info> for Kamino {
         }
 
         let deposit_ix = InstructionView {
-            program_id: &KAMINO_LEND_PROGRAM_ID,
+            program_id: ctx.kamino_lending_program.address(),
             accounts: &accounts,
             data: unsafe {
                 core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 16)
             },
         };
 
-        invoke_signed(&deposit_ix, &account_infos, signer_seeds)?;
+        invoke_signed_with_remaining(&deposit_ix, &account_infos, ctx.remaining, signer_seeds)?;
 
         Ok(())
     }
+}
 
-    fn deposit(ctx: &KaminoDepositAccounts<'info>, amount: u64) -> ProgramResult {
-        Self::deposit_signed(ctx, amount, &[])
+impl<'info> Preview<'info> for Kamino {
+    type Accounts = KaminoDepositAccounts<'info>;
+
+    /// Returns the collateral (cTokens) expected for depositing `amount` into the reserve.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Expected collateral amount
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    /// * `Err(ProgramError)` - Reserve data could not be read
+    fn preview_deposit(
+        ctx: &KaminoDepositAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.reserve.owned_by(ctx.kamino_lending_program.address()) {
+            return Err(
+                AccountError::of(KAMINO_DEPOSIT_ACCOUNTS, "reserve", AccountCheck::Owner).into(),
+            );
+        }
+
+        let data = ctx.reserve.try_borrow()?;
+        KaminoReserveState::from_bytes(&data)?.liquidity_to_collateral(amount)
+    }
+}
+
+impl<'info> Capacity<'info> for Kamino {
+    type Accounts = KaminoDepositAccounts<'info>;
+
+    /// Checks `amount` against the reserve's `deposit_limit`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The deposit fits within the reserve's deposit limit
+    /// * `Err(BeethovenError::DepositCapExceeded)` - The deposit would exceed the limit
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    fn check_capacity(ctx: &KaminoDepositAccounts<'info>, amount: u64) -> ProgramResult {
+        if amount > Self::available_deposit_capacity(ctx)? {
+            return Err(BeethovenError::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the liquidity left before the reserve's `deposit_limit`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - `deposit_limit` minus the reserve's total liquidity, floored at zero
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    fn available_deposit_capacity(ctx: &KaminoDepositAccounts<'info>) -> Result<u64, ProgramError> {
+        if !ctx.reserve.owned_by(ctx.kamino_lending_program.address()) {
+            return Err(
+                AccountError::of(KAMINO_DEPOSIT_ACCOUNTS, "reserve", AccountCheck::Owner).into(),
+            );
+        }
+
+        let data = ctx.reserve.try_borrow()?;
+        KaminoReserveState::from_bytes(&data)?.deposit_capacity()
+    }
+
+    /// Returns the reserve's available (unborrowed) liquidity.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Liquidity a withdrawal can be paid out of
+    /// * `Err(ProgramError::IllegalOwner)` - Reserve is not owned by the Kamino program
+    fn available_liquidity_for_withdraw(
+        ctx: &KaminoDepositAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.reserve.owned_by(ctx.kamino_lending_program.address()) {
+            return Err(
+                AccountError::of(KAMINO_DEPOSIT_ACCOUNTS, "reserve", AccountCheck::Owner).into(),
+            );
+        }
+
+        let data = ctx.reserve.try_borrow()?;
+        Ok(KaminoReserveState::from_bytes(&data)?.available_amount)
+    }
+
+    /// Returns `true` when the reserve is active and the market is not in emergency mode.
+    fn is_operational(ctx: &KaminoDepositAccounts<'info>) -> bool {
+        reserve_is_operational(ctx.kamino_lending_program, ctx.reserve, ctx.lending_market)
+    }
+}
+
+/// Returns `true` when `reserve` is active and `lending_market` is not in emergency mode.
+///
+/// Accounts not owned by `kamino_lending_program`, or whose data cannot be parsed, are
+/// reported as not operational.
+fn reserve_is_operational(
+    kamino_lending_program: &AccountView,
+    reserve: &AccountView,
+    lending_market: &AccountView,
+) -> bool {
+    let program_id = kamino_lending_program.address();
+    if !reserve.owned_by(program_id) || !lending_market.owned_by(program_id) {
+        return false;
+    }
+
+    let reserve_active = reserve
+        .try_borrow()
+        .ok()
+        .and_then(|data| KaminoReserveState::from_bytes(&data).ok())
+        .is_some_and(|state| state.is_active());
+    let market_open = lending_market
+        .try_borrow()
+        .ok()
+        .and_then(|data| KaminoLendingMarketState::from_bytes(&data).ok())
+        .is_some_and(|state| !state.emergency_mode);
+
+    reserve_active && market_open
+}
+
+/// Account order expected by `KaminoHealthAccounts::try_from`.
+pub const KAMINO_HEALTH_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("kamino_lending_program"),
+    AccountSpec::readonly("obligation"),
+];
+
+/// Account context for reading the health of a Kamino obligation.
+///
+/// # Account Order
+/// 1. Kamino Lending Program
+/// 2. Obligation
+pub struct KaminoHealthAccounts<'info> {
+    /// Kamino Lending Program
+    pub kamino_lending_program: &'info AccountView,
+    /// The obligation to assess
+    pub obligation: &'info AccountView,
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for KaminoHealthAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into `KaminoHealthAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoHealthAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 2 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `KAMINO_LEND_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &KAMINO_LEND_PROGRAM_ID)
+    }
+}
+
+impl<'info> KaminoHealthAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [kamino_lending_program, obligation, ..] = accounts else {
+            return Err(AccountError::missing(KAMINO_HEALTH_ACCOUNTS, accounts.len()).into());
+        };
+
+        if !address_eq(kamino_lending_program.address(), program_id) {
+            return Err(
+                AccountError::new(KAMINO_HEALTH_ACCOUNTS, 0, AccountCheck::ProgramId).into(),
+            );
+        }
+
+        Ok(KaminoHealthAccounts {
+            kamino_lending_program,
+            obligation,
+        })
+    }
+}
+
+impl<'info> Health<'info> for Kamino {
+    type Accounts = KaminoHealthAccounts<'info>;
+
+    /// Returns the obligation's health as of its last `refresh_obligation`.
+    ///
+    /// # Returns
+    /// * `Ok(HealthInfo)` - Normalized safety margins
+    /// * `Err(ProgramError::IllegalOwner)` - Obligation is not owned by the Kamino program
+    /// * `Err(ProgramError)` - Obligation data could not be read
+    fn health(ctx: &KaminoHealthAccounts<'info>) -> Result<HealthInfo, ProgramError> {
+        if !ctx
+            .obligation
+            .owned_by(ctx.kamino_lending_program.address())
+        {
+            return Err(AccountError::of(
+                KAMINO_HEALTH_ACCOUNTS,
+                "obligation",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.obligation.try_borrow()?;
+        KaminoObligationState::from_bytes(&data)?.health()
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, account_data, address, bench, views},
+        std::vec::Vec,
+    };
+
+    pub(crate) const OBLIGATION: usize = 2;
+    const RESERVE: usize = 5;
+    pub(crate) const RESERVE_ACCOUNTS: usize = 19;
+
+    /// `Reserve` / `Obligation` bytes whose `LastUpdate` is `slot`, with the `stale` flag.
+    pub(crate) fn last_update_data(slot: u64, stale: bool) -> Vec<u8> {
+        let mut data = account_data(&[0; 8], 32);
+        data[16..24].copy_from_slice(&slot.to_le_bytes());
+        data[24] = stale as u8;
+        data
+    }
+
+    /// Deposit accounts with `reserves` other reserves followed by `oracles` accounts; every
+    /// reserve and the obligation were refreshed at `slot`.
+    pub(crate) fn deposit_accounts(reserves: usize, oracles: usize, slot: u64) -> Vec<TestAccount> {
+        let fresh = last_update_data(slot, false);
+        let mut accounts: Vec<TestAccount> = (0..RESERVE_ACCOUNTS as u8)
+            .map(|i| match i as usize {
+                0 => TestAccount::empty(KAMINO_LEND_PROGRAM_ID),
+                OBLIGATION | RESERVE => {
+                    TestAccount::new(address(i), KAMINO_LEND_PROGRAM_ID, &fresh)
+                }
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect();
+        accounts.extend(
+            (0..reserves as u8)
+                .map(|i| TestAccount::new(address(100 + i), KAMINO_LEND_PROGRAM_ID, &fresh)),
+        );
+        accounts.extend(
+            (0..oracles as u8).map(|i| TestAccount::new(address(200 + i), SCOPE_PROGRAM_ID, &[])),
+        );
+        accounts
+    }
+
+    #[test]
+    fn parse_pairs_each_reserve_with_its_oracle() {
+        let mut accounts = deposit_accounts(2, 3, 0);
+        let accounts = views(&mut accounts);
+        let ctx = KaminoDepositAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(ctx.reserve_accounts.len(), 2);
+        assert_eq!(ctx.reserve_oracles[0].address(), &address(200));
+        assert_eq!(ctx.reserve_oracles[1].address(), &address(201));
+        assert_eq!(ctx.remaining.len(), 1);
+        assert_eq!(ctx.remaining[0].address(), &address(202));
+    }
+
+    /// The deposit CPI as a router would hand-roll it: no account checks, no refreshes.
+    fn hand_built_deposit(accounts: &[AccountView], amount: u64) -> ProgramResult {
+        let metas = [
+            InstructionAccount::writable_signer(accounts[1].address()),
+            InstructionAccount::writable(accounts[2].address()),
+            InstructionAccount::readonly(accounts[3].address()),
+            InstructionAccount::readonly(accounts[4].address()),
+            InstructionAccount::writable(accounts[5].address()),
+            InstructionAccount::readonly(accounts[6].address()),
+            InstructionAccount::writable(accounts[7].address()),
+            InstructionAccount::writable(accounts[8].address()),
+            InstructionAccount::writable(accounts[9].address()),
+            InstructionAccount::writable(accounts[10].address()),
+            InstructionAccount::readonly(accounts[11].address()),
+            InstructionAccount::readonly(accounts[12].address()),
+            InstructionAccount::readonly(accounts[13].address()),
+            InstructionAccount::readonly(accounts[14].address()),
+            InstructionAccount::writable(accounts[15].address()),
+            InstructionAccount::writable(accounts[16].address()),
+            InstructionAccount::readonly(accounts[17].address()),
+        ];
+        let account_infos: [&AccountView; 17] = core::array::from_fn(|i| &accounts[i + 1]);
+
+        let mut data = [0u8; 16];
+        data[..8]
+            .copy_from_slice(&DEPOSIT_RESERVE_LIQUIDITY_AND_OBLIGATION_COLLATERAL_V2_DISCRIMINATOR);
+        data[8..].copy_from_slice(&amount.to_le_bytes());
+
+        let ix = InstructionView {
+            program_id: accounts[0].address(),
+            accounts: &metas,
+            data: &data,
+        };
+        pinocchio::cpi::invoke_signed(&ix, &account_infos, &[])
+    }
+
+    // Off-chain there is no Clock sysvar, so the typed deposit takes the fixtures' slot.
+    #[test]
+    #[ignore = "benchmark, run with `make bench`"]
+    fn bench_deposit_context_against_the_typed_deposit() {
+        let mut accounts = deposit_accounts(2, 2, 0);
+        let views = views(&mut accounts);
+
+        bench("kamino try_from_deposit_context", || {
+            crate::try_from_deposit_context(core::hint::black_box(&views)).unwrap();
+        });
+        bench(
+            "kamino KaminoDepositAccounts::try_from + deposit_signed_at",
+            || {
+                let ctx = KaminoDepositAccounts::try_from(core::hint::black_box(views.as_slice()))
+                    .unwrap();
+                Kamino::deposit_signed_at(&ctx, core::hint::black_box(1_000), &[], 0).unwrap();
+            },
+        );
+        bench("kamino hand-built InstructionView", || {
+            hand_built_deposit(core::hint::black_box(&views), core::hint::black_box(1_000))
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn parse_names_a_reserve_oracle_not_owned_by_scope() {
+        let mut accounts = deposit_accounts(2, 2, 0);
+        // Trailing accounts past the oracles keep any owner.
+        accounts.push(TestAccount::empty(address(250)));
+        // A reserve without a Scope feed passes the program ID.
+        accounts[RESERVE_ACCOUNTS + 2] = TestAccount::empty(KAMINO_LEND_PROGRAM_ID);
+        assert!(KaminoDepositAccounts::try_from(&views(&mut accounts)[..]).is_ok());
+
+        accounts[RESERVE_ACCOUNTS + 3] = TestAccount::empty(address(201));
+        let accounts = views(&mut accounts);
+        assert_eq!(
+            check_reserve_oracles(
+                &accounts[RESERVE_ACCOUNTS + 2..RESERVE_ACCOUNTS + 4],
+                RESERVE_ACCOUNTS + 2,
+                &KAMINO_LEND_PROGRAM_ID,
+                &SCOPE_PROGRAM_ID,
+            ),
+            Err(AccountError {
+                index: RESERVE_ACCOUNTS + 3,
+                name: "reserve_oracles",
+                check: AccountCheck::Owner,
+            })
+        );
+        assert!(matches!(
+            KaminoDepositAccounts::try_from(&accounts[..]),
+            Err(ProgramError::IllegalOwner)
+        ));
+    }
+
+    #[test]
+    fn parse_checks_reserve_oracles_against_the_configured_scope_program() {
+        let mut ids = ProgramIds::MAINNET;
+        ids.kamino_scope = address(77);
+        let mut accounts = deposit_accounts(1, 1, 0);
+        accounts[RESERVE_ACCOUNTS + 1] = TestAccount::new(address(200), address(77), &[]);
+        let accounts = views(&mut accounts);
+
+        assert!(KaminoDepositAccounts::try_from_with_ids(&accounts, &ids).is_ok());
+        assert!(matches!(
+            KaminoDepositAccounts::try_from(&accounts[..]),
+            Err(ProgramError::IllegalOwner)
+        ));
+    }
+
+    #[test]
+    fn parse_rejects_a_reserve_without_its_oracle() {
+        let mut accounts = deposit_accounts(2, 1, 0);
+        let accounts = views(&mut accounts);
+        assert!(matches!(
+            KaminoDepositAccounts::try_from(&accounts[..]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
     }
 }
@@ -0,0 +1,127 @@
+//! Seeds of the PDAs Kamino derives for its users.
+//!
+//! The seed functions work everywhere (on-chain validation, client builders). The `find_*`
+//! functions derive the addresses on-chain, and off-chain with the `curve25519` feature
+//! (enabled by `client`).
+
+use pinocchio::Address;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+use {super::KAMINO_LEND_PROGRAM_ID, crate::util::find_program_address};
+
+const USER_METADATA_SEED: &[u8] = b"user_meta";
+const LENDING_MARKET_AUTHORITY_SEED: &[u8] = b"lma";
+const FARM_USER_STATE_SEED: &[u8] = b"user";
+
+/// Tag and id of a vanilla obligation (no leverage/multiply position).
+const VANILLA_OBLIGATION_TAG: &[u8] = &[0];
+const VANILLA_OBLIGATION_ID: &[u8] = &[0];
+const DEFAULT_ADDRESS: &[u8] = &[0; 32];
+
+/// Seeds of the user metadata PDA (Kamino Lending program).
+pub fn user_metadata_seeds(owner: &Address) -> [&[u8]; 2] {
+    [USER_METADATA_SEED, owner.as_ref()]
+}
+
+/// Seeds of the owner's vanilla obligation PDA in `lending_market` (Kamino Lending program).
+pub fn vanilla_obligation_seeds<'a>(
+    owner: &'a Address,
+    lending_market: &'a Address,
+) -> [&'a [u8]; 6] {
+    [
+        VANILLA_OBLIGATION_TAG,
+        VANILLA_OBLIGATION_ID,
+        owner.as_ref(),
+        lending_market.as_ref(),
+        DEFAULT_ADDRESS,
+        DEFAULT_ADDRESS,
+    ]
+}
+
+/// Seeds of the lending market authority PDA (Kamino Lending program).
+pub fn lending_market_authority_seeds(lending_market: &Address) -> [&[u8]; 2] {
+    [LENDING_MARKET_AUTHORITY_SEED, lending_market.as_ref()]
+}
+
+/// Seeds of an obligation's farm user state PDA (Farms program).
+pub fn obligation_farm_user_state_seeds<'a>(
+    farm_state: &'a Address,
+    obligation: &'a Address,
+) -> [&'a [u8]; 3] {
+    [
+        FARM_USER_STATE_SEED,
+        farm_state.as_ref(),
+        obligation.as_ref(),
+    ]
+}
+
+/// Finds the user metadata PDA of `owner` and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_user_metadata(owner: &Address) -> (Address, u8) {
+    find_program_address(&user_metadata_seeds(owner), &KAMINO_LEND_PROGRAM_ID)
+}
+
+/// Finds the owner's vanilla obligation PDA in `lending_market` and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_vanilla_obligation(owner: &Address, lending_market: &Address) -> (Address, u8) {
+    find_program_address(
+        &vanilla_obligation_seeds(owner, lending_market),
+        &KAMINO_LEND_PROGRAM_ID,
+    )
+}
+
+/// Finds the lending market authority PDA of `lending_market` and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_lending_market_authority(lending_market: &Address) -> (Address, u8) {
+    find_program_address(
+        &lending_market_authority_seeds(lending_market),
+        &KAMINO_LEND_PROGRAM_ID,
+    )
+}
+
+/// Finds an obligation's farm user state PDA under `farms_program_id` and its bump.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn find_obligation_farm_user_state(
+    farm_state: &Address,
+    obligation: &Address,
+    farms_program_id: &Address,
+) -> (Address, u8) {
+    find_program_address(
+        &obligation_farm_user_state_seeds(farm_state, obligation),
+        farms_program_id,
+    )
+}
+
+#[cfg(all(test, feature = "curve25519"))]
+mod tests {
+    use super::*;
+
+    // Kamino's main market (7u3HeHxYDLhnCoErrtycNokbQYbWGzLs6JSDqGAv5PfF).
+    const MAIN_MARKET: Address = Address::new_from_array([
+        102, 122, 232, 212, 88, 85, 169, 117, 80, 83, 73, 44, 128, 74, 30, 112, 209, 0, 88, 25,
+        168, 58, 43, 226, 118, 155, 25, 13, 13, 45, 225, 18,
+    ]);
+
+    #[test]
+    fn lending_market_authority_matches_mainnet() {
+        // 9DrvZvyWh1HuAoZxvYWMvkf2XCzryCpGgHqrMjyDWpmo
+        let authority = Address::new_from_array([
+            122, 40, 233, 244, 246, 177, 180, 89, 251, 122, 103, 207, 93, 98, 80, 126, 226, 104,
+            54, 234, 116, 244, 168, 88, 229, 168, 64, 53, 8, 35, 83, 138,
+        ]);
+        assert_eq!(
+            find_lending_market_authority(&MAIN_MARKET),
+            (authority, 248)
+        );
+    }
+
+    #[test]
+    fn obligations_are_distinct_per_owner() {
+        let owner = Address::new_from_array([1; 32]);
+        let other = Address::new_from_array([2; 32]);
+        assert_ne!(
+            find_vanilla_obligation(&owner, &MAIN_MARKET).0,
+            find_vanilla_obligation(&other, &MAIN_MARKET).0
+        );
+        assert_ne!(find_user_metadata(&owner).0, find_user_metadata(&other).0);
+    }
+}
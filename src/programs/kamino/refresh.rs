@@ -0,0 +1,299 @@
+use {
+    super::{
+        Kamino, KaminoDepositAccounts, REFRESH_OBLIGATION_DISCRIMINATOR,
+        REFRESH_RESERVE_DISCRIMINATOR,
+    },
+    crate::util::bytes::*,
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, ProgramResult,
+        cpi::{invoke_signed, invoke_signed_with_bounds},
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+// Byte offsets of `LastUpdate` in Kamino's `Reserve` and `Obligation` accounts (same for both).
+const LAST_UPDATE_SLOT_OFFSET: usize = 16;
+const LAST_UPDATE_STALE_OFFSET: usize = 24;
+
+/// Maximum number of reserves an obligation references in `refresh_obligation`.
+pub const KAMINO_MAX_OBLIGATION_RESERVES: usize = 13;
+
+/// Refresh CPIs a Kamino deposit needs before it can run in the current slot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KaminoRefreshes {
+    /// Bit 0: the deposit reserve; bit `i + 1`: `reserve_accounts[i]`
+    pub reserves: u16,
+    /// Whether the obligation must be refreshed
+    pub obligation: bool,
+}
+
+impl KaminoRefreshes {
+    /// Every refresh of the context, regardless of staleness.
+    pub const fn all(ctx: &KaminoDepositAccounts) -> Self {
+        Self {
+            reserves: (1 << (ctx.reserve_accounts.len() + 1)) - 1,
+            obligation: true,
+        }
+    }
+
+    /// Returns `true` when no refresh is needed.
+    pub const fn is_empty(&self) -> bool {
+        self.reserves == 0 && !self.obligation
+    }
+}
+
+/// Returns `true` when a Kamino `Reserve` or `Obligation` must be refreshed before use at `slot`.
+pub(super) fn is_stale(account: &AccountView, slot: u64) -> Result<bool, ProgramError> {
+    let data = account.try_borrow()?;
+    let last_update_slot = read_u64(&data, LAST_UPDATE_SLOT_OFFSET)?;
+    let stale = read_array::<1>(&data, LAST_UPDATE_STALE_OFFSET)?[0];
+    Ok(stale != 0 || last_update_slot < slot)
+}
+
+impl Kamino {
+    /// Refreshes `reserve` via `refresh_reserve`, reading prices from `scope_oracle`.
+    ///
+    /// # Notes
+    /// * The Pyth and Switchboard oracle slots are passed as the program ID (unused).
+    pub fn refresh_reserve(
+        kamino_lending_program: &AccountView,
+        reserve: &AccountView,
+        lending_market: &AccountView,
+        scope_oracle: &AccountView,
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(reserve.address()),
+            InstructionAccount::readonly(lending_market.address()),
+            InstructionAccount::readonly(kamino_lending_program.address()),
+            InstructionAccount::readonly(kamino_lending_program.address()),
+            InstructionAccount::readonly(kamino_lending_program.address()),
+            InstructionAccount::readonly(scope_oracle.address()),
+        ];
+
+        let account_infos = [
+            reserve,
+            lending_market,
+            kamino_lending_program,
+            kamino_lending_program,
+            kamino_lending_program,
+            scope_oracle,
+        ];
+
+        let instruction = InstructionView {
+            program_id: kamino_lending_program.address(),
+            accounts: &accounts,
+            data: &REFRESH_RESERVE_DISCRIMINATOR,
+        };
+
+        invoke_signed(&instruction, &account_infos, &[])
+    }
+
+    /// Refreshes `obligation` via `refresh_obligation`.
+    ///
+    /// # Arguments
+    /// * `reserves` - The obligation's deposit reserves, then its borrow reserves, all refreshed
+    ///   in the current slot
+    ///
+    /// # Returns
+    /// * `Ok(())` - Obligation refreshed
+    /// * `Err(ProgramError::InvalidArgument)` - More than `KAMINO_MAX_OBLIGATION_RESERVES` reserves
+    /// * `Err(ProgramError)` - CPI failed
+    pub fn refresh_obligation(
+        kamino_lending_program: &AccountView,
+        obligation: &AccountView,
+        lending_market: &AccountView,
+        reserves: &[AccountView],
+    ) -> ProgramResult {
+        const MAX_REFRESH_OBLIGATION_ACCOUNTS: usize = 2 + KAMINO_MAX_OBLIGATION_RESERVES;
+
+        if reserves.len() > KAMINO_MAX_OBLIGATION_RESERVES {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Build account metas: obligation + lending_market + all reserves (read-only)
+        let mut accounts =
+            MaybeUninit::<[InstructionAccount; MAX_REFRESH_OBLIGATION_ACCOUNTS]>::uninit();
+        let accounts_ptr = accounts.as_mut_ptr() as *mut InstructionAccount;
+
+        unsafe {
+            core::ptr::write(
+                accounts_ptr,
+                InstructionAccount::writable(obligation.address()),
+            );
+            core::ptr::write(
+                accounts_ptr.add(1),
+                InstructionAccount::readonly(lending_market.address()),
+            );
+
+            for (i, reserve) in reserves.iter().enumerate() {
+                core::ptr::write(
+                    accounts_ptr.add(2 + i),
+                    InstructionAccount::readonly(reserve.address()),
+                );
+            }
+        }
+
+        let len = 2 + reserves.len();
+        let accounts_slice = unsafe { core::slice::from_raw_parts(accounts_ptr, len) };
+
+        // Unused slots are filled with the obligation; only the first `len` are passed on
+        let mut account_infos = [obligation; MAX_REFRESH_OBLIGATION_ACCOUNTS];
+        account_infos[1] = lending_market;
+
+        for (i, reserve) in reserves.iter().enumerate() {
+            account_infos[2 + i] = reserve;
+        }
+
+        let instruction = InstructionView {
+            program_id: kamino_lending_program.address(),
+            accounts: accounts_slice,
+            data: &REFRESH_OBLIGATION_DISCRIMINATOR,
+        };
+
+        invoke_signed_with_bounds::<MAX_REFRESH_OBLIGATION_ACCOUNTS>(
+            &instruction,
+            &account_infos[..len],
+            &[],
+        )
+    }
+
+    /// Returns the refreshes a deposit with `ctx` needs at `slot` (usually `Clock::get()?.slot`).
+    ///
+    /// Kamino rejects reserves and obligations not refreshed in the current slot. Deposits
+    /// only run the refreshes this reports, so accounts refreshed by an earlier top-level
+    /// instruction in the same slot cost no CPI.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoRefreshes)` - Stale accounts; the obligation is included whenever any of
+    ///   its reserves is refreshed
+    /// * `Err(ProgramError)` - Reserve or obligation data could not be read
+    pub fn required_refreshes(
+        ctx: &KaminoDepositAccounts,
+        slot: u64,
+    ) -> Result<KaminoRefreshes, ProgramError> {
+        let mut refreshes = KaminoRefreshes::default();
+
+        if is_stale(ctx.reserve, slot)? {
+            refreshes.reserves |= 1;
+        }
+        for (i, reserve) in ctx.reserve_accounts.iter().enumerate() {
+            if is_stale(reserve, slot)? {
+                refreshes.reserves |= 1 << (i + 1);
+            }
+        }
+
+        refreshes.obligation = refreshes.reserves != 0 || is_stale(ctx.obligation, slot)?;
+        Ok(refreshes)
+    }
+
+    /// Runs the refresh CPIs selected in `refreshes`, reserves first.
+    ///
+    /// Each reserve is refreshed with its own oracle: `scope_oracle` for the deposit reserve,
+    /// `reserve_oracles[i]` for `reserve_accounts[i]`.
+    pub fn refresh(ctx: &KaminoDepositAccounts, refreshes: &KaminoRefreshes) -> ProgramResult {
+        if refreshes.reserves & 1 != 0 {
+            Self::refresh_reserve(
+                ctx.kamino_lending_program,
+                ctx.reserve,
+                ctx.lending_market,
+                ctx.scope_oracle,
+            )?;
+        }
+
+        for (i, (reserve, oracle)) in ctx
+            .reserve_accounts
+            .iter()
+            .zip(ctx.reserve_oracles)
+            .enumerate()
+        {
+            if refreshes.reserves & (1 << (i + 1)) != 0 {
+                Self::refresh_reserve(
+                    ctx.kamino_lending_program,
+                    reserve,
+                    ctx.lending_market,
+                    oracle,
+                )?;
+            }
+        }
+
+        if refreshes.obligation {
+            Self::refresh_obligation(
+                ctx.kamino_lending_program,
+                ctx.obligation,
+                ctx.lending_market,
+                ctx.reserve_accounts,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            programs::kamino::tests::{
+                OBLIGATION, RESERVE_ACCOUNTS, deposit_accounts, last_update_data,
+            },
+            test_utils::{TestAccount, views},
+        },
+    };
+
+    fn set_last_update(account: &mut TestAccount, slot: u64, stale: bool) {
+        account.view().try_borrow_mut().unwrap()[..32]
+            .copy_from_slice(&last_update_data(slot, stale));
+    }
+
+    fn required_refreshes(accounts: &mut [TestAccount], slot: u64) -> KaminoRefreshes {
+        let accounts = views(accounts);
+        let ctx = KaminoDepositAccounts::try_from(&accounts[..]).unwrap();
+        Kamino::required_refreshes(&ctx, slot).unwrap()
+    }
+
+    #[test]
+    fn nothing_to_refresh_in_the_same_slot() {
+        let mut accounts = deposit_accounts(2, 2, 100);
+        assert!(required_refreshes(&mut accounts, 100).is_empty());
+    }
+
+    #[test]
+    fn stale_reserves_are_selected_with_the_obligation() {
+        let mut accounts = deposit_accounts(3, 3, 100);
+        set_last_update(&mut accounts[RESERVE_ACCOUNTS], 99, false);
+        set_last_update(&mut accounts[RESERVE_ACCOUNTS + 2], 100, true);
+
+        assert_eq!(
+            required_refreshes(&mut accounts, 100),
+            KaminoRefreshes {
+                reserves: 0b1010,
+                obligation: true,
+            }
+        );
+    }
+
+    #[test]
+    fn a_stale_obligation_alone_refreshes_no_reserve() {
+        let mut accounts = deposit_accounts(1, 1, 100);
+        set_last_update(&mut accounts[OBLIGATION], 99, false);
+
+        assert_eq!(
+            required_refreshes(&mut accounts, 100),
+            KaminoRefreshes {
+                reserves: 0,
+                obligation: true,
+            }
+        );
+    }
+
+    #[test]
+    fn all_selects_every_reserve() {
+        let mut accounts = deposit_accounts(2, 2, 0);
+        let accounts = views(&mut accounts);
+        let ctx = KaminoDepositAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(KaminoRefreshes::all(&ctx).reserves, 0b111);
+    }
+}
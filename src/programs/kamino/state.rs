@@ -0,0 +1,484 @@
+use {
+    crate::{
+        BPS_DENOMINATOR, HEALTH_VALUE_SCALE, HealthInfo,
+        util::{Rounding, bytes::*, div, mul_div, mul_div_u64, to_u64},
+    },
+    pinocchio::error::ProgramError,
+};
+
+pub(crate) const RESERVE_DISCRIMINATOR: [u8; 8] = [43, 242, 204, 202, 26, 247, 59, 127];
+const OBLIGATION_DISCRIMINATOR: [u8; 8] = [168, 206, 141, 106, 88, 76, 172, 167];
+const LENDING_MARKET_DISCRIMINATOR: [u8; 8] = [246, 114, 50, 98, 72, 157, 28, 120];
+
+/// `ReserveStatus::Active`; `Obsolete` and `Hidden` reserves reject deposits.
+const RESERVE_STATUS_ACTIVE: u8 = 0;
+
+// Byte offsets into Kamino's zero-copy `Reserve` account (discriminator included).
+const RESERVE_LIQUIDITY_AVAILABLE_AMOUNT_OFFSET: usize = 224;
+const RESERVE_LIQUIDITY_BORROWED_AMOUNT_SF_OFFSET: usize = 232;
+const RESERVE_LIQUIDITY_ACCUMULATED_PROTOCOL_FEES_SF_OFFSET: usize = 344;
+const RESERVE_LIQUIDITY_ACCUMULATED_REFERRER_FEES_SF_OFFSET: usize = 360;
+const RESERVE_LIQUIDITY_PENDING_REFERRER_FEES_SF_OFFSET: usize = 376;
+const RESERVE_COLLATERAL_MINT_TOTAL_SUPPLY_OFFSET: usize = 2592;
+const RESERVE_CONFIG_STATUS_OFFSET: usize = 4856;
+const RESERVE_CONFIG_DEPOSIT_LIMIT_OFFSET: usize = 5016;
+
+// Byte offsets into Kamino's zero-copy `LendingMarket` account (discriminator included).
+const LENDING_MARKET_EMERGENCY_MODE_OFFSET: usize = 122;
+
+// Byte offsets into Kamino's zero-copy `Obligation` account (discriminator included).
+const OBLIGATION_DEPOSITED_VALUE_SF_OFFSET: usize = 1192;
+const OBLIGATION_BORROW_FACTOR_ADJUSTED_DEBT_VALUE_SF_OFFSET: usize = 2208;
+const OBLIGATION_BORROWED_ASSETS_MARKET_VALUE_SF_OFFSET: usize = 2224;
+const OBLIGATION_UNHEALTHY_BORROW_VALUE_SF_OFFSET: usize = 2256;
+const OBLIGATION_ELEVATION_GROUP_OFFSET: usize = 2285;
+
+/// Number of fractional bits in Kamino's scaled fractions (`*_sf` fields).
+pub const KAMINO_SF_BITS: u32 = 60;
+
+/// Subset of Kamino's `Reserve` account needed to value deposits.
+pub struct KaminoReserveState {
+    /// Liquidity held by the reserve and available to borrow or withdraw
+    pub available_amount: u64,
+    /// Outstanding borrows, as a scaled fraction
+    pub borrowed_amount_sf: u128,
+    /// Protocol fees not yet claimed, as a scaled fraction
+    pub accumulated_protocol_fees_sf: u128,
+    /// Referrer fees not yet claimed, as a scaled fraction
+    pub accumulated_referrer_fees_sf: u128,
+    /// Referrer fees pending accrual, as a scaled fraction
+    pub pending_referrer_fees_sf: u128,
+    /// Total supply of the reserve's collateral (cToken) mint
+    pub collateral_mint_total_supply: u64,
+    /// Maximum total liquidity the reserve accepts (`ReserveConfig::deposit_limit`)
+    pub deposit_limit: u64,
+    /// Reserve lifecycle status (`ReserveConfig::status`), `0` when active
+    pub status: u8,
+}
+
+impl KaminoReserveState {
+    /// Parses the relevant fields out of raw `Reserve` account data.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoReserveState)` - Parsed reserve state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Kamino `Reserve`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &RESERVE_DISCRIMINATOR)?;
+
+        Ok(Self {
+            available_amount: read_u64(data, RESERVE_LIQUIDITY_AVAILABLE_AMOUNT_OFFSET)?,
+            borrowed_amount_sf: read_u128(data, RESERVE_LIQUIDITY_BORROWED_AMOUNT_SF_OFFSET)?,
+            accumulated_protocol_fees_sf: read_u128(
+                data,
+                RESERVE_LIQUIDITY_ACCUMULATED_PROTOCOL_FEES_SF_OFFSET,
+            )?,
+            accumulated_referrer_fees_sf: read_u128(
+                data,
+                RESERVE_LIQUIDITY_ACCUMULATED_REFERRER_FEES_SF_OFFSET,
+            )?,
+            pending_referrer_fees_sf: read_u128(
+                data,
+                RESERVE_LIQUIDITY_PENDING_REFERRER_FEES_SF_OFFSET,
+            )?,
+            collateral_mint_total_supply: read_u64(
+                data,
+                RESERVE_COLLATERAL_MINT_TOTAL_SUPPLY_OFFSET,
+            )?,
+            deposit_limit: read_u64(data, RESERVE_CONFIG_DEPOSIT_LIMIT_OFFSET)?,
+            status: read_array::<1>(data, RESERVE_CONFIG_STATUS_OFFSET)?[0],
+        })
+    }
+
+    /// Returns `true` when the reserve is active, i.e. neither obsolete nor hidden.
+    pub fn is_active(&self) -> bool {
+        self.status == RESERVE_STATUS_ACTIVE
+    }
+
+    /// Returns `true` when `data` carries the Kamino `Reserve` account discriminator.
+    pub fn is_reserve(data: &[u8]) -> bool {
+        check_discriminator(data, &RESERVE_DISCRIMINATOR).is_ok()
+    }
+
+    /// Total liquidity owned by depositors (available + borrowed - unclaimed fees), as a
+    /// scaled fraction.
+    pub fn total_liquidity_sf(&self) -> Result<u128, ProgramError> {
+        ((self.available_amount as u128) << KAMINO_SF_BITS)
+            .checked_add(self.borrowed_amount_sf)
+            .and_then(|sf| sf.checked_sub(self.accumulated_protocol_fees_sf))
+            .and_then(|sf| sf.checked_sub(self.accumulated_referrer_fees_sf))
+            .and_then(|sf| sf.checked_sub(self.pending_referrer_fees_sf))
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// Total liquidity owned by depositors, rounded to whole tokens in `rounding` direction.
+    pub fn total_liquidity(&self, rounding: Rounding) -> Result<u64, ProgramError> {
+        to_u64(div(
+            self.total_liquidity_sf()?,
+            1 << KAMINO_SF_BITS,
+            rounding,
+        )?)
+    }
+
+    /// Liquidity that can still be deposited before reaching the reserve's deposit limit.
+    ///
+    /// Outstanding borrows are rounded up, so the capacity never overshoots the limit.
+    pub fn deposit_capacity(&self) -> Result<u64, ProgramError> {
+        Ok(self
+            .deposit_limit
+            .saturating_sub(self.total_liquidity(Rounding::Up)?))
+    }
+
+    /// Collateral (cTokens) minted for depositing `amount` of liquidity, rounded down.
+    ///
+    /// Mirrors Kamino's collateral exchange rate: 1:1 for an empty reserve, otherwise
+    /// `collateral_supply / total_liquidity`. The total liquidity is rounded up, so the
+    /// preview never promises more collateral than Kamino mints.
+    pub fn liquidity_to_collateral(&self, amount: u64) -> Result<u64, ProgramError> {
+        let total_liquidity = self.total_liquidity(Rounding::Up)?;
+
+        if self.collateral_mint_total_supply == 0 || total_liquidity == 0 {
+            return Ok(amount);
+        }
+
+        mul_div_u64(
+            amount,
+            self.collateral_mint_total_supply,
+            total_liquidity,
+            Rounding::Down,
+        )
+    }
+}
+
+/// Subset of Kamino's `LendingMarket` account needed to detect a market-wide pause.
+pub struct KaminoLendingMarketState {
+    /// Whether the market's emergency mode, which halts all user operations, is on
+    pub emergency_mode: bool,
+}
+
+impl KaminoLendingMarketState {
+    /// Parses the relevant fields out of raw `LendingMarket` account data.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoLendingMarketState)` - Parsed market state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Kamino `LendingMarket`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &LENDING_MARKET_DISCRIMINATOR)?;
+
+        Ok(Self {
+            emergency_mode: read_array::<1>(data, LENDING_MARKET_EMERGENCY_MODE_OFFSET)?[0] != 0,
+        })
+    }
+}
+
+/// Subset of Kamino's `Obligation` account needed to assess position health.
+pub struct KaminoObligationState {
+    /// Market value of all deposits, as a scaled fraction
+    pub deposited_value_sf: u128,
+    /// Debt value weighted by each reserve's borrow factor, as a scaled fraction
+    pub borrow_factor_adjusted_debt_value_sf: u128,
+    /// Market value of all borrows, as a scaled fraction
+    pub borrowed_assets_market_value_sf: u128,
+    /// Debt value above which the obligation can be liquidated, as a scaled fraction
+    pub unhealthy_borrow_value_sf: u128,
+    /// Elevation group (eMode) the obligation borrows under, `0` when none
+    pub elevation_group: u8,
+}
+
+impl KaminoObligationState {
+    /// Parses the relevant fields out of raw `Obligation` account data.
+    ///
+    /// # Returns
+    /// * `Ok(KaminoObligationState)` - Parsed obligation state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Kamino `Obligation`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &OBLIGATION_DISCRIMINATOR)?;
+
+        Ok(Self {
+            deposited_value_sf: read_u128(data, OBLIGATION_DEPOSITED_VALUE_SF_OFFSET)?,
+            borrow_factor_adjusted_debt_value_sf: read_u128(
+                data,
+                OBLIGATION_BORROW_FACTOR_ADJUSTED_DEBT_VALUE_SF_OFFSET,
+            )?,
+            borrowed_assets_market_value_sf: read_u128(
+                data,
+                OBLIGATION_BORROWED_ASSETS_MARKET_VALUE_SF_OFFSET,
+            )?,
+            unhealthy_borrow_value_sf: read_u128(
+                data,
+                OBLIGATION_UNHEALTHY_BORROW_VALUE_SF_OFFSET,
+            )?,
+            elevation_group: read_array::<1>(data, OBLIGATION_ELEVATION_GROUP_OFFSET)?[0],
+        })
+    }
+
+    /// Normalizes the obligation into a protocol-agnostic `HealthInfo`.
+    ///
+    /// LTV uses the borrow-factor-adjusted debt, matching how Kamino decides liquidations.
+    pub fn health(&self) -> Result<HealthInfo, ProgramError> {
+        Ok(HealthInfo {
+            ltv: sf_ratio_bps(
+                self.borrow_factor_adjusted_debt_value_sf,
+                self.deposited_value_sf,
+            )?,
+            liquidation_threshold: sf_ratio_bps(
+                self.unhealthy_borrow_value_sf,
+                self.deposited_value_sf,
+            )?,
+            borrow_value: sf_to_value(self.borrowed_assets_market_value_sf)?,
+            collateral_value: sf_to_value(self.deposited_value_sf)?,
+        })
+    }
+}
+
+/// `numerator / denominator` in basis points, rounded down (0 when `denominator` is 0).
+fn sf_ratio_bps(numerator: u128, denominator: u128) -> Result<u64, ProgramError> {
+    if denominator == 0 {
+        return Ok(0);
+    }
+
+    to_u64(mul_div(
+        numerator,
+        BPS_DENOMINATOR as u128,
+        denominator,
+        Rounding::Down,
+    )?)
+}
+
+/// Converts a Kamino scaled fraction into a value scaled by `HEALTH_VALUE_SCALE`.
+fn sf_to_value(sf: u128) -> Result<u128, ProgramError> {
+    let integer = (sf >> KAMINO_SF_BITS)
+        .checked_mul(HEALTH_VALUE_SCALE)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let fraction = ((sf & ((1 << KAMINO_SF_BITS) - 1)) * HEALTH_VALUE_SCALE) >> KAMINO_SF_BITS;
+
+    integer
+        .checked_add(fraction)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {
+        super::*,
+        crate::test_utils::{PROPERTY_CASES, Rng, account_data},
+        std::vec::Vec,
+    };
+
+    const RESERVE_LEN: usize = 8624;
+
+    /// `Reserve` bytes with `fields` written at their offsets, everything else zeroed.
+    fn reserve_data(fields: &[(usize, &[u8])]) -> Vec<u8> {
+        let mut data = account_data(&RESERVE_DISCRIMINATOR, RESERVE_LEN);
+        for (offset, bytes) in fields {
+            data[*offset..*offset + bytes.len()].copy_from_slice(bytes);
+        }
+        data
+    }
+
+    /// USDC-like reserve: 1.25M available, 3.5M (+0.5) borrowed, 1.2M (+0.25) protocol fees.
+    fn usdc_reserve() -> Vec<u8> {
+        let half = 1u128 << (KAMINO_SF_BITS - 1);
+        reserve_data(&[
+            (
+                RESERVE_LIQUIDITY_AVAILABLE_AMOUNT_OFFSET,
+                &1_250_000_000_000u64.to_le_bytes(),
+            ),
+            (
+                RESERVE_LIQUIDITY_BORROWED_AMOUNT_SF_OFFSET,
+                &((3_500_000_000_000u128 << KAMINO_SF_BITS) + half).to_le_bytes(),
+            ),
+            (
+                RESERVE_LIQUIDITY_ACCUMULATED_PROTOCOL_FEES_SF_OFFSET,
+                &((1_200_000u128 << KAMINO_SF_BITS) + half / 2).to_le_bytes(),
+            ),
+            (
+                RESERVE_COLLATERAL_MINT_TOTAL_SUPPLY_OFFSET,
+                &4_400_123_456_789u64.to_le_bytes(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn total_liquidity_rounds_in_requested_direction() {
+        let reserve = KaminoReserveState::from_bytes(&usdc_reserve()).unwrap();
+        assert_eq!(
+            reserve.total_liquidity(Rounding::Down),
+            Ok(4_749_998_800_000)
+        );
+        assert_eq!(reserve.total_liquidity(Rounding::Up), Ok(4_749_998_800_001));
+    }
+
+    #[test]
+    fn deposit_capacity_reads_the_config_limit() {
+        // `ReserveConfig::deposit_limit` sits at byte 5016 in the IDL layout.
+        let mut data = usdc_reserve();
+        data[5016..5024].copy_from_slice(&5_000_000_000_000u64.to_le_bytes());
+        let reserve = KaminoReserveState::from_bytes(&data).unwrap();
+        assert_eq!(reserve.deposit_limit, 5_000_000_000_000);
+        assert_eq!(reserve.deposit_capacity(), Ok(250_001_199_999));
+
+        data[5016..5024].copy_from_slice(&4_000_000_000_000u64.to_le_bytes());
+        let reserve = KaminoReserveState::from_bytes(&data).unwrap();
+        assert_eq!(reserve.deposit_capacity(), Ok(0));
+    }
+
+    #[test]
+    fn liquidity_to_collateral_matches_snapshot() {
+        let reserve = KaminoReserveState::from_bytes(&usdc_reserve()).unwrap();
+        assert_eq!(
+            reserve.liquidity_to_collateral(10_000_000_000),
+            Ok(9_263_420_143)
+        );
+    }
+
+    #[test]
+    fn liquidity_to_collateral_rounds_against_depositor() {
+        // 2.5 liquidity backing 5 cTokens: 3 liquidity is worth exactly 6 cTokens, and a
+        // floored divisor of 2 would promise 7.
+        let reserve = KaminoReserveState::from_bytes(&reserve_data(&[
+            (
+                RESERVE_LIQUIDITY_AVAILABLE_AMOUNT_OFFSET,
+                &2u64.to_le_bytes(),
+            ),
+            (
+                RESERVE_LIQUIDITY_BORROWED_AMOUNT_SF_OFFSET,
+                &(1u128 << (KAMINO_SF_BITS - 1)).to_le_bytes(),
+            ),
+            (
+                RESERVE_COLLATERAL_MINT_TOTAL_SUPPLY_OFFSET,
+                &5u64.to_le_bytes(),
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(reserve.liquidity_to_collateral(3), Ok(5));
+    }
+
+    /// Full 256-bit product of `a` and `b`, as `(high, low)` halves.
+    fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+        const LOW: u128 = u64::MAX as u128;
+        let (a1, a0, b1, b0) = (a >> 64, a & LOW, b >> 64, b & LOW);
+        let (mid, mid_carry) = (a1 * b0).overflowing_add(a0 * b1);
+        let (low, low_carry) = (a0 * b0).overflowing_add(mid << 64);
+        let high = a1 * b1 + (mid >> 64) + ((mid_carry as u128) << 64) + low_carry as u128;
+        (high, low)
+    }
+
+    #[test]
+    fn minted_collateral_is_never_worth_more_than_the_deposit() {
+        let mut rng = Rng::new(14);
+        for _ in 0..PROPERTY_CASES {
+            let available_amount = rng.amount();
+            let borrowed_amount_sf = ((rng.amount() as u128) << KAMINO_SF_BITS)
+                | (rng.next_u64() >> (64 - KAMINO_SF_BITS)) as u128;
+            let gross_sf = ((available_amount as u128) << KAMINO_SF_BITS) + borrowed_amount_sf;
+            let reserve = KaminoReserveState {
+                available_amount,
+                borrowed_amount_sf,
+                accumulated_protocol_fees_sf: (rng.amount() as u128).min(gross_sf),
+                accumulated_referrer_fees_sf: 0,
+                pending_referrer_fees_sf: 0,
+                collateral_mint_total_supply: rng.amount(),
+                deposit_limit: u64::MAX,
+                status: RESERVE_STATUS_ACTIVE,
+            };
+            let x = rng.amount();
+
+            let (Ok(collateral), Ok(total_liquidity_sf)) = (
+                reserve.liquidity_to_collateral(x),
+                reserve.total_liquidity_sf(),
+            ) else {
+                continue;
+            };
+            if reserve.collateral_mint_total_supply == 0 || total_liquidity_sf == 0 {
+                continue;
+            }
+            // `collateral` valued at the exact rate, `total_liquidity_sf / supply`.
+            let value = mul_wide(collateral as u128, total_liquidity_sf);
+            let deposit = mul_wide(
+                x as u128 * reserve.collateral_mint_total_supply as u128,
+                1 << KAMINO_SF_BITS,
+            );
+            assert!(value <= deposit);
+        }
+    }
+
+    #[test]
+    fn liquidity_to_collateral_is_one_to_one_for_empty_reserve() {
+        let reserve = KaminoReserveState::from_bytes(&reserve_data(&[])).unwrap();
+        assert_eq!(reserve.liquidity_to_collateral(42), Ok(42));
+    }
+
+    #[test]
+    fn from_bytes_rejects_other_accounts() {
+        assert_eq!(
+            KaminoReserveState::from_bytes(&[0; RESERVE_LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            KaminoReserveState::from_bytes(&account_data(&RESERVE_DISCRIMINATOR, 64)).err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    /// `Obligation` bytes in elevation group 2 holding the given values, in whole quote units.
+    pub(crate) fn obligation_data(
+        deposited: u128,
+        adjusted_debt: u128,
+        borrowed: u128,
+        unhealthy: u128,
+    ) -> Vec<u8> {
+        let mut data = account_data(&OBLIGATION_DISCRIMINATOR, 3344);
+        for (offset, value) in [
+            (OBLIGATION_DEPOSITED_VALUE_SF_OFFSET, deposited),
+            (
+                OBLIGATION_BORROW_FACTOR_ADJUSTED_DEBT_VALUE_SF_OFFSET,
+                adjusted_debt,
+            ),
+            (OBLIGATION_BORROWED_ASSETS_MARKET_VALUE_SF_OFFSET, borrowed),
+            (OBLIGATION_UNHEALTHY_BORROW_VALUE_SF_OFFSET, unhealthy),
+        ] {
+            data[offset..offset + 16].copy_from_slice(&(value << KAMINO_SF_BITS).to_le_bytes());
+        }
+        data[OBLIGATION_ELEVATION_GROUP_OFFSET] = 2;
+        data
+    }
+
+    #[test]
+    fn obligation_health_uses_the_borrow_factor_adjusted_debt() {
+        let obligation =
+            KaminoObligationState::from_bytes(&obligation_data(1_000, 600, 500, 800)).unwrap();
+        assert_eq!(obligation.elevation_group, 2);
+        assert_eq!(
+            obligation.health(),
+            Ok(HealthInfo {
+                ltv: 6_000,
+                liquidation_threshold: 8_000,
+                borrow_value: 500 * HEALTH_VALUE_SCALE,
+                collateral_value: 1_000 * HEALTH_VALUE_SCALE,
+            })
+        );
+    }
+
+    #[test]
+    fn empty_obligations_report_zero_ratios() {
+        let obligation = KaminoObligationState::from_bytes(&obligation_data(0, 0, 0, 0)).unwrap();
+        let health = obligation.health().unwrap();
+        assert_eq!((health.ltv, health.liquidation_threshold), (0, 0));
+        assert!(!health.is_healthy());
+    }
+
+    #[test]
+    fn scaled_fractions_keep_their_fractional_part() {
+        assert_eq!(
+            sf_to_value(1 << (KAMINO_SF_BITS - 1)),
+            Ok(HEALTH_VALUE_SCALE / 2)
+        );
+        assert_eq!(
+            sf_to_value((5 << KAMINO_SF_BITS) + (1 << (KAMINO_SF_BITS - 2))),
+            Ok(5 * HEALTH_VALUE_SCALE + HEALTH_VALUE_SCALE / 4)
+        );
+    }
+}
@@ -0,0 +1,339 @@
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+use crate::programs::Protocol;
+use {
+    crate::{AccountCheck, AccountError},
+    pinocchio::{AccountView, address::address_eq},
+};
+
+/// Describes one account expected by a protocol context, in parse order.
+///
+/// Each protocol module exposes its account orderings as `&[AccountSpec]` tables, mirroring
+/// its `try_from` implementation and the flags its CPI passes. Off-chain builders and code
+/// generators read these tables instead of duplicating the orderings by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountSpec {
+    /// Field name in the accounts struct
+    pub name: &'static str,
+    /// Whether the account must be passed writable
+    pub writable: bool,
+    /// Whether the account must sign the transaction (unless signed for by a PDA)
+    pub signer: bool,
+    /// Whether the writable account may alias another writable account (e.g. an optional
+    /// fee receiver commonly set to the user's own token account)
+    pub aliasable: bool,
+}
+
+impl AccountSpec {
+    pub const fn readonly(name: &'static str) -> Self {
+        Self {
+            name,
+            writable: false,
+            signer: false,
+            aliasable: false,
+        }
+    }
+
+    pub const fn writable(name: &'static str) -> Self {
+        Self {
+            name,
+            writable: true,
+            signer: false,
+            aliasable: false,
+        }
+    }
+
+    pub const fn writable_aliasable(name: &'static str) -> Self {
+        Self {
+            name,
+            writable: true,
+            signer: false,
+            aliasable: true,
+        }
+    }
+
+    pub const fn readonly_signer(name: &'static str) -> Self {
+        Self {
+            name,
+            writable: false,
+            signer: true,
+            aliasable: false,
+        }
+    }
+
+    pub const fn writable_signer(name: &'static str) -> Self {
+        Self {
+            name,
+            writable: true,
+            signer: true,
+            aliasable: false,
+        }
+    }
+}
+
+/// Checks `accounts` against `layout` before a context is parsed.
+///
+/// Rejects short slices and writable accounts that alias an earlier writable account,
+/// which protocols would otherwise only reject deep inside the CPI. Writable slots filled
+/// with the protocol program itself (the placeholder for unused optional accounts, e.g.
+/// Kamino farms) may repeat, as may slots marked `aliasable`.
+///
+/// # Returns
+/// * `Ok(())` - The slice holds the whole layout with no aliased writable accounts
+/// * `Err(AccountError)` - `AccountCheck::Missing` for a short slice, or
+///   `AccountCheck::Duplicate` at the second of two aliased writable accounts
+pub fn check_layout(layout: &[AccountSpec], accounts: &[AccountView]) -> Result<(), AccountError> {
+    if accounts.len() < layout.len() {
+        return Err(AccountError::missing(layout, accounts.len()));
+    }
+
+    let Some(program) = accounts.first().map(AccountView::address) else {
+        return Ok(());
+    };
+    let aliased = |index: usize, address| {
+        layout[..index].iter().zip(accounts).any(|(spec, account)| {
+            spec.writable && !spec.aliasable && address_eq(account.address(), address)
+        })
+    };
+
+    for (index, (spec, account)) in layout.iter().zip(accounts).enumerate() {
+        let address = account.address();
+        if spec.writable
+            && !spec.aliasable
+            && !address_eq(address, program)
+            && aliased(index, address)
+        {
+            return Err(AccountError::new(layout, index, AccountCheck::Duplicate));
+        }
+    }
+
+    Ok(())
+}
+
+/// An account layout together with the name of the accounts struct it describes.
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NamedLayout {
+    /// Protocol the layout belongs to
+    pub protocol: Protocol,
+    /// Name of the accounts struct (e.g. `KaminoDepositAccounts`)
+    pub name: &'static str,
+    /// Accounts in parse order
+    pub accounts: &'static [AccountSpec],
+}
+
+/// Every account layout enabled in this build, for code generators.
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub const LAYOUTS: &[NamedLayout] = &[
+    #[cfg(feature = "kamino")]
+    NamedLayout {
+        protocol: Protocol::Kamino,
+        name: "KaminoDepositAccounts",
+        accounts: crate::programs::kamino::KAMINO_DEPOSIT_ACCOUNTS,
+    },
+    #[cfg(feature = "kamino")]
+    NamedLayout {
+        protocol: Protocol::Kamino,
+        name: "KaminoDepositLiquidityAccounts",
+        accounts: crate::programs::kamino::KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+    },
+    #[cfg(feature = "kamino")]
+    NamedLayout {
+        protocol: Protocol::Kamino,
+        name: "KaminoLiquidateAccounts",
+        accounts: crate::programs::kamino::KAMINO_LIQUIDATE_ACCOUNTS,
+    },
+    #[cfg(feature = "kamino")]
+    NamedLayout {
+        protocol: Protocol::Kamino,
+        name: "KaminoHealthAccounts",
+        accounts: crate::programs::kamino::KAMINO_HEALTH_ACCOUNTS,
+    },
+    #[cfg(feature = "kamino")]
+    NamedLayout {
+        protocol: Protocol::Kamino,
+        name: "KaminoRequestElevationGroupAccounts",
+        accounts: crate::programs::kamino::KAMINO_REQUEST_ELEVATION_GROUP_ACCOUNTS,
+    },
+    #[cfg(feature = "jupiter")]
+    NamedLayout {
+        protocol: Protocol::Jupiter,
+        name: "JupiterEarnDepositAccounts",
+        accounts: crate::programs::jupiter::JUPITER_EARN_DEPOSIT_ACCOUNTS,
+    },
+    #[cfg(feature = "drift")]
+    NamedLayout {
+        protocol: Protocol::Drift,
+        name: "DriftInsuranceFundStakeAccounts",
+        accounts: crate::programs::drift::DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+    },
+    #[cfg(feature = "jito")]
+    NamedLayout {
+        protocol: Protocol::Jito,
+        name: "JitoEnqueueWithdrawalAccounts",
+        accounts: crate::programs::jito::JITO_ENQUEUE_WITHDRAWAL_ACCOUNTS,
+    },
+    #[cfg(feature = "jito")]
+    NamedLayout {
+        protocol: Protocol::Jito,
+        name: "JitoBurnWithdrawalTicketAccounts",
+        accounts: crate::programs::jito::JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS,
+    },
+    #[cfg(feature = "solayer")]
+    NamedLayout {
+        protocol: Protocol::Solayer,
+        name: "SolayerRestakeAccounts",
+        accounts: crate::programs::solayer::SOLAYER_RESTAKE_ACCOUNTS,
+    },
+    #[cfg(feature = "marinade")]
+    NamedLayout {
+        protocol: Protocol::Marinade,
+        name: "MarinadeDepositAccounts",
+        accounts: crate::programs::marinade::MARINADE_DEPOSIT_ACCOUNTS,
+    },
+    #[cfg(feature = "marinade")]
+    NamedLayout {
+        protocol: Protocol::Marinade,
+        name: "MarinadeOrderUnstakeAccounts",
+        accounts: crate::programs::marinade::MARINADE_ORDER_UNSTAKE_ACCOUNTS,
+    },
+    #[cfg(feature = "marinade")]
+    NamedLayout {
+        protocol: Protocol::Marinade,
+        name: "MarinadeClaimAccounts",
+        accounts: crate::programs::marinade::MARINADE_CLAIM_ACCOUNTS,
+    },
+    #[cfg(feature = "stake_pool")]
+    NamedLayout {
+        protocol: Protocol::StakePool,
+        name: "SplStakePoolDepositSolAccounts",
+        accounts: crate::programs::stake_pool::SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS,
+    },
+    #[cfg(feature = "stake_pool")]
+    NamedLayout {
+        protocol: Protocol::StakePool,
+        name: "SplStakePoolWithdrawStakeAccounts",
+        accounts: crate::programs::stake_pool::SPL_STAKE_POOL_WITHDRAW_STAKE_ACCOUNTS,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        std::vec::Vec,
+    };
+
+    const LAYOUT: &[AccountSpec] = &[
+        AccountSpec::readonly("program"),
+        AccountSpec::writable_signer("owner"),
+        AccountSpec::writable("vault"),
+        AccountSpec::readonly("mint"),
+        AccountSpec::writable_aliasable("fee_receiver"),
+        AccountSpec::writable("optional"),
+    ];
+
+    fn check(addresses: &[u8]) -> Result<(), AccountError> {
+        let mut accounts: Vec<TestAccount> = addresses
+            .iter()
+            .map(|&byte| TestAccount::empty(address(byte)))
+            .collect();
+        check_layout(LAYOUT, &views(&mut accounts))
+    }
+
+    #[test]
+    fn accepts_distinct_accounts_and_trailing_accounts() {
+        assert_eq!(check(&[0, 1, 2, 3, 4, 5]), Ok(()));
+        assert_eq!(check(&[0, 1, 2, 3, 4, 5, 1, 1]), Ok(()));
+    }
+
+    #[test]
+    fn names_the_first_missing_account() {
+        assert_eq!(
+            check(&[0, 1, 2, 3]),
+            Err(AccountError {
+                index: 4,
+                name: "fee_receiver",
+                check: AccountCheck::Missing,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_the_second_of_two_aliased_writable_accounts() {
+        assert_eq!(
+            check(&[0, 1, 2, 3, 4, 2]),
+            Err(AccountError {
+                index: 5,
+                name: "optional",
+                check: AccountCheck::Duplicate,
+            })
+        );
+    }
+
+    #[test]
+    fn allows_readonly_aliasable_and_program_placeholder_repeats() {
+        // A read-only account may repeat a writable one.
+        assert_eq!(check(&[0, 1, 2, 2, 4, 5]), Ok(()));
+        // The fee receiver may be the owner's own account.
+        assert_eq!(check(&[0, 1, 2, 3, 1, 5]), Ok(()));
+        // Unused optional accounts are filled with the program.
+        assert_eq!(check(&[0, 1, 2, 3, 0, 0]), Ok(()));
+    }
+
+    #[cfg(any(
+        feature = "drift",
+        feature = "jito",
+        feature = "jupiter",
+        feature = "kamino",
+        feature = "marinade",
+        feature = "solayer",
+        feature = "stake_pool"
+    ))]
+    #[test]
+    fn layouts_have_unique_struct_and_account_names() {
+        for (index, layout) in LAYOUTS.iter().enumerate() {
+            assert!(
+                LAYOUTS[..index]
+                    .iter()
+                    .all(|other| other.name != layout.name),
+                "{} is listed twice",
+                layout.name
+            );
+            for (position, spec) in layout.accounts.iter().enumerate() {
+                assert!(
+                    layout.accounts[..position]
+                        .iter()
+                        .all(|other| other.name != spec.name),
+                    "{} repeats {}",
+                    layout.name,
+                    spec.name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,643 @@
+mod state;
+pub use state::*;
+
+mod ticket;
+pub use ticket::*;
+use {
+    crate::{
+        AccountCheck, AccountError, AccountSpec, Capacity, ClaimWithdraw, Deposit, Preview,
+        RequestWithdraw, util::cpi::invoke_signed_with_remaining,
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+pub const MARINADE_PROGRAM_ID: Address = Address::new_from_array([
+    5, 69, 227, 101, 190, 242, 113, 173, 117, 53, 3, 103, 86, 93, 164, 13, 163, 54, 220, 28, 135,
+    155, 177, 84, 138, 122, 252, 197, 90, 169, 57, 30,
+]);
+const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const ORDER_UNSTAKE_DISCRIMINATOR: [u8; 8] = [97, 167, 144, 107, 117, 190, 128, 36];
+const CLAIM_DISCRIMINATOR: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+
+/// Account order expected by `MarinadeDepositAccounts::try_from`.
+pub const MARINADE_DEPOSIT_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("marinade_program"),
+    AccountSpec::writable("state"),
+    AccountSpec::writable("msol_mint"),
+    AccountSpec::writable("liq_pool_sol_leg_pda"),
+    AccountSpec::writable("liq_pool_msol_leg"),
+    AccountSpec::readonly("liq_pool_msol_leg_authority"),
+    AccountSpec::writable("reserve_pda"),
+    AccountSpec::writable_signer("transfer_from"),
+    AccountSpec::writable("mint_to"),
+    AccountSpec::readonly("msol_mint_authority"),
+    AccountSpec::readonly("system_program"),
+    AccountSpec::readonly("token_program"),
+];
+
+/// Account order expected by `MarinadeOrderUnstakeAccounts::try_from`.
+pub const MARINADE_ORDER_UNSTAKE_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("marinade_program"),
+    AccountSpec::writable("state"),
+    AccountSpec::writable("msol_mint"),
+    AccountSpec::writable("burn_msol_from"),
+    AccountSpec::readonly_signer("burn_msol_authority"),
+    AccountSpec::writable("new_ticket_account"),
+    AccountSpec::readonly("clock"),
+    AccountSpec::readonly("rent"),
+    AccountSpec::readonly("token_program"),
+];
+
+/// Account order expected by `MarinadeClaimAccounts::try_from`.
+pub const MARINADE_CLAIM_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("marinade_program"),
+    AccountSpec::writable("state"),
+    AccountSpec::writable("reserve_pda"),
+    AccountSpec::writable("ticket_account"),
+    AccountSpec::writable("transfer_sol_to"),
+    AccountSpec::readonly("clock"),
+    AccountSpec::readonly("system_program"),
+];
+
+/// Marinade liquid staking integration
+pub struct Marinade;
+
+/// Account context for Marinade's deposit instruction.
+///
+/// Stakes SOL and mints mSOL, swapping against the liquidity pool's mSOL leg first when it
+/// holds any.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 12 accounts are present.
+pub struct MarinadeDepositAccounts<'info> {
+    /// Marinade Program
+    pub marinade_program: &'info AccountView,
+    /// Marinade state (writable)
+    pub state: &'info AccountView,
+    /// mSOL mint (writable)
+    pub msol_mint: &'info AccountView,
+    /// Liquidity pool SOL leg PDA (writable)
+    pub liq_pool_sol_leg_pda: &'info AccountView,
+    /// Liquidity pool mSOL leg token account (writable)
+    pub liq_pool_msol_leg: &'info AccountView,
+    /// Authority of the liquidity pool mSOL leg
+    pub liq_pool_msol_leg_authority: &'info AccountView,
+    /// Marinade reserve PDA receiving the SOL (writable)
+    pub reserve_pda: &'info AccountView,
+    /// SOL source (mutable, signer)
+    pub transfer_from: &'info AccountView,
+    /// mSOL token account receiving the minted mSOL (writable)
+    pub mint_to: &'info AccountView,
+    /// mSOL mint authority PDA
+    pub msol_mint_authority: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for MarinadeDepositAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `MarinadeDepositAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(MarinadeDepositAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 12 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `MARINADE_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &MARINADE_PROGRAM_ID)
+    }
+}
+
+impl<'info> MarinadeDepositAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            marinade_program,
+            state,
+            msol_mint,
+            liq_pool_sol_leg_pda,
+            liq_pool_msol_leg,
+            liq_pool_msol_leg_authority,
+            reserve_pda,
+            transfer_from,
+            mint_to,
+            msol_mint_authority,
+            system_program,
+            token_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(MARINADE_DEPOSIT_ACCOUNTS, accounts.len()).into());
+        };
+
+        if !address_eq(marinade_program.address(), program_id) {
+            return Err(
+                AccountError::new(MARINADE_DEPOSIT_ACCOUNTS, 0, AccountCheck::ProgramId).into(),
+            );
+        }
+
+        Ok(Self {
+            marinade_program,
+            state,
+            msol_mint,
+            liq_pool_sol_leg_pda,
+            liq_pool_msol_leg,
+            liq_pool_msol_leg_authority,
+            reserve_pda,
+            transfer_from,
+            mint_to,
+            msol_mint_authority,
+            system_program,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+/// Account context for Marinade's order_unstake instruction.
+///
+/// Burns mSOL and opens an unstake ticket, claimable for SOL once the stake is deactivated
+/// (usually the next epoch).
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 9 accounts are present.
+pub struct MarinadeOrderUnstakeAccounts<'info> {
+    /// Marinade Program
+    pub marinade_program: &'info AccountView,
+    /// Marinade state (writable)
+    pub state: &'info AccountView,
+    /// mSOL mint (writable)
+    pub msol_mint: &'info AccountView,
+    /// mSOL token account to burn from (writable)
+    pub burn_msol_from: &'info AccountView,
+    /// Owner or delegate of `burn_msol_from` (signer)
+    pub burn_msol_authority: &'info AccountView,
+    /// Rent-exempt, zeroed ticket account owned by Marinade (writable)
+    pub new_ticket_account: &'info AccountView,
+    /// Clock sysvar
+    pub clock: &'info AccountView,
+    /// Rent sysvar
+    pub rent: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for MarinadeOrderUnstakeAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `MarinadeOrderUnstakeAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(MarinadeOrderUnstakeAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 9 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `MARINADE_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &MARINADE_PROGRAM_ID)
+    }
+}
+
+impl<'info> MarinadeOrderUnstakeAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            marinade_program,
+            state,
+            msol_mint,
+            burn_msol_from,
+            burn_msol_authority,
+            new_ticket_account,
+            clock,
+            rent,
+            token_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(
+                AccountError::missing(MARINADE_ORDER_UNSTAKE_ACCOUNTS, accounts.len()).into(),
+            );
+        };
+
+        if !address_eq(marinade_program.address(), program_id) {
+            return Err(AccountError::new(
+                MARINADE_ORDER_UNSTAKE_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            marinade_program,
+            state,
+            msol_mint,
+            burn_msol_from,
+            burn_msol_authority,
+            new_ticket_account,
+            clock,
+            rent,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+/// Account context for Marinade's claim instruction.
+///
+/// Pays out the SOL of a ready unstake ticket and closes it.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 7 accounts are present.
+pub struct MarinadeClaimAccounts<'info> {
+    /// Marinade Program
+    pub marinade_program: &'info AccountView,
+    /// Marinade state (writable)
+    pub state: &'info AccountView,
+    /// Marinade reserve PDA paying out the SOL (writable)
+    pub reserve_pda: &'info AccountView,
+    /// The unstake ticket to claim (writable)
+    pub ticket_account: &'info AccountView,
+    /// Ticket beneficiary receiving the SOL (writable)
+    pub transfer_sol_to: &'info AccountView,
+    /// Clock sysvar
+    pub clock: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for MarinadeClaimAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `MarinadeClaimAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(MarinadeClaimAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 7 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `MARINADE_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &MARINADE_PROGRAM_ID)
+    }
+}
+
+impl<'info> MarinadeClaimAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            marinade_program,
+            state,
+            reserve_pda,
+            ticket_account,
+            transfer_sol_to,
+            clock,
+            system_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(MARINADE_CLAIM_ACCOUNTS, accounts.len()).into());
+        };
+
+        if !address_eq(marinade_program.address(), program_id) {
+            return Err(
+                AccountError::new(MARINADE_CLAIM_ACCOUNTS, 0, AccountCheck::ProgramId).into(),
+            );
+        }
+
+        Ok(Self {
+            marinade_program,
+            state,
+            reserve_pda,
+            ticket_account,
+            transfer_sol_to,
+            clock,
+            system_program,
+            remaining,
+        })
+    }
+}
+
+impl<'info> Deposit<'info> for Marinade {
+    type Accounts = MarinadeDepositAccounts<'info>;
+
+    /// Stakes `amount` lamports with Marinade via CPI, receiving mSOL.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `MarinadeDepositAccounts`)
+    /// * `amount` - Lamports to stake
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    fn deposit_signed(
+        ctx: &MarinadeDepositAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(ctx.state.address()),
+            InstructionAccount::writable(ctx.msol_mint.address()),
+            InstructionAccount::writable(ctx.liq_pool_sol_leg_pda.address()),
+            InstructionAccount::writable(ctx.liq_pool_msol_leg.address()),
+            InstructionAccount::readonly(ctx.liq_pool_msol_leg_authority.address()),
+            InstructionAccount::writable(ctx.reserve_pda.address()),
+            InstructionAccount::writable_signer(ctx.transfer_from.address()),
+            InstructionAccount::writable(ctx.mint_to.address()),
+            InstructionAccount::readonly(ctx.msol_mint_authority.address()),
+            InstructionAccount::readonly(ctx.system_program.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.state,
+            ctx.msol_mint,
+            ctx.liq_pool_sol_leg_pda,
+            ctx.liq_pool_msol_leg,
+            ctx.liq_pool_msol_leg_authority,
+            ctx.reserve_pda,
+            ctx.transfer_from,
+            ctx.mint_to,
+            ctx.msol_mint_authority,
+            ctx.system_program,
+            ctx.token_program,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + lamports (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 16]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(DEPOSIT_DISCRIMINATOR.as_ptr(), ptr, 8);
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(8), 8);
+        }
+
+        let deposit_ix = InstructionView {
+            program_id: ctx.marinade_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 16)
+            },
+        };
+
+        invoke_signed_with_remaining(&deposit_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    fn deposit(ctx: &MarinadeDepositAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::deposit_signed(ctx, amount, &[])
+    }
+}
+
+impl<'info> Preview<'info> for Marinade {
+    type Accounts = MarinadeDepositAccounts<'info>;
+
+    /// Returns the mSOL expected for staking `amount` lamports.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Expected mSOL amount, at the price of the last epoch update
+    /// * `Err(ProgramError::IllegalOwner)` - State is not owned by the Marinade program
+    /// * `Err(ProgramError)` - State data could not be read
+    fn preview_deposit(
+        ctx: &MarinadeDepositAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.state.owned_by(ctx.marinade_program.address()) {
+            return Err(
+                AccountError::of(MARINADE_DEPOSIT_ACCOUNTS, "state", AccountCheck::Owner).into(),
+            );
+        }
+
+        let data = ctx.state.try_borrow()?;
+        MarinadeState::from_bytes(&data)?.lamports_to_msol(amount)
+    }
+}
+
+impl<'info> Capacity<'info> for Marinade {
+    type Accounts = MarinadeDepositAccounts<'info>;
+
+    /// Always succeeds: Marinade's staking cap is enforced by the deposit CPI itself.
+    fn check_capacity(_ctx: &MarinadeDepositAccounts<'info>, _amount: u64) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Always `u64::MAX`, for the same reason as `check_capacity`.
+    fn available_deposit_capacity(
+        _ctx: &MarinadeDepositAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        Ok(u64::MAX)
+    }
+
+    /// Always `u64::MAX`: delayed unstakes are paid from deactivated stake, not from a
+    /// liquidity pool.
+    fn available_liquidity_for_withdraw(
+        _ctx: &MarinadeDepositAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        Ok(u64::MAX)
+    }
+
+    /// Always `true`: Marinade's pause flag is not decoded; a paused program surfaces as a
+    /// CPI failure.
+    fn is_operational(_ctx: &MarinadeDepositAccounts<'info>) -> bool {
+        true
+    }
+}
+
+impl<'info> RequestWithdraw<'info> for Marinade {
+    type Accounts = MarinadeOrderUnstakeAccounts<'info>;
+
+    /// Burns `amount` mSOL into a new unstake ticket via `order_unstake`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `MarinadeOrderUnstakeAccounts`)
+    /// * `amount` - Amount of mSOL to unstake
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    fn request_withdraw_signed(
+        ctx: &MarinadeOrderUnstakeAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(ctx.state.address()),
+            InstructionAccount::writable(ctx.msol_mint.address()),
+            InstructionAccount::writable(ctx.burn_msol_from.address()),
+            InstructionAccount::readonly_signer(ctx.burn_msol_authority.address()),
+            InstructionAccount::writable(ctx.new_ticket_account.address()),
+            InstructionAccount::readonly(ctx.clock.address()),
+            InstructionAccount::readonly(ctx.rent.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.state,
+            ctx.msol_mint,
+            ctx.burn_msol_from,
+            ctx.burn_msol_authority,
+            ctx.new_ticket_account,
+            ctx.clock,
+            ctx.rent,
+            ctx.token_program,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + msol_amount (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 16]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(ORDER_UNSTAKE_DISCRIMINATOR.as_ptr(), ptr, 8);
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(8), 8);
+        }
+
+        let order_unstake_ix = InstructionView {
+            program_id: ctx.marinade_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 16)
+            },
+        };
+
+        invoke_signed_with_remaining(
+            &order_unstake_ix,
+            &account_infos,
+            ctx.remaining,
+            signer_seeds,
+        )
+    }
+
+    fn request_withdraw(ctx: &MarinadeOrderUnstakeAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::request_withdraw_signed(ctx, amount, &[])
+    }
+}
+
+impl<'info> ClaimWithdraw<'info> for Marinade {
+    type Accounts = MarinadeClaimAccounts<'info>;
+
+    /// Claims the SOL of a ready unstake ticket via `claim`.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `MarinadeClaimAccounts`)
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    ///
+    /// # Notes
+    /// * Marinade rejects tickets that are not due yet, aborting the transaction. Use
+    ///   `Marinade::check_ticket_ready` first to defer instead.
+    fn claim_withdraw_signed(
+        ctx: &MarinadeClaimAccounts<'info>,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(ctx.state.address()),
+            InstructionAccount::writable(ctx.reserve_pda.address()),
+            InstructionAccount::writable(ctx.ticket_account.address()),
+            InstructionAccount::writable(ctx.transfer_sol_to.address()),
+            InstructionAccount::readonly(ctx.clock.address()),
+            InstructionAccount::readonly(ctx.system_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.state,
+            ctx.reserve_pda,
+            ctx.ticket_account,
+            ctx.transfer_sol_to,
+            ctx.clock,
+            ctx.system_program,
+        ];
+
+        let claim_ix = InstructionView {
+            program_id: ctx.marinade_program.address(),
+            accounts: &accounts,
+            data: &CLAIM_DISCRIMINATOR,
+        };
+
+        invoke_signed_with_remaining(&claim_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    fn claim_withdraw(ctx: &MarinadeClaimAccounts<'info>) -> ProgramResult {
+        Self::claim_withdraw_signed(ctx, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{state::tests::state_data, *},
+        crate::test_utils::{TestAccount, address, views},
+        std::vec::Vec,
+    };
+
+    /// `len` accounts led by the Marinade program, with `state` at index 1.
+    fn accounts(len: usize, state_owner: Address) -> Vec<TestAccount> {
+        (0..len as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(MARINADE_PROGRAM_ID),
+                1 => TestAccount::new(
+                    address(i),
+                    state_owner.clone(),
+                    &state_data(0, MARINADE_PRICE_DENOMINATOR * 5 / 4),
+                ),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn previews_msol_from_the_state_price() {
+        let mut accounts = accounts(MARINADE_DEPOSIT_ACCOUNTS.len(), MARINADE_PROGRAM_ID);
+        let accounts = views(&mut accounts);
+        let ctx = MarinadeDepositAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(
+            Marinade::preview_deposit(&ctx, 1_000_000_000),
+            Ok(800_000_000)
+        );
+        assert_eq!(Marinade::deposit(&ctx, 1_000_000_000), Ok(()));
+    }
+
+    #[test]
+    fn preview_rejects_states_of_another_program() {
+        let mut accounts = accounts(MARINADE_DEPOSIT_ACCOUNTS.len(), address(42));
+        let accounts = views(&mut accounts);
+        let ctx = MarinadeDepositAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(
+            Marinade::preview_deposit(&ctx, 1),
+            Err(ProgramError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn withdraw_contexts_check_the_program_and_account_count() {
+        let mut accounts = accounts(MARINADE_ORDER_UNSTAKE_ACCOUNTS.len(), MARINADE_PROGRAM_ID);
+        let accounts = views(&mut accounts);
+
+        let order = MarinadeOrderUnstakeAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(Marinade::request_withdraw(&order, 1_000), Ok(()));
+        let claim = MarinadeClaimAccounts::try_from(&accounts[..]).unwrap();
+        // Accounts past the claim layout are forwarded.
+        assert_eq!(claim.remaining.len(), 2);
+
+        assert!(matches!(
+            MarinadeOrderUnstakeAccounts::try_from(&accounts[..8]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+        assert!(matches!(
+            MarinadeClaimAccounts::try_from_with_program_id(&accounts, &address(9)),
+            Err(ProgramError::IncorrectProgramId)
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use {
+    crate::util::{Rounding, bytes::*, mul_div_u64},
+    pinocchio::error::ProgramError,
+};
+
+const STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+
+// Byte offsets into Marinade's borsh `State` account (discriminator included).
+const STATE_MSOL_SUPPLY_OFFSET: usize = 504;
+const STATE_MSOL_PRICE_OFFSET: usize = 512;
+
+/// Denominator of Marinade's `msol_price` (lamports per mSOL, as a 32-bit fixed point).
+pub const MARINADE_PRICE_DENOMINATOR: u64 = 1 << 32;
+
+/// Subset of Marinade's `State` account needed to value deposits.
+pub struct MarinadeState {
+    /// Total mSOL supply tracked by the program
+    pub msol_supply: u64,
+    /// Lamports per mSOL, scaled by `MARINADE_PRICE_DENOMINATOR`, as of the last epoch update
+    pub msol_price: u64,
+}
+
+impl MarinadeState {
+    /// Parses the relevant fields out of raw `State` account data.
+    ///
+    /// # Returns
+    /// * `Ok(MarinadeState)` - Parsed state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Marinade `State`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &STATE_DISCRIMINATOR)?;
+
+        Ok(Self {
+            msol_supply: read_u64(data, STATE_MSOL_SUPPLY_OFFSET)?,
+            msol_price: read_u64(data, STATE_MSOL_PRICE_OFFSET)?,
+        })
+    }
+
+    /// mSOL minted for depositing `lamports`, rounded down.
+    pub fn lamports_to_msol(&self, lamports: u64) -> Result<u64, ProgramError> {
+        if self.msol_price == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        mul_div_u64(
+            lamports,
+            MARINADE_PRICE_DENOMINATOR,
+            self.msol_price,
+            Rounding::Down,
+        )
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {
+        super::*,
+        crate::test_utils::{PROPERTY_CASES, Rng, account_data},
+        std::vec::Vec,
+    };
+
+    /// `State` bytes with `msol_supply` and a price of `lamports_per_msol` (fixed point).
+    pub(crate) fn state_data(msol_supply: u64, msol_price: u64) -> Vec<u8> {
+        let mut data = account_data(&STATE_DISCRIMINATOR, 1_000);
+        data[STATE_MSOL_SUPPLY_OFFSET..STATE_MSOL_SUPPLY_OFFSET + 8]
+            .copy_from_slice(&msol_supply.to_le_bytes());
+        data[STATE_MSOL_PRICE_OFFSET..STATE_MSOL_PRICE_OFFSET + 8]
+            .copy_from_slice(&msol_price.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn reads_supply_and_price() {
+        let state = MarinadeState::from_bytes(&state_data(7, 9)).unwrap();
+        assert_eq!((state.msol_supply, state.msol_price), (7, 9));
+    }
+
+    #[test]
+    fn deposits_mint_at_the_msol_price() {
+        // 1.25 SOL per mSOL.
+        let state =
+            MarinadeState::from_bytes(&state_data(0, MARINADE_PRICE_DENOMINATOR * 5 / 4)).unwrap();
+        assert_eq!(state.lamports_to_msol(1_000_000_000), Ok(800_000_000));
+        // Rounded down.
+        assert_eq!(state.lamports_to_msol(9), Ok(7));
+    }
+
+    #[test]
+    fn rejects_unset_prices_and_other_accounts() {
+        let state = MarinadeState::from_bytes(&state_data(0, 0)).unwrap();
+        assert_eq!(
+            state.lamports_to_msol(1),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut data = state_data(0, MARINADE_PRICE_DENOMINATOR);
+        data[0] ^= 1;
+        assert!(matches!(
+            MarinadeState::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        ));
+        assert!(matches!(
+            MarinadeState::from_bytes(&state_data(0, 0)[..515]),
+            Err(ProgramError::AccountDataTooSmall)
+        ));
+    }
+
+    #[test]
+    fn minted_msol_is_never_worth_more_than_the_deposit() {
+        let mut rng = Rng::new(12);
+        for _ in 0..PROPERTY_CASES {
+            let price = rng.amount().max(1);
+            let state = MarinadeState::from_bytes(&state_data(rng.amount(), price)).unwrap();
+            let x = rng.amount();
+
+            if let Ok(msol) = state.lamports_to_msol(x) {
+                assert!(
+                    msol as u128 * price as u128 <= x as u128 * MARINADE_PRICE_DENOMINATOR as u128
+                );
+            }
+        }
+    }
+}
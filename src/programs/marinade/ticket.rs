@@ -0,0 +1,283 @@
+use {
+    super::{MARINADE_CLAIM_ACCOUNTS, Marinade, MarinadeClaimAccounts},
+    crate::{
+        AccountCheck, AccountError, BeethovenError,
+        util::{SYSTEM_PROGRAM_ID, bytes::*},
+    },
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::{Signer, invoke_signed},
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+        sysvars::{Sysvar, clock::Clock, rent::Rent},
+    },
+};
+
+const TICKET_ACCOUNT_DISCRIMINATOR: [u8; 8] = [133, 77, 18, 98, 211, 1, 231, 3];
+
+// Byte offsets into Marinade's `TicketAccountData` account (discriminator included).
+const TICKET_STATE_OFFSET: usize = 8;
+const TICKET_BENEFICIARY_OFFSET: usize = 40;
+const TICKET_LAMPORTS_AMOUNT_OFFSET: usize = 72;
+const TICKET_CREATED_EPOCH_OFFSET: usize = 80;
+
+/// Size of a Marinade unstake ticket account, in bytes.
+pub const MARINADE_TICKET_ACCOUNT_LEN: usize = 88;
+
+/// Seconds Marinade waits into the epoch after `created_epoch` before a ticket is due.
+pub const MARINADE_TICKET_EXTRA_WAIT_SECONDS: i64 = 30 * 60;
+
+const CREATE_ACCOUNT_TAG: u32 = 0;
+
+/// Marinade's `TicketAccountData`: SOL owed to `beneficiary` by a delayed unstake.
+pub struct MarinadeTicketState {
+    /// Marinade state the ticket belongs to
+    pub state: Address,
+    /// Account the SOL is paid to on claim
+    pub beneficiary: Address,
+    /// Lamports owed
+    pub lamports_amount: u64,
+    /// Epoch the unstake was ordered in
+    pub created_epoch: u64,
+}
+
+impl MarinadeTicketState {
+    /// Parses raw `TicketAccountData` account data.
+    ///
+    /// # Returns
+    /// * `Ok(MarinadeTicketState)` - Parsed ticket
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Marinade ticket
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &TICKET_ACCOUNT_DISCRIMINATOR)?;
+
+        Ok(Self {
+            state: read_address(data, TICKET_STATE_OFFSET)?,
+            beneficiary: read_address(data, TICKET_BENEFICIARY_OFFSET)?,
+            lamports_amount: read_u64(data, TICKET_LAMPORTS_AMOUNT_OFFSET)?,
+            created_epoch: read_u64(data, TICKET_CREATED_EPOCH_OFFSET)?,
+        })
+    }
+
+    /// Returns `true` when Marinade accepts claiming the ticket at `clock`.
+    ///
+    /// A ticket is due one full epoch after it was created, plus
+    /// `MARINADE_TICKET_EXTRA_WAIT_SECONDS` into that epoch so the stake has deactivated.
+    pub fn is_ready(&self, clock: &Clock) -> bool {
+        let due_epoch = self.created_epoch.saturating_add(1);
+        clock.epoch > due_epoch
+            || (clock.epoch == due_epoch
+                && clock.unix_timestamp
+                    >= clock
+                        .epoch_start_timestamp
+                        .saturating_add(MARINADE_TICKET_EXTRA_WAIT_SECONDS))
+    }
+}
+
+impl Marinade {
+    /// Creates an empty, rent-exempt ticket account owned by `marinade_program`, to pass as
+    /// `new_ticket_account` to `order_unstake`.
+    ///
+    /// # Arguments
+    /// * `payer` - Funds the rent (mutable, signer)
+    /// * `ticket` - Ticket account to create (mutable, signer: keypair or PDA)
+    /// * `system_program` - System program
+    /// * `marinade_program` - Marinade program, owner of the new account
+    /// * `signer_seeds` - PDA signer seeds of `payer` and/or `ticket`
+    pub fn create_ticket_account_signed(
+        payer: &AccountView,
+        ticket: &AccountView,
+        system_program: &AccountView,
+        marinade_program: &AccountView,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        if !address_eq(system_program.address(), &SYSTEM_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let lamports = Rent::get()?.try_minimum_balance(MARINADE_TICKET_ACCOUNT_LEN)?;
+
+        let accounts = [
+            InstructionAccount::writable_signer(payer.address()),
+            InstructionAccount::writable_signer(ticket.address()),
+        ];
+
+        // Build instruction data: tag (4 bytes) + lamports (8 bytes) + space (8 bytes) + owner (32 bytes)
+        let mut instruction_data = [0u8; 52];
+        instruction_data[..4].copy_from_slice(&CREATE_ACCOUNT_TAG.to_le_bytes());
+        instruction_data[4..12].copy_from_slice(&lamports.to_le_bytes());
+        instruction_data[12..20]
+            .copy_from_slice(&(MARINADE_TICKET_ACCOUNT_LEN as u64).to_le_bytes());
+        instruction_data[20..].copy_from_slice(marinade_program.address().as_ref());
+
+        let create_ix = InstructionView {
+            program_id: system_program.address(),
+            accounts: &accounts,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&create_ix, &[payer, ticket], signer_seeds)
+    }
+
+    /// Reads the ticket of a claim context.
+    ///
+    /// # Returns
+    /// * `Ok(MarinadeTicketState)` - Parsed ticket
+    /// * `Err(ProgramError::IllegalOwner)` - Ticket is not owned by the Marinade program
+    /// * `Err(ProgramError)` - Ticket data could not be read
+    pub fn ticket(ctx: &MarinadeClaimAccounts) -> Result<MarinadeTicketState, ProgramError> {
+        if !ctx.ticket_account.owned_by(ctx.marinade_program.address()) {
+            return Err(AccountError::of(
+                MARINADE_CLAIM_ACCOUNTS,
+                "ticket_account",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        MarinadeTicketState::from_bytes(&ctx.ticket_account.try_borrow()?)
+    }
+
+    /// Checks, against the Clock sysvar, that the ticket of `ctx` can be claimed now.
+    ///
+    /// Lets callers skip or defer a claim instead of having the claim CPI abort the
+    /// transaction.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Lamports the claim will pay out
+    /// * `Err(BeethovenError::WithdrawalNotReady)` - The ticket is not due yet
+    /// * `Err(ProgramError::InvalidAccountData)` - `transfer_sol_to` is not the beneficiary
+    /// * `Err(ProgramError)` - Ticket or Clock sysvar could not be read
+    pub fn check_ticket_ready(ctx: &MarinadeClaimAccounts) -> Result<u64, ProgramError> {
+        let ticket = Self::ticket(ctx)?;
+
+        if !address_eq(&ticket.beneficiary, ctx.transfer_sol_to.address()) {
+            return Err(AccountError::of(
+                MARINADE_CLAIM_ACCOUNTS,
+                "transfer_sol_to",
+                AccountCheck::Key,
+            )
+            .into());
+        }
+
+        if !ticket.is_ready(&Clock::get()?) {
+            return Err(BeethovenError::WithdrawalNotReady.into());
+        }
+        Ok(ticket.lamports_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            programs::marinade::MARINADE_PROGRAM_ID,
+            test_utils::{TestAccount, account_data, address, views},
+        },
+        std::vec::Vec,
+    };
+
+    const EPOCH_START: i64 = 1_700_000_000;
+
+    fn ticket_data(created_epoch: u64) -> Vec<u8> {
+        let mut data = account_data(&TICKET_ACCOUNT_DISCRIMINATOR, MARINADE_TICKET_ACCOUNT_LEN);
+        data[TICKET_STATE_OFFSET..TICKET_BENEFICIARY_OFFSET].copy_from_slice(address(1).as_ref());
+        data[TICKET_BENEFICIARY_OFFSET..TICKET_LAMPORTS_AMOUNT_OFFSET]
+            .copy_from_slice(address(4).as_ref());
+        data[TICKET_LAMPORTS_AMOUNT_OFFSET..TICKET_CREATED_EPOCH_OFFSET]
+            .copy_from_slice(&2_000_000_000u64.to_le_bytes());
+        data[TICKET_CREATED_EPOCH_OFFSET..MARINADE_TICKET_ACCOUNT_LEN]
+            .copy_from_slice(&created_epoch.to_le_bytes());
+        data
+    }
+
+    fn clock(epoch: u64, seconds_into_epoch: i64) -> Clock {
+        Clock {
+            slot: 0,
+            epoch_start_timestamp: EPOCH_START,
+            epoch,
+            leader_schedule_epoch: epoch + 1,
+            unix_timestamp: EPOCH_START + seconds_into_epoch,
+        }
+    }
+
+    /// Claim accounts whose ticket is owned by `owner` and paid to `transfer_sol_to`.
+    fn claim_accounts(owner: Address, transfer_sol_to: u8) -> Vec<TestAccount> {
+        let mut accounts: Vec<TestAccount> = (0..MARINADE_CLAIM_ACCOUNTS.len() as u8)
+            .map(|i| TestAccount::empty(address(i)))
+            .collect();
+        accounts[0] = TestAccount::empty(MARINADE_PROGRAM_ID);
+        accounts[3] = TestAccount::new(address(3), owner, &ticket_data(500));
+        accounts[4] = TestAccount::empty(address(transfer_sol_to));
+        accounts
+    }
+
+    #[test]
+    fn ticket_reads_its_fields() {
+        let ticket = MarinadeTicketState::from_bytes(&ticket_data(500)).unwrap();
+        assert_eq!(ticket.state, address(1));
+        assert_eq!(ticket.beneficiary, address(4));
+        assert_eq!(ticket.lamports_amount, 2_000_000_000);
+        assert_eq!(ticket.created_epoch, 500);
+    }
+
+    #[test]
+    fn ticket_is_due_after_the_extra_wait_of_the_next_epoch() {
+        let ticket = MarinadeTicketState::from_bytes(&ticket_data(500)).unwrap();
+
+        assert!(!ticket.is_ready(&clock(500, 86_400)));
+        assert!(!ticket.is_ready(&clock(501, MARINADE_TICKET_EXTRA_WAIT_SECONDS - 1)));
+        assert!(ticket.is_ready(&clock(501, MARINADE_TICKET_EXTRA_WAIT_SECONDS)));
+        assert!(ticket.is_ready(&clock(502, 0)));
+    }
+
+    #[test]
+    fn ticket_requires_a_marinade_owned_account() {
+        let mut accounts = claim_accounts(address(42), 4);
+        let accounts = views(&mut accounts);
+        let ctx = MarinadeClaimAccounts::try_from(&accounts[..]).unwrap();
+        assert!(matches!(
+            Marinade::ticket(&ctx),
+            Err(ProgramError::IllegalOwner)
+        ));
+    }
+
+    #[test]
+    fn readiness_check_rejects_another_beneficiary() {
+        let mut accounts = claim_accounts(MARINADE_PROGRAM_ID, 9);
+        let accounts = views(&mut accounts);
+        let ctx = MarinadeClaimAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(
+            Marinade::ticket(&ctx).unwrap().lamports_amount,
+            2_000_000_000
+        );
+        assert_eq!(
+            Marinade::check_ticket_ready(&ctx),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn ticket_creation_requires_the_system_program() {
+        let mut accounts = [
+            TestAccount::empty(address(1)).signer(),
+            TestAccount::empty(address(2)).signer(),
+            TestAccount::empty(address(3)),
+            TestAccount::empty(MARINADE_PROGRAM_ID),
+        ];
+        let [payer, ticket, system_program, marinade_program] =
+            accounts.each_mut().map(|account| account.view());
+        assert_eq!(
+            Marinade::create_ticket_account_signed(
+                &payer,
+                &ticket,
+                &system_program,
+                &marinade_program,
+                &[]
+            ),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+}
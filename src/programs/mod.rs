@@ -1,3 +1,128 @@
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod ids;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use ids::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod protocol;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use protocol::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod detector;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use detector::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod descriptor;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use descriptor::*;
+
+pub mod layout;
+pub use layout::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod errors;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use errors::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod guard;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use guard::*;
+
 #[cfg(feature = "kamino")]
 pub mod kamino;
 #[cfg(feature = "kamino")]
@@ -7,3 +132,28 @@ pub use kamino::*;
 pub mod jupiter;
 #[cfg(feature = "jupiter")]
 pub use jupiter::*;
+
+#[cfg(feature = "drift")]
+pub mod drift;
+#[cfg(feature = "drift")]
+pub use drift::*;
+
+#[cfg(feature = "jito")]
+pub mod jito;
+#[cfg(feature = "jito")]
+pub use jito::*;
+
+#[cfg(feature = "marinade")]
+pub mod marinade;
+#[cfg(feature = "marinade")]
+pub use marinade::*;
+
+#[cfg(feature = "solayer")]
+pub mod solayer;
+#[cfg(feature = "solayer")]
+pub use solayer::*;
+
+#[cfg(feature = "stake_pool")]
+pub mod stake_pool;
+#[cfg(feature = "stake_pool")]
+pub use stake_pool::*;
@@ -0,0 +1,159 @@
+use {
+    crate::programs::ProgramIds,
+    core::{fmt, str::FromStr},
+    pinocchio::{Address, error::ProgramError},
+};
+
+/// Protocols beethoven can route to.
+///
+/// Discriminant values are stable and never reused, so they can be carried in
+/// instruction data regardless of which protocol features are enabled.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    #[cfg(feature = "kamino")]
+    Kamino = 0,
+
+    #[cfg(feature = "jupiter")]
+    Jupiter = 1,
+
+    #[cfg(feature = "drift")]
+    Drift = 2,
+
+    #[cfg(feature = "solayer")]
+    Solayer = 3,
+
+    #[cfg(feature = "marinade")]
+    Marinade = 4,
+
+    #[cfg(feature = "stake_pool")]
+    StakePool = 5,
+
+    #[cfg(feature = "jito")]
+    Jito = 6,
+}
+
+impl Protocol {
+    /// Every protocol enabled in this build.
+    pub const ALL: &'static [Protocol] = &[
+        #[cfg(feature = "kamino")]
+        Protocol::Kamino,
+        #[cfg(feature = "jupiter")]
+        Protocol::Jupiter,
+        #[cfg(feature = "drift")]
+        Protocol::Drift,
+        #[cfg(feature = "solayer")]
+        Protocol::Solayer,
+        #[cfg(feature = "marinade")]
+        Protocol::Marinade,
+        #[cfg(feature = "stake_pool")]
+        Protocol::StakePool,
+        #[cfg(feature = "jito")]
+        Protocol::Jito,
+    ];
+
+    /// Stable lowercase identifier, as used in logs and by `FromStr`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "kamino")]
+            Protocol::Kamino => "kamino",
+
+            #[cfg(feature = "jupiter")]
+            Protocol::Jupiter => "jupiter",
+
+            #[cfg(feature = "drift")]
+            Protocol::Drift => "drift",
+
+            #[cfg(feature = "solayer")]
+            Protocol::Solayer => "solayer",
+
+            #[cfg(feature = "marinade")]
+            Protocol::Marinade => "marinade",
+
+            #[cfg(feature = "stake_pool")]
+            Protocol::StakePool => "stake_pool",
+
+            #[cfg(feature = "jito")]
+            Protocol::Jito => "jito",
+        }
+    }
+
+    /// Program ID of the protocol's mainnet deployment (see `ProgramIds::MAINNET`).
+    pub fn program_id(self) -> &'static Address {
+        const MAINNET: &ProgramIds = &ProgramIds::MAINNET;
+        MAINNET.get(self)
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = ProgramError;
+
+    /// Parses a protocol from its `name()`.
+    ///
+    /// # Returns
+    /// * `Ok(Protocol)` - Name matches an enabled protocol
+    /// * `Err(ProgramError::InvalidArgument)` - Unknown name or protocol feature disabled
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Protocol::ALL
+            .iter()
+            .copied()
+            .find(|protocol| protocol.name() == name)
+            .ok_or(ProgramError::InvalidArgument)
+    }
+}
+
+impl TryFrom<u8> for Protocol {
+    type Error = ProgramError;
+
+    /// Converts a stable protocol tag back into a `Protocol`.
+    ///
+    /// # Returns
+    /// * `Ok(Protocol)` - Tag matches an enabled protocol
+    /// * `Err(ProgramError::InvalidInstructionData)` - Unknown tag or protocol feature disabled
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        Protocol::ALL
+            .iter()
+            .copied()
+            .find(|protocol| *protocol as u8 == tag)
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_tags_round_trip() {
+        for &protocol in Protocol::ALL {
+            assert_eq!(protocol.name().parse(), Ok(protocol));
+            assert_eq!(Protocol::try_from(protocol as u8), Ok(protocol));
+            assert_eq!(
+                ProgramIds::MAINNET.protocol_of(protocol.program_id()),
+                Some(protocol)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_tags() {
+        assert_eq!(
+            "solend".parse::<Protocol>(),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            "Kamino".parse::<Protocol>(),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            Protocol::try_from(u8::MAX),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,394 @@
+mod state;
+pub use state::*;
+use {
+    crate::{
+        AccountCheck, AccountError, AccountSpec, BeethovenError, Capacity, Deposit, Preview,
+        util::{
+            bytes::{MINT_SUPPLY_OFFSET, TOKEN_ACCOUNT_AMOUNT_OFFSET, read_address, read_u64},
+            cpi::invoke_signed_with_remaining,
+        },
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+pub const SOLAYER_RESTAKING_PROGRAM_ID: Address = Address::new_from_array([
+    12, 236, 52, 126, 25, 7, 34, 108, 140, 139, 228, 36, 86, 183, 236, 94, 235, 38, 229, 119, 211,
+    36, 94, 120, 19, 41, 171, 164, 124, 166, 113, 239,
+]);
+const RESTAKE_DISCRIMINATOR: [u8; 8] = [97, 161, 241, 167, 6, 32, 213, 53];
+
+const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+/// Account order expected by `SolayerRestakeAccounts::try_from`.
+pub const SOLAYER_RESTAKE_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("restaking_program"),
+    AccountSpec::writable_signer("signer"),
+    AccountSpec::readonly("lst_mint"),
+    AccountSpec::writable("lst_ata"),
+    AccountSpec::writable("rst_ata"),
+    AccountSpec::writable("rst_mint"),
+    AccountSpec::writable("vault"),
+    AccountSpec::writable("pool"),
+    AccountSpec::readonly("associated_token_program"),
+    AccountSpec::readonly("token_program"),
+    AccountSpec::readonly("system_program"),
+];
+
+/// Solayer restaking integration
+pub struct SolayerRestaking;
+
+/// Account context for Solayer's restake instruction.
+///
+/// Restakes an LST into its Solayer pool and mints the matching restaked token (e.g. sSOL).
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 11 accounts are present.
+pub struct SolayerRestakeAccounts<'info> {
+    /// Solayer Restaking Program
+    pub restaking_program: &'info AccountView,
+    /// User signer (mutable, signer)
+    pub signer: &'info AccountView,
+    /// Mint of the LST being restaked
+    pub lst_mint: &'info AccountView,
+    /// User's LST token account (writable)
+    pub lst_ata: &'info AccountView,
+    /// User's restaked token account (writable)
+    pub rst_ata: &'info AccountView,
+    /// Restaked token mint (writable)
+    pub rst_mint: &'info AccountView,
+    /// Pool's LST vault (writable)
+    pub vault: &'info AccountView,
+    /// Restaking pool PDA (writable)
+    pub pool: &'info AccountView,
+    /// Associated token program
+    pub associated_token_program: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for SolayerRestakeAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `SolayerRestakeAccounts`.
+    ///
+    /// # Arguments
+    /// * `accounts` - Slice containing at least 11 accounts in the correct order
+    ///
+    /// # Returns
+    /// * `Ok(SolayerRestakeAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 11 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `SOLAYER_RESTAKING_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &SOLAYER_RESTAKING_PROGRAM_ID)
+    }
+}
+
+impl<'info> SolayerRestakeAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`
+    /// instead of `SOLAYER_RESTAKING_PROGRAM_ID` (e.g. for devnet or staging deployments).
+    ///
+    /// # Returns
+    /// * `Ok(SolayerRestakeAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 11 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `program_id`
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            restaking_program,
+            signer,
+            lst_mint,
+            lst_ata,
+            rst_ata,
+            rst_mint,
+            vault,
+            pool,
+            associated_token_program,
+            token_program,
+            system_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(SOLAYER_RESTAKE_ACCOUNTS, accounts.len()).into());
+        };
+
+        if !address_eq(restaking_program.address(), program_id) {
+            return Err(
+                AccountError::new(SOLAYER_RESTAKE_ACCOUNTS, 0, AccountCheck::ProgramId).into(),
+            );
+        }
+
+        Ok(Self {
+            restaking_program,
+            signer,
+            lst_mint,
+            lst_ata,
+            rst_ata,
+            rst_mint,
+            vault,
+            pool,
+            associated_token_program,
+            token_program,
+            system_program,
+            remaining,
+        })
+    }
+}
+
+impl<'info> Deposit<'info> for SolayerRestaking {
+    type Accounts = SolayerRestakeAccounts<'info>;
+
+    /// Restakes `amount` of LST into its Solayer pool via CPI.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `SolayerRestakeAccounts`)
+    /// * `amount` - Amount of LST to restake
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    ///
+    /// # Returns
+    /// * `Ok(())` - Restake completed successfully
+    /// * `Err(ProgramError)` - Invalid accounts or CPI failure
+    fn deposit_signed(
+        ctx: &SolayerRestakeAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable_signer(ctx.signer.address()),
+            InstructionAccount::readonly(ctx.lst_mint.address()),
+            InstructionAccount::writable(ctx.lst_ata.address()),
+            InstructionAccount::writable(ctx.rst_ata.address()),
+            InstructionAccount::writable(ctx.rst_mint.address()),
+            InstructionAccount::writable(ctx.vault.address()),
+            InstructionAccount::writable(ctx.pool.address()),
+            InstructionAccount::readonly(ctx.associated_token_program.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+            InstructionAccount::readonly(ctx.system_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.signer,
+            ctx.lst_mint,
+            ctx.lst_ata,
+            ctx.rst_ata,
+            ctx.rst_mint,
+            ctx.vault,
+            ctx.pool,
+            ctx.associated_token_program,
+            ctx.token_program,
+            ctx.system_program,
+        ];
+
+        // Build instruction data: discriminator (8 bytes) + amount (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 16]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            core::ptr::copy_nonoverlapping(RESTAKE_DISCRIMINATOR.as_ptr(), ptr, 8);
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(8), 8);
+        }
+
+        let restake_ix = InstructionView {
+            program_id: ctx.restaking_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 16)
+            },
+        };
+
+        invoke_signed_with_remaining(&restake_ix, &account_infos, ctx.remaining, signer_seeds)?;
+
+        Ok(())
+    }
+
+    fn deposit(ctx: &SolayerRestakeAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::deposit_signed(ctx, amount, &[])
+    }
+}
+
+impl SolayerRestakeAccounts<'_> {
+    /// Reads the pool's vault balance and restaked token supply, after checking that `pool`,
+    /// `vault` and the mints belong together.
+    ///
+    /// # Returns
+    /// * `Ok((u64, u64))` - LST held by the vault, and supply of the restaked token
+    /// * `Err(ProgramError::IllegalOwner)` - `pool` is not owned by the restaking program
+    /// * `Err(ProgramError::InvalidAccountData)` - `pool` is not a `RestakingPool` for
+    ///   `lst_mint` and `rst_mint`, or `vault` is not its LST account
+    /// * `Err(ProgramError)` - Account data could not be read
+    pub fn pool_backing(&self) -> Result<(u64, u64), ProgramError> {
+        if !self.pool.owned_by(self.restaking_program.address()) {
+            return Err(ProgramError::IllegalOwner);
+        }
+        let pool = SolayerRestakingPoolState::from_bytes(&self.pool.try_borrow()?)?;
+        if !address_eq(&pool.lst_mint, self.lst_mint.address())
+            || !address_eq(&pool.rst_mint, self.rst_mint.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault = self.vault.try_borrow()?;
+        if !address_eq(
+            &read_address(&vault, TOKEN_ACCOUNT_MINT_OFFSET)?,
+            self.lst_mint.address(),
+        ) || !address_eq(
+            &read_address(&vault, TOKEN_ACCOUNT_OWNER_OFFSET)?,
+            self.pool.address(),
+        ) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((
+            read_u64(&vault, TOKEN_ACCOUNT_AMOUNT_OFFSET)?,
+            read_u64(&self.rst_mint.try_borrow()?, MINT_SUPPLY_OFFSET)?,
+        ))
+    }
+}
+
+impl<'info> Preview<'info> for SolayerRestaking {
+    type Accounts = SolayerRestakeAccounts<'info>;
+
+    /// Returns the restaked tokens expected for restaking `amount`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Restaked tokens at the pool's vault backing, rounded down
+    /// * `Err(ProgramError)` - Pool accounts are invalid (see `pool_backing`)
+    fn preview_deposit(
+        ctx: &SolayerRestakeAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let (vault_amount, rst_supply) = ctx.pool_backing()?;
+        lst_to_rst(amount, vault_amount, rst_supply)
+    }
+}
+
+impl<'info> Capacity<'info> for SolayerRestaking {
+    type Accounts = SolayerRestakeAccounts<'info>;
+
+    /// Checks that restaking `amount` fits in the pool's vault and restaked token supply.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The deposit fits
+    /// * `Err(BeethovenError::DepositCapExceeded)` - `amount` exceeds the remaining capacity
+    /// * `Err(ProgramError)` - Pool accounts are invalid
+    fn check_capacity(ctx: &SolayerRestakeAccounts<'info>, amount: u64) -> ProgramResult {
+        if amount > Self::available_deposit_capacity(ctx)? {
+            return Err(BeethovenError::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the LST the pool can still accept before its vault balance or the restaked
+    /// token supply overflows.
+    ///
+    /// # Notes
+    /// * Solayer restaking pools carry no configured deposit cap.
+    fn available_deposit_capacity(
+        ctx: &SolayerRestakeAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        let (vault_amount, rst_supply) = ctx.pool_backing()?;
+        restake_capacity(vault_amount, rst_supply)
+    }
+    /// Returns the LST balance of the pool's vault, which unrestakes are paid from.
+    fn available_liquidity_for_withdraw(
+        ctx: &SolayerRestakeAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        read_u64(&ctx.vault.try_borrow()?, TOKEN_ACCOUNT_AMOUNT_OFFSET)
+    }
+
+    /// Always `true`: Solayer restaking pools expose no pause flag.
+    fn is_operational(_ctx: &SolayerRestakeAccounts<'info>) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test_utils::*, std::vec::Vec};
+
+    fn pool_data(lst_mint: &Address, rst_mint: &Address) -> Vec<u8> {
+        let mut data = account_data(&RESTAKING_POOL_DISCRIMINATOR, 73);
+        data[8..40].copy_from_slice(lst_mint.as_ref());
+        data[40..72].copy_from_slice(rst_mint.as_ref());
+        data
+    }
+
+    fn token_account(mint: &Address, owner: &Address, amount: u64) -> Vec<u8> {
+        let mut data = std::vec![0u8; 165];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data
+    }
+
+    fn mint(supply: u64) -> Vec<u8> {
+        let mut data = std::vec![0u8; 82];
+        data[36..44].copy_from_slice(&supply.to_le_bytes());
+        data
+    }
+
+    fn restake_accounts(pool_owner: Address, rst_supply: u64) -> Vec<TestAccount> {
+        let (lst_mint, rst_mint, pool) = (address(2), address(5), address(7));
+        std::vec![
+            TestAccount::empty(SOLAYER_RESTAKING_PROGRAM_ID),
+            TestAccount::empty(address(1)).signer(),
+            TestAccount::empty(lst_mint.clone()),
+            TestAccount::empty(address(3)),
+            TestAccount::empty(address(4)),
+            TestAccount::new(rst_mint.clone(), Address::default(), &mint(rst_supply)),
+            TestAccount::new(
+                address(6),
+                Address::default(),
+                &token_account(&lst_mint, &pool, 9_000),
+            ),
+            TestAccount::new(pool, pool_owner, &pool_data(&lst_mint, &rst_mint)),
+            TestAccount::empty(address(8)),
+            TestAccount::empty(address(9)),
+            TestAccount::empty(address(10)),
+        ]
+    }
+
+    #[test]
+    fn preview_uses_the_vault_backing() {
+        let mut accounts = restake_accounts(SOLAYER_RESTAKING_PROGRAM_ID, 10_000);
+        let views = views(&mut accounts);
+        let ctx = SolayerRestakeAccounts::try_from(views.as_slice()).unwrap();
+
+        assert_eq!(ctx.pool_backing(), Ok((9_000, 10_000)));
+        assert_eq!(SolayerRestaking::preview_deposit(&ctx, 900), Ok(1_000));
+        assert_eq!(
+            SolayerRestaking::check_capacity(&ctx, u64::MAX),
+            Err(BeethovenError::DepositCapExceeded.into())
+        );
+    }
+
+    #[test]
+    fn pool_backing_rejects_foreign_pools() {
+        let mut accounts = restake_accounts(address(99), 10_000);
+        let views = views(&mut accounts);
+        let ctx = SolayerRestakeAccounts::try_from(views.as_slice()).unwrap();
+        assert_eq!(ctx.pool_backing(), Err(ProgramError::IllegalOwner));
+    }
+
+    #[test]
+    fn pool_backing_rejects_mismatched_mints() {
+        let mut accounts = restake_accounts(SOLAYER_RESTAKING_PROGRAM_ID, 10_000);
+        accounts[5] = TestAccount::new(address(42), Address::default(), &mint(10_000));
+        let views = views(&mut accounts);
+        let ctx = SolayerRestakeAccounts::try_from(views.as_slice()).unwrap();
+        assert_eq!(ctx.pool_backing(), Err(ProgramError::InvalidAccountData));
+    }
+}
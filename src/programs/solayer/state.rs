@@ -0,0 +1,147 @@
+use {
+    crate::util::{Rounding, bytes::*, mul_div, mul_div_u64, to_u64},
+    pinocchio::{Address, error::ProgramError},
+};
+
+pub(crate) const RESTAKING_POOL_DISCRIMINATOR: [u8; 8] = [12, 5, 100, 143, 125, 94, 26, 214];
+
+// Byte offsets into Solayer's borsh `RestakingPool` account (discriminator included).
+const POOL_LST_MINT_OFFSET: usize = 8;
+const POOL_RST_MINT_OFFSET: usize = 40;
+
+/// Subset of Solayer's `RestakingPool` account needed to validate a restake.
+pub struct SolayerRestakingPoolState {
+    /// Mint of the LST the pool accepts
+    pub lst_mint: Address,
+    /// Mint of the restaked token the pool issues
+    pub rst_mint: Address,
+}
+
+impl SolayerRestakingPoolState {
+    /// Parses the relevant fields out of raw `RestakingPool` account data.
+    ///
+    /// # Returns
+    /// * `Ok(SolayerRestakingPoolState)` - Parsed state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a Solayer `RestakingPool`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        check_discriminator(data, &RESTAKING_POOL_DISCRIMINATOR)?;
+
+        Ok(Self {
+            lst_mint: read_address(data, POOL_LST_MINT_OFFSET)?,
+            rst_mint: read_address(data, POOL_RST_MINT_OFFSET)?,
+        })
+    }
+}
+
+/// Restaked tokens backed by restaking `amount`, rounded down.
+///
+/// The pool stores no exchange rate: each restaked token is backed by the pool's vault, so
+/// the rate is the vault's LST balance against the restaked token supply.
+///
+/// # Arguments
+/// * `amount` - LST to restake
+/// * `vault_amount` - LST held by the pool's vault
+/// * `rst_supply` - Supply of the restaked token mint
+///
+/// # Returns
+/// * `Ok(u64)` - Restaked tokens, 1:1 while the pool is empty
+/// * `Err(ProgramError::InvalidAccountData)` - Restaked tokens are outstanding against an
+///   empty vault
+/// * `Err(ProgramError::ArithmeticOverflow)` - The result does not fit in a `u64`
+pub fn lst_to_rst(amount: u64, vault_amount: u64, rst_supply: u64) -> Result<u64, ProgramError> {
+    match (vault_amount, rst_supply) {
+        (_, 0) => Ok(amount),
+        (0, _) => Err(ProgramError::InvalidAccountData),
+        _ => mul_div_u64(amount, rst_supply, vault_amount, Rounding::Down),
+    }
+}
+
+/// LST that can still be restaked before the vault balance or the restaked token supply
+/// overflows a `u64`, rounded down.
+///
+/// # Returns
+/// * `Ok(u64)` - Remaining capacity, in LST
+/// * `Err(ProgramError::ArithmeticOverflow)` - Intermediate overflow
+pub fn restake_capacity(vault_amount: u64, rst_supply: u64) -> Result<u64, ProgramError> {
+    let vault_headroom = u64::MAX - vault_amount;
+    let supply_headroom = u64::MAX - rst_supply;
+    if rst_supply == 0 {
+        return Ok(vault_headroom.min(supply_headroom));
+    }
+
+    let lst_for_supply = mul_div(
+        supply_headroom as u128,
+        vault_amount as u128,
+        rst_supply as u128,
+        Rounding::Down,
+    )?;
+    to_u64(lst_for_supply.min(vault_headroom as u128))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test_utils::*};
+
+    fn pool_data(lst_mint: &Address, rst_mint: &Address) -> std::vec::Vec<u8> {
+        let mut data = account_data(&RESTAKING_POOL_DISCRIMINATOR, 73);
+        data[POOL_LST_MINT_OFFSET..POOL_LST_MINT_OFFSET + 32].copy_from_slice(lst_mint.as_ref());
+        data[POOL_RST_MINT_OFFSET..POOL_RST_MINT_OFFSET + 32].copy_from_slice(rst_mint.as_ref());
+        data
+    }
+
+    #[test]
+    fn from_bytes_reads_the_pool_mints() {
+        let state =
+            SolayerRestakingPoolState::from_bytes(&pool_data(&address(1), &address(2))).unwrap();
+        assert_eq!(state.lst_mint, address(1));
+        assert_eq!(state.rst_mint, address(2));
+    }
+
+    #[test]
+    fn from_bytes_rejects_other_accounts() {
+        let mut data = pool_data(&address(1), &address(2));
+        data[0] ^= 1;
+        assert!(matches!(
+            SolayerRestakingPoolState::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        ));
+        assert!(matches!(
+            SolayerRestakingPoolState::from_bytes(&pool_data(&address(1), &address(2))[..40]),
+            Err(ProgramError::AccountDataTooSmall)
+        ));
+    }
+
+    #[test]
+    fn lst_to_rst_follows_the_vault_backing() {
+        assert_eq!(lst_to_rst(1_000, 0, 0), Ok(1_000));
+        assert_eq!(lst_to_rst(1_000, 5_000, 5_000), Ok(1_000));
+        // Vault slashed by 10%: each restaked token is backed by 0.9 LST.
+        assert_eq!(lst_to_rst(1_000, 4_500, 5_000), Ok(1_111));
+        assert_eq!(
+            lst_to_rst(1_000, 0, 5_000),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn restake_capacity_is_bounded_by_vault_and_supply() {
+        assert_eq!(restake_capacity(0, 0), Ok(u64::MAX));
+        assert_eq!(restake_capacity(u64::MAX - 10, u64::MAX - 10), Ok(10));
+        // Supply has 11 tokens of headroom, each backed by 0.5 LST.
+        let rst_supply = u64::MAX - 11;
+        assert_eq!(restake_capacity(rst_supply / 2, rst_supply), Ok(5));
+    }
+
+    #[test]
+    fn restaked_tokens_are_never_backed_by_more_than_the_deposit() {
+        let mut rng = Rng::new(13);
+        for _ in 0..PROPERTY_CASES {
+            let (x, vault_amount, rst_supply) = (rng.amount(), rng.amount(), rng.amount());
+
+            if let (Ok(rst), true) = (lst_to_rst(x, vault_amount, rst_supply), rst_supply > 0) {
+                assert!(rst as u128 * vault_amount as u128 <= x as u128 * rst_supply as u128);
+            }
+        }
+    }
+}
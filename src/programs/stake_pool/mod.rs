@@ -0,0 +1,321 @@
+mod state;
+pub use state::*;
+
+mod withdraw_stake;
+pub use withdraw_stake::*;
+use {
+    crate::{
+        AccountCheck, AccountError, AccountSpec, Capacity, Deposit, Preview,
+        util::cpi::invoke_signed_with_remaining,
+    },
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+pub const SPL_STAKE_POOL_PROGRAM_ID: Address = Address::new_from_array([
+    6, 129, 78, 212, 202, 246, 138, 23, 70, 114, 253, 172, 134, 3, 26, 99, 232, 78, 161, 94, 250,
+    29, 68, 183, 34, 147, 246, 219, 219, 0, 22, 80,
+]);
+const DEPOSIT_SOL_TAG: u8 = 14;
+
+/// Account order expected by `SplStakePoolDepositSolAccounts::try_from`.
+pub const SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("stake_pool_program"),
+    AccountSpec::writable("stake_pool"),
+    AccountSpec::readonly("withdraw_authority"),
+    AccountSpec::writable("reserve_stake"),
+    AccountSpec::writable_signer("lamports_from"),
+    AccountSpec::writable("pool_tokens_to"),
+    AccountSpec::writable_aliasable("manager_fee_account"),
+    AccountSpec::writable_aliasable("referrer_pool_tokens_account"),
+    AccountSpec::writable("pool_mint"),
+    AccountSpec::readonly("system_program"),
+    AccountSpec::readonly("token_program"),
+];
+
+/// SPL stake pool integration (generic pools such as JitoSOL or bSOL)
+pub struct SplStakePool;
+
+/// Account context for the SPL stake pool's DepositSol instruction.
+///
+/// Deposits SOL into the pool's reserve stake and mints pool tokens.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 11 accounts are present.
+pub struct SplStakePoolDepositSolAccounts<'info> {
+    /// SPL Stake Pool Program
+    pub stake_pool_program: &'info AccountView,
+    /// Stake pool (writable)
+    pub stake_pool: &'info AccountView,
+    /// Stake pool withdraw authority PDA
+    pub withdraw_authority: &'info AccountView,
+    /// Pool reserve stake account (writable)
+    pub reserve_stake: &'info AccountView,
+    /// SOL source (mutable, signer)
+    pub lamports_from: &'info AccountView,
+    /// Pool token account receiving the minted tokens (writable)
+    pub pool_tokens_to: &'info AccountView,
+    /// Pool token account receiving the manager's deposit fee (writable)
+    pub manager_fee_account: &'info AccountView,
+    /// Pool token account receiving the referral fee (writable)
+    pub referrer_pool_tokens_account: &'info AccountView,
+    /// Pool token mint (writable)
+    pub pool_mint: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI (e.g. the pool's SOL deposit authority)
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for SplStakePoolDepositSolAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `SplStakePoolDepositSolAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(SplStakePoolDepositSolAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 11 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `SPL_STAKE_POOL_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &SPL_STAKE_POOL_PROGRAM_ID)
+    }
+}
+
+impl<'info> SplStakePoolDepositSolAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            stake_pool_program,
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            lamports_from,
+            pool_tokens_to,
+            manager_fee_account,
+            referrer_pool_tokens_account,
+            pool_mint,
+            system_program,
+            token_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(
+                AccountError::missing(SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS, accounts.len()).into(),
+            );
+        };
+
+        if !address_eq(stake_pool_program.address(), program_id) {
+            return Err(AccountError::new(
+                SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            stake_pool_program,
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            lamports_from,
+            pool_tokens_to,
+            manager_fee_account,
+            referrer_pool_tokens_account,
+            pool_mint,
+            system_program,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+impl<'info> Deposit<'info> for SplStakePool {
+    type Accounts = SplStakePoolDepositSolAccounts<'info>;
+
+    /// Deposits `amount` lamports into the stake pool via CPI, receiving pool tokens.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `SplStakePoolDepositSolAccounts`)
+    /// * `amount` - Lamports to deposit
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    fn deposit_signed(
+        ctx: &SplStakePoolDepositSolAccounts<'info>,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(ctx.stake_pool.address()),
+            InstructionAccount::readonly(ctx.withdraw_authority.address()),
+            InstructionAccount::writable(ctx.reserve_stake.address()),
+            InstructionAccount::writable_signer(ctx.lamports_from.address()),
+            InstructionAccount::writable(ctx.pool_tokens_to.address()),
+            InstructionAccount::writable(ctx.manager_fee_account.address()),
+            InstructionAccount::writable(ctx.referrer_pool_tokens_account.address()),
+            InstructionAccount::writable(ctx.pool_mint.address()),
+            InstructionAccount::readonly(ctx.system_program.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.stake_pool,
+            ctx.withdraw_authority,
+            ctx.reserve_stake,
+            ctx.lamports_from,
+            ctx.pool_tokens_to,
+            ctx.manager_fee_account,
+            ctx.referrer_pool_tokens_account,
+            ctx.pool_mint,
+            ctx.system_program,
+            ctx.token_program,
+        ];
+
+        // Build instruction data: tag (1 byte) + lamports (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 9]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            *ptr = DEPOSIT_SOL_TAG;
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(1), 8);
+        }
+
+        let deposit_ix = InstructionView {
+            program_id: ctx.stake_pool_program.address(),
+            accounts: &accounts,
+            data: unsafe { core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 9) },
+        };
+
+        invoke_signed_with_remaining(&deposit_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    fn deposit(ctx: &SplStakePoolDepositSolAccounts<'info>, amount: u64) -> ProgramResult {
+        Self::deposit_signed(ctx, amount, &[])
+    }
+}
+
+impl<'info> Preview<'info> for SplStakePool {
+    type Accounts = SplStakePoolDepositSolAccounts<'info>;
+
+    /// Returns the pool tokens expected for depositing `amount` lamports.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Expected pool tokens, before the pool's SOL deposit and referral fees
+    /// * `Err(ProgramError::IllegalOwner)` - Stake pool is not owned by the stake pool program
+    /// * `Err(ProgramError)` - Stake pool data could not be read
+    fn preview_deposit(
+        ctx: &SplStakePoolDepositSolAccounts<'info>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.stake_pool.owned_by(ctx.stake_pool_program.address()) {
+            return Err(AccountError::of(
+                SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS,
+                "stake_pool",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.stake_pool.try_borrow()?;
+        SplStakePoolState::from_bytes(&data)?.lamports_to_pool_tokens(amount)
+    }
+}
+
+impl<'info> Capacity<'info> for SplStakePool {
+    type Accounts = SplStakePoolDepositSolAccounts<'info>;
+
+    /// Always succeeds: SPL stake pools carry no deposit cap.
+    fn check_capacity(_ctx: &SplStakePoolDepositSolAccounts<'info>, _amount: u64) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Always `u64::MAX`: SPL stake pools carry no deposit cap.
+    fn available_deposit_capacity(
+        _ctx: &SplStakePoolDepositSolAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        Ok(u64::MAX)
+    }
+
+    /// Returns the lamports held by the pool's reserve stake, which SOL withdrawals are paid
+    /// from.
+    ///
+    /// # Notes
+    /// * Stake withdrawals (`SplStakePool::withdraw_stake`) split validator stake instead and
+    ///   are not bounded by the reserve.
+    fn available_liquidity_for_withdraw(
+        ctx: &SplStakePoolDepositSolAccounts<'info>,
+    ) -> Result<u64, ProgramError> {
+        Ok(ctx.reserve_stake.lamports())
+    }
+
+    /// Always `true`: SPL stake pools expose no pause flag.
+    fn is_operational(_ctx: &SplStakePoolDepositSolAccounts<'info>) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{state::tests::stake_pool_data, *},
+        crate::test_utils::*,
+        std::vec::Vec,
+    };
+
+    fn deposit_accounts(stake_pool_owner: Address) -> Vec<TestAccount> {
+        (0..SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS.len() as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(SPL_STAKE_POOL_PROGRAM_ID),
+                1 => TestAccount::new(
+                    address(i),
+                    stake_pool_owner.clone(),
+                    &stake_pool_data(1_100, 1_000),
+                ),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn previews_pool_tokens_at_the_exchange_rate() {
+        let mut accounts = deposit_accounts(SPL_STAKE_POOL_PROGRAM_ID);
+        let accounts = views(&mut accounts);
+        let ctx = SplStakePoolDepositSolAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(SplStakePool::preview_deposit(&ctx, 1_100), Ok(1_000));
+    }
+
+    #[test]
+    fn preview_rejects_pools_of_another_program() {
+        let mut accounts = deposit_accounts(address(42));
+        let accounts = views(&mut accounts);
+        let ctx = SplStakePoolDepositSolAccounts::try_from(&accounts[..]).unwrap();
+        assert_eq!(
+            SplStakePool::preview_deposit(&ctx, 1_100),
+            Err(ProgramError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn parse_checks_the_program_and_account_count() {
+        let mut accounts = deposit_accounts(SPL_STAKE_POOL_PROGRAM_ID);
+        let views = views(&mut accounts);
+        assert!(matches!(
+            SplStakePoolDepositSolAccounts::try_from(&views[..10]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+        assert!(matches!(
+            SplStakePoolDepositSolAccounts::try_from_with_program_id(&views, &address(42)),
+            Err(ProgramError::IncorrectProgramId)
+        ));
+    }
+}
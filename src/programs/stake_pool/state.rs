@@ -0,0 +1,153 @@
+use {
+    crate::util::{Rounding, bytes::*, mul_div_u64},
+    pinocchio::error::ProgramError,
+};
+
+/// `AccountType::StakePool` tag leading every stake pool account.
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+// Byte offsets into the borsh `StakePool` account.
+const STAKE_POOL_TOTAL_LAMPORTS_OFFSET: usize = 258;
+const STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+
+/// Subset of an SPL stake pool's `StakePool` account needed to value deposits and
+/// withdrawals.
+pub struct SplStakePoolState {
+    /// Lamports under management as of the last epoch update
+    pub total_lamports: u64,
+    /// Pool token supply as of the last epoch update
+    pub pool_token_supply: u64,
+}
+
+impl SplStakePoolState {
+    /// Parses the relevant fields out of raw `StakePool` account data.
+    ///
+    /// # Returns
+    /// * `Ok(SplStakePoolState)` - Parsed pool state
+    /// * `Err(ProgramError::InvalidAccountData)` - Data is not a `StakePool`
+    /// * `Err(ProgramError::AccountDataTooSmall)` - Data is too short
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.first() != Some(&STAKE_POOL_ACCOUNT_TYPE) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            total_lamports: read_u64(data, STAKE_POOL_TOTAL_LAMPORTS_OFFSET)?,
+            pool_token_supply: read_u64(data, STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET)?,
+        })
+    }
+
+    /// Pool tokens minted for depositing `lamports`, rounded down and before fees.
+    ///
+    /// Mirrors the stake pool's exchange rate: 1:1 for an empty pool, otherwise
+    /// `pool_token_supply / total_lamports`.
+    pub fn lamports_to_pool_tokens(&self, lamports: u64) -> Result<u64, ProgramError> {
+        if self.total_lamports == 0 || self.pool_token_supply == 0 {
+            return Ok(lamports);
+        }
+
+        mul_div_u64(
+            lamports,
+            self.pool_token_supply,
+            self.total_lamports,
+            Rounding::Down,
+        )
+    }
+
+    /// Lamports of stake split off for burning `pool_tokens`, rounded down and before fees.
+    pub fn pool_tokens_to_lamports(&self, pool_tokens: u64) -> Result<u64, ProgramError> {
+        if self.pool_token_supply == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        mul_div_u64(
+            pool_tokens,
+            self.total_lamports,
+            self.pool_token_supply,
+            Rounding::Down,
+        )
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use {
+        super::*,
+        crate::test_utils::{PROPERTY_CASES, Rng},
+        std::{vec, vec::Vec},
+    };
+
+    /// `StakePool` bytes managing `total_lamports` for `pool_token_supply` pool tokens.
+    pub(crate) fn stake_pool_data(total_lamports: u64, pool_token_supply: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 611];
+        data[0] = STAKE_POOL_ACCOUNT_TYPE;
+        data[STAKE_POOL_TOTAL_LAMPORTS_OFFSET..STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET]
+            .copy_from_slice(&total_lamports.to_le_bytes());
+        data[STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET..STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET + 8]
+            .copy_from_slice(&pool_token_supply.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn converts_at_the_pool_exchange_rate() {
+        // 1.1 SOL per pool token.
+        let pool = SplStakePoolState::from_bytes(&stake_pool_data(1_100, 1_000)).unwrap();
+        assert_eq!(
+            pool.lamports_to_pool_tokens(1_100_000_000),
+            Ok(1_000_000_000)
+        );
+        assert_eq!(
+            pool.pool_tokens_to_lamports(1_000_000_000),
+            Ok(1_100_000_000)
+        );
+        // Both round down.
+        assert_eq!(pool.lamports_to_pool_tokens(10), Ok(9));
+        assert_eq!(pool.pool_tokens_to_lamports(9), Ok(9));
+    }
+
+    #[test]
+    fn empty_pools_mint_one_to_one_and_cannot_be_withdrawn_from() {
+        let empty = SplStakePoolState::from_bytes(&stake_pool_data(0, 0)).unwrap();
+        assert_eq!(empty.lamports_to_pool_tokens(5), Ok(5));
+        assert_eq!(
+            empty.pool_tokens_to_lamports(5),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn rejects_other_account_types_and_short_data() {
+        let mut data = stake_pool_data(1, 1);
+        assert!(matches!(
+            SplStakePoolState::from_bytes(&data[..270]),
+            Err(ProgramError::AccountDataTooSmall)
+        ));
+        // `AccountType::ValidatorList`
+        data[0] = 2;
+        assert!(matches!(
+            SplStakePoolState::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        ));
+    }
+
+    #[test]
+    fn round_trips_never_pay_out_more_than_was_paid_in() {
+        let mut rng = Rng::new(11);
+        for _ in 0..PROPERTY_CASES {
+            let data = stake_pool_data(rng.amount(), rng.amount());
+            let pool = SplStakePoolState::from_bytes(&data).unwrap();
+            let (x, s) = (rng.amount(), rng.amount());
+
+            // An empty pool mints 1:1 but has no supply to withdraw against.
+            if let Ok(pool_tokens) = pool.lamports_to_pool_tokens(x)
+                && let Ok(lamports) = pool.pool_tokens_to_lamports(pool_tokens)
+            {
+                assert!(lamports <= x);
+            }
+            if let Ok(lamports) = pool.pool_tokens_to_lamports(s) {
+                let pool_tokens = pool.lamports_to_pool_tokens(lamports);
+                assert!(pool_tokens.is_ok_and(|pool_tokens| pool_tokens <= s));
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use {
+    super::{SPL_STAKE_POOL_PROGRAM_ID, SplStakePool, SplStakePoolState},
+    crate::{AccountCheck, AccountError, AccountSpec, util::cpi::invoke_signed_with_remaining},
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::Signer,
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+const WITHDRAW_STAKE_TAG: u8 = 10;
+
+/// Account order expected by `SplStakePoolWithdrawStakeAccounts::try_from`.
+pub const SPL_STAKE_POOL_WITHDRAW_STAKE_ACCOUNTS: &[AccountSpec] = &[
+    AccountSpec::readonly("stake_pool_program"),
+    AccountSpec::writable("stake_pool"),
+    AccountSpec::writable("validator_list"),
+    AccountSpec::readonly("withdraw_authority"),
+    AccountSpec::writable("stake_to_split"),
+    AccountSpec::writable("stake_to_receive"),
+    AccountSpec::readonly("user_stake_authority"),
+    AccountSpec::readonly_signer("user_transfer_authority"),
+    AccountSpec::writable("user_pool_token_account"),
+    AccountSpec::writable("manager_fee_account"),
+    AccountSpec::writable("pool_mint"),
+    AccountSpec::readonly("clock"),
+    AccountSpec::readonly("token_program"),
+    AccountSpec::readonly("stake_program"),
+];
+
+/// Account context for the SPL stake pool's WithdrawStake instruction.
+///
+/// Burns pool tokens and splits the matching stake off a validator stake account (or the
+/// reserve, when no validator has any) into `stake_to_receive`. Unlike a SOL withdrawal,
+/// the result is an activated stake account delegated to the same validator, which
+/// `user_stake_authority` then controls.
+///
+/// # Account Order
+/// Accounts must be provided in the exact order listed below. The TryFrom implementation
+/// will validate that at least 14 accounts are present.
+pub struct SplStakePoolWithdrawStakeAccounts<'info> {
+    /// SPL Stake Pool Program
+    pub stake_pool_program: &'info AccountView,
+    /// Stake pool (writable)
+    pub stake_pool: &'info AccountView,
+    /// Pool validator list (writable)
+    pub validator_list: &'info AccountView,
+    /// Stake pool withdraw authority PDA
+    pub withdraw_authority: &'info AccountView,
+    /// Validator or reserve stake account to split from (writable)
+    pub stake_to_split: &'info AccountView,
+    /// Uninitialized stake account owned by the stake program, receiving the split (writable)
+    pub stake_to_receive: &'info AccountView,
+    /// Staker and withdrawer authority set on the new stake account
+    pub user_stake_authority: &'info AccountView,
+    /// Owner or delegate of `user_pool_token_account` (signer)
+    pub user_transfer_authority: &'info AccountView,
+    /// Pool token account to burn from (writable)
+    pub user_pool_token_account: &'info AccountView,
+    /// Pool token account receiving the withdrawal fee (writable)
+    pub manager_fee_account: &'info AccountView,
+    /// Pool token mint (writable)
+    pub pool_mint: &'info AccountView,
+    /// Clock sysvar
+    pub clock: &'info AccountView,
+    /// Token program
+    pub token_program: &'info AccountView,
+    /// Stake program
+    pub stake_program: &'info AccountView,
+    /// Trailing accounts forwarded as-is to the CPI
+    pub remaining: &'info [AccountView],
+}
+
+impl<'info> TryFrom<&'info [AccountView]> for SplStakePoolWithdrawStakeAccounts<'info> {
+    type Error = ProgramError;
+
+    /// Converts a slice of `AccountView` into validated `SplStakePoolWithdrawStakeAccounts`.
+    ///
+    /// # Returns
+    /// * `Ok(SplStakePoolWithdrawStakeAccounts)` - Successfully parsed account context
+    /// * `Err(ProgramError::NotEnoughAccountKeys)` - Fewer than 14 accounts provided
+    /// * `Err(ProgramError::IncorrectProgramId)` - First account is not `SPL_STAKE_POOL_PROGRAM_ID`
+    fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
+        Self::try_from_with_program_id(accounts, &SPL_STAKE_POOL_PROGRAM_ID)
+    }
+}
+
+impl<'info> SplStakePoolWithdrawStakeAccounts<'info> {
+    /// Same as `try_from`, but validates the leading program account against `program_id`.
+    pub fn try_from_with_program_id(
+        accounts: &'info [AccountView],
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        let [
+            stake_pool_program,
+            stake_pool,
+            validator_list,
+            withdraw_authority,
+            stake_to_split,
+            stake_to_receive,
+            user_stake_authority,
+            user_transfer_authority,
+            user_pool_token_account,
+            manager_fee_account,
+            pool_mint,
+            clock,
+            token_program,
+            stake_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(AccountError::missing(
+                SPL_STAKE_POOL_WITHDRAW_STAKE_ACCOUNTS,
+                accounts.len(),
+            )
+            .into());
+        };
+
+        if !address_eq(stake_pool_program.address(), program_id) {
+            return Err(AccountError::new(
+                SPL_STAKE_POOL_WITHDRAW_STAKE_ACCOUNTS,
+                0,
+                AccountCheck::ProgramId,
+            )
+            .into());
+        }
+
+        Ok(Self {
+            stake_pool_program,
+            stake_pool,
+            validator_list,
+            withdraw_authority,
+            stake_to_split,
+            stake_to_receive,
+            user_stake_authority,
+            user_transfer_authority,
+            user_pool_token_account,
+            manager_fee_account,
+            pool_mint,
+            clock,
+            token_program,
+            stake_program,
+            remaining,
+        })
+    }
+}
+
+impl SplStakePool {
+    /// Burns `pool_tokens` and withdraws the matching stake as a new activated stake account.
+    ///
+    /// # Arguments
+    /// * `ctx` - Account context (see `SplStakePoolWithdrawStakeAccounts`)
+    /// * `pool_tokens` - Pool tokens to burn
+    /// * `signer_seeds` - Optional PDA signer seeds for CPI with signing
+    ///
+    /// # Returns
+    /// * `Ok(())` - Stake split into `ctx.stake_to_receive`
+    /// * `Err(ProgramError)` - CPI failure
+    ///
+    /// # Notes
+    /// * `stake_to_receive` must already exist: allocated with the stake account size and
+    ///   assigned to the stake program, but not initialized.
+    pub fn withdraw_stake_signed(
+        ctx: &SplStakePoolWithdrawStakeAccounts,
+        pool_tokens: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(ctx.stake_pool.address()),
+            InstructionAccount::writable(ctx.validator_list.address()),
+            InstructionAccount::readonly(ctx.withdraw_authority.address()),
+            InstructionAccount::writable(ctx.stake_to_split.address()),
+            InstructionAccount::writable(ctx.stake_to_receive.address()),
+            InstructionAccount::readonly(ctx.user_stake_authority.address()),
+            InstructionAccount::readonly_signer(ctx.user_transfer_authority.address()),
+            InstructionAccount::writable(ctx.user_pool_token_account.address()),
+            InstructionAccount::writable(ctx.manager_fee_account.address()),
+            InstructionAccount::writable(ctx.pool_mint.address()),
+            InstructionAccount::readonly(ctx.clock.address()),
+            InstructionAccount::readonly(ctx.token_program.address()),
+            InstructionAccount::readonly(ctx.stake_program.address()),
+        ];
+
+        let account_infos = [
+            ctx.stake_pool,
+            ctx.validator_list,
+            ctx.withdraw_authority,
+            ctx.stake_to_split,
+            ctx.stake_to_receive,
+            ctx.user_stake_authority,
+            ctx.user_transfer_authority,
+            ctx.user_pool_token_account,
+            ctx.manager_fee_account,
+            ctx.pool_mint,
+            ctx.clock,
+            ctx.token_program,
+            ctx.stake_program,
+        ];
+
+        // Build instruction data: tag (1 byte) + pool tokens (8 bytes)
+        let mut instruction_data = MaybeUninit::<[u8; 9]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            *ptr = WITHDRAW_STAKE_TAG;
+            core::ptr::copy_nonoverlapping(pool_tokens.to_le_bytes().as_ptr(), ptr.add(1), 8);
+        }
+
+        let withdraw_ix = InstructionView {
+            program_id: ctx.stake_pool_program.address(),
+            accounts: &accounts,
+            data: unsafe { core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 9) },
+        };
+
+        invoke_signed_with_remaining(&withdraw_ix, &account_infos, ctx.remaining, signer_seeds)
+    }
+
+    /// Same as `withdraw_stake_signed`, without PDA signing.
+    pub fn withdraw_stake(
+        ctx: &SplStakePoolWithdrawStakeAccounts,
+        pool_tokens: u64,
+    ) -> ProgramResult {
+        Self::withdraw_stake_signed(ctx, pool_tokens, &[])
+    }
+
+    /// Returns the lamports of stake expected for burning `pool_tokens`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Expected stake, before the pool's stake withdrawal fee
+    /// * `Err(ProgramError::IllegalOwner)` - Stake pool is not owned by the stake pool program
+    /// * `Err(ProgramError)` - Stake pool data could not be read
+    pub fn preview_withdraw_stake(
+        ctx: &SplStakePoolWithdrawStakeAccounts,
+        pool_tokens: u64,
+    ) -> Result<u64, ProgramError> {
+        if !ctx.stake_pool.owned_by(ctx.stake_pool_program.address()) {
+            return Err(AccountError::of(
+                SPL_STAKE_POOL_WITHDRAW_STAKE_ACCOUNTS,
+                "stake_pool",
+                AccountCheck::Owner,
+            )
+            .into());
+        }
+
+        let data = ctx.stake_pool.try_borrow()?;
+        SplStakePoolState::from_bytes(&data)?.pool_tokens_to_lamports(pool_tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{super::state::tests::stake_pool_data, *},
+        crate::{programs::stake_pool::SPL_STAKE_POOL_PROGRAM_ID, test_utils::*},
+        std::vec::Vec,
+    };
+
+    #[test]
+    fn previews_the_stake_split_off() {
+        let mut accounts: Vec<TestAccount> = (0..SPL_STAKE_POOL_WITHDRAW_STAKE_ACCOUNTS.len()
+            as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(SPL_STAKE_POOL_PROGRAM_ID),
+                1 => TestAccount::new(
+                    address(i),
+                    SPL_STAKE_POOL_PROGRAM_ID,
+                    &stake_pool_data(1_100, 1_000),
+                ),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect();
+        let accounts = views(&mut accounts);
+        let ctx = SplStakePoolWithdrawStakeAccounts::try_from(&accounts[..]).unwrap();
+
+        assert_eq!(SplStakePool::preview_withdraw_stake(&ctx, 1_000), Ok(1_100));
+        assert!(matches!(
+            SplStakePoolWithdrawStakeAccounts::try_from(&accounts[..13]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! Off-chain account fixtures for unit tests.
+
+use {
+    core::{mem::size_of, time::Duration},
+    pinocchio::{
+        AccountView, Address,
+        account::{NOT_BORROWED, RuntimeAccount},
+    },
+    std::{vec, vec::Vec},
+};
+
+/// Iterations each `#[ignore]`d benchmark times (`make bench`).
+pub const BENCH_ITERATIONS: u32 = 100_000;
+
+/// Random cases each property test checks.
+pub const PROPERTY_CASES: u32 = 10_000;
+
+/// Seeded SplitMix64 generator for property tests, so failures reproduce.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns an amount spread over every magnitude, from dust to `u64::MAX`.
+    pub fn amount(&mut self) -> u64 {
+        let shift = self.next_u64() % 64;
+        self.next_u64() >> shift
+    }
+}
+
+/// Backing memory for an `AccountView`: a `RuntimeAccount` header followed by its data,
+/// laid out the way the runtime serializes accounts.
+pub struct TestAccount {
+    buffer: Vec<u64>,
+}
+
+impl TestAccount {
+    /// Creates a read-only, non-signer account holding `data`.
+    pub fn new(address: Address, owner: Address, data: &[u8]) -> Self {
+        let header = size_of::<RuntimeAccount>();
+        let mut buffer = vec![0u64; (header + data.len()).div_ceil(8)];
+        let raw = buffer.as_mut_ptr() as *mut RuntimeAccount;
+        // SAFETY: `buffer` is u64-aligned and large enough for the header and `data`.
+        unsafe {
+            raw.write(RuntimeAccount {
+                borrow_state: NOT_BORROWED,
+                is_signer: 0,
+                is_writable: 0,
+                executable: 0,
+                resize_delta: 0,
+                address,
+                owner,
+                lamports: 0,
+                data_len: data.len() as u64,
+            });
+            core::ptr::copy_nonoverlapping(data.as_ptr(), (raw as *mut u8).add(header), data.len());
+        }
+        Self { buffer }
+    }
+
+    /// Creates an account with no data, as wallets and sysvar placeholders are.
+    pub fn empty(address: Address) -> Self {
+        Self::new(address, Address::default(), &[])
+    }
+
+    /// Marks the account as a transaction signer.
+    pub fn signer(mut self) -> Self {
+        self.header().is_signer = 1;
+        self
+    }
+
+    /// Marks the account as an executable program.
+    pub fn executable(mut self) -> Self {
+        self.header().executable = 1;
+        self
+    }
+
+    /// Returns a view over the account.
+    pub fn view(&mut self) -> AccountView {
+        // SAFETY: The buffer starts with a valid `RuntimeAccount` followed by its data.
+        unsafe { AccountView::new_unchecked(self.buffer.as_mut_ptr() as *mut RuntimeAccount) }
+    }
+
+    fn header(&mut self) -> &mut RuntimeAccount {
+        // SAFETY: The buffer starts with a valid, u64-aligned `RuntimeAccount`.
+        unsafe { &mut *(self.buffer.as_mut_ptr() as *mut RuntimeAccount) }
+    }
+}
+
+/// Returns views over `accounts`, in order.
+pub fn views(accounts: &mut [TestAccount]) -> Vec<AccountView> {
+    accounts.iter_mut().map(TestAccount::view).collect()
+}
+
+/// Returns a distinct address filled with `byte`.
+pub fn address(byte: u8) -> Address {
+    Address::new_from_array([byte; 32])
+}
+
+/// Returns `len` zeroed bytes starting with `discriminator`.
+pub fn account_data(discriminator: &[u8; 8], len: usize) -> Vec<u8> {
+    let mut data = vec![0u8; len];
+    data[..8].copy_from_slice(discriminator);
+    data
+}
+
+/// Times `f` over `BENCH_ITERATIONS` runs, after a warm-up, and prints the mean per run.
+pub fn bench(name: &str, mut f: impl FnMut()) -> Duration {
+    for _ in 0..BENCH_ITERATIONS / 10 {
+        f();
+    }
+    let start = std::time::Instant::now();
+    for _ in 0..BENCH_ITERATIONS {
+        f();
+    }
+    let mean = start.elapsed() / BENCH_ITERATIONS;
+    std::println!("bench {name}: {} ns/iter", mean.as_nanos());
+    mean
+}
@@ -0,0 +1,284 @@
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+use crate::ClaimWithdrawContext;
+use {
+    crate::{
+        BeethovenError, Deposit, DepositContext, try_from_deposit_context,
+        util::{
+            TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            bytes::{TOKEN_ACCOUNT_AMOUNT_OFFSET, read_u64},
+        },
+    },
+    pinocchio::{AccountView, ProgramResult, cpi::Signer, error::ProgramError},
+};
+
+/// Account whose balance a deposit moves.
+#[derive(Clone, Copy)]
+pub enum Balance<'a> {
+    /// SPL Token or Token-2022 account, measured by its token amount
+    Token(&'a AccountView),
+    /// Native account, measured in lamports
+    Lamports(&'a AccountView),
+}
+
+impl Balance<'_> {
+    /// Reads the current balance.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Token amount or lamports
+    /// * `Err(ProgramError::IllegalOwner)` - A token balance is not owned by a token program
+    /// * `Err(ProgramError)` - Account data could not be read
+    pub fn read(&self) -> Result<u64, ProgramError> {
+        match self {
+            Balance::Token(account) => {
+                if !account.owned_by(&TOKEN_PROGRAM_ID) && !account.owned_by(&TOKEN_2022_PROGRAM_ID)
+                {
+                    return Err(ProgramError::IllegalOwner);
+                }
+                read_u64(&account.try_borrow()?, TOKEN_ACCOUNT_AMOUNT_OFFSET)
+            }
+            Balance::Lamports(account) => Ok(account.lamports()),
+        }
+    }
+}
+
+impl<'info> DepositContext<'info> {
+    /// Returns the account the deposit spends from and the account receiving the position.
+    ///
+    /// # Notes
+    /// * Kamino obligation deposits credit the reserve's collateral supply, and Drift
+    ///   insurance fund stakes its vault: the user's shares live in program state there.
+    pub fn balances(&self) -> (Balance<'info>, Balance<'info>) {
+        match self {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(ctx) => (
+                Balance::Token(ctx.user_source_liquidity),
+                Balance::Token(ctx.reserve_destination_deposit_collateral),
+            ),
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(ctx) => (
+                Balance::Token(ctx.user_source_liquidity),
+                Balance::Token(ctx.user_destination_collateral),
+            ),
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(ctx) => (
+                Balance::Token(ctx.depositor_token_account),
+                Balance::Token(ctx.recipient_token_account),
+            ),
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(ctx) => (
+                Balance::Token(ctx.user_token_account),
+                Balance::Token(ctx.insurance_fund_vault),
+            ),
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(ctx) => {
+                (Balance::Token(ctx.lst_ata), Balance::Token(ctx.rst_ata))
+            }
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(ctx) => (
+                Balance::Lamports(ctx.transfer_from),
+                Balance::Token(ctx.mint_to),
+            ),
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(ctx) => (
+                Balance::Lamports(ctx.lamports_from),
+                Balance::Token(ctx.pool_tokens_to),
+            ),
+        }
+    }
+}
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+impl<'info> ClaimWithdrawContext<'info> {
+    /// Returns the account a claim pays the withdrawn funds into.
+    pub fn destination(&self) -> Balance<'info> {
+        match self {
+            #[cfg(feature = "drift")]
+            ClaimWithdrawContext::DriftInsuranceFund(ctx) => Balance::Token(ctx.user_token_account),
+
+            #[cfg(feature = "jito")]
+            ClaimWithdrawContext::Jito(ctx) => Balance::Token(ctx.staker_token_account),
+
+            #[cfg(feature = "marinade")]
+            ClaimWithdrawContext::Marinade(ctx) => Balance::Lamports(ctx.transfer_sol_to),
+        }
+    }
+}
+
+/// Source and destination balances of a deposit, taken before the CPI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BalanceSnapshot {
+    /// Balance of the source account
+    pub source: u64,
+    /// Balance of the destination account
+    pub destination: u64,
+}
+
+impl BalanceSnapshot {
+    /// Snapshots the balances `ctx` is about to move.
+    pub fn take(ctx: &DepositContext) -> Result<Self, ProgramError> {
+        let (source, destination) = ctx.balances();
+        Ok(Self {
+            source: source.read()?,
+            destination: destination.read()?,
+        })
+    }
+
+    /// Checks that, since the snapshot, the source decreased by exactly `amount` and the
+    /// destination increased.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Balances moved as the deposit implies
+    /// * `Err(BeethovenError::UnexpectedBalanceChange)` - They did not
+    /// * `Err(ProgramError)` - A balance could not be read
+    pub fn verify(&self, ctx: &DepositContext, amount: u64) -> ProgramResult {
+        let (source, destination) = ctx.balances();
+        let spent = self.source.checked_sub(source.read()?);
+        if spent != Some(amount) || destination.read()? <= self.destination {
+            return Err(BeethovenError::UnexpectedBalanceChange.into());
+        }
+        Ok(())
+    }
+}
+
+/// Convenience function: Parses accounts, deposits with PDA signing, and verifies the
+/// balances the deposit moved.
+///
+/// Opt-in guard against account substitutions that pass structural validation, e.g. a
+/// destination the user does not control.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit
+/// * `signer_seeds` - Seeds for PDA signing
+///
+/// # Returns
+/// * `Ok(())` - Deposit executed and balances moved as expected
+/// * `Err(BeethovenError::UnexpectedBalanceChange)` - The source did not decrease by exactly
+///   `amount`, or the destination did not increase
+/// * `Err(ProgramError)` - Parsing, a balance read or the CPI failed
+pub fn deposit_signed_with_balance_check(
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ctx = try_from_deposit_context(accounts)?;
+    let snapshot = BalanceSnapshot::take(&ctx)?;
+    DepositContext::deposit_signed(&ctx, amount, signer_seeds)?;
+    snapshot.verify(&ctx, amount)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "marinade")]
+    use crate::{
+        programs::marinade::{MARINADE_CLAIM_ACCOUNTS, MARINADE_PROGRAM_ID},
+        try_from_claim_withdraw_context,
+    };
+    #[cfg(feature = "marinade")]
+    use std::vec::Vec;
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        pinocchio::Address,
+        std::vec,
+    };
+
+    fn token_account(byte: u8, owner: Address, amount: u64) -> TestAccount {
+        let mut data = vec![0u8; 165];
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&amount.to_le_bytes());
+        TestAccount::new(address(byte), owner, &data)
+    }
+
+    #[test]
+    fn reads_token_amounts_and_lamports() {
+        let mut accounts = [
+            token_account(1, TOKEN_PROGRAM_ID, 7),
+            token_account(2, TOKEN_2022_PROGRAM_ID, 8),
+            token_account(3, address(9), 9),
+        ];
+        let accounts = views(&mut accounts);
+        accounts[2].set_lamports(10);
+
+        assert_eq!(Balance::Token(&accounts[0]).read(), Ok(7));
+        assert_eq!(Balance::Token(&accounts[1]).read(), Ok(8));
+        assert_eq!(
+            Balance::Token(&accounts[2]).read(),
+            Err(ProgramError::IllegalOwner)
+        );
+        assert_eq!(Balance::Lamports(&accounts[2]).read(), Ok(10));
+    }
+
+    #[cfg(feature = "jupiter")]
+    fn set_amount(account: &AccountView, amount: u64) {
+        account.try_borrow_mut().unwrap()
+            [TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&amount.to_le_bytes());
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn verify_requires_the_exact_spend_and_a_credit() {
+        // Indices into `JUPITER_EARN_DEPOSIT_ACCOUNTS`.
+        const DEPOSITOR: usize = 2;
+        const RECIPIENT: usize = 3;
+
+        let mut accounts = crate::programs::jupiter::tests::deposit_accounts();
+        accounts[DEPOSITOR] = token_account(DEPOSITOR as u8, TOKEN_PROGRAM_ID, 1_000);
+        accounts[RECIPIENT] = token_account(RECIPIENT as u8, TOKEN_PROGRAM_ID, 0);
+        let accounts = views(&mut accounts);
+        let ctx = try_from_deposit_context(&accounts).unwrap();
+        let snapshot = BalanceSnapshot::take(&ctx).unwrap();
+        assert_eq!(
+            snapshot,
+            BalanceSnapshot {
+                source: 1_000,
+                destination: 0,
+            }
+        );
+
+        // Nothing moved: the CPI is a no-op off-chain.
+        assert_eq!(
+            deposit_signed_with_balance_check(&accounts, 400, &[]),
+            Err(BeethovenError::UnexpectedBalanceChange.into())
+        );
+
+        set_amount(&accounts[DEPOSITOR], 600);
+        assert_eq!(
+            snapshot.verify(&ctx, 400),
+            Err(BeethovenError::UnexpectedBalanceChange.into())
+        );
+        set_amount(&accounts[RECIPIENT], 390);
+        assert_eq!(snapshot.verify(&ctx, 400), Ok(()));
+        assert_eq!(
+            snapshot.verify(&ctx, 300),
+            Err(BeethovenError::UnexpectedBalanceChange.into())
+        );
+    }
+
+    #[cfg(feature = "marinade")]
+    #[test]
+    fn marinade_claims_pay_lamports_to_the_recipient() {
+        let mut accounts: Vec<TestAccount> = (0..MARINADE_CLAIM_ACCOUNTS.len() as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(MARINADE_PROGRAM_ID),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect();
+        let accounts = views(&mut accounts);
+        accounts[4].set_lamports(5);
+
+        let ctx = try_from_claim_withdraw_context(&accounts).unwrap();
+        let Balance::Lamports(destination) = ctx.destination() else {
+            panic!("expected a lamport balance");
+        };
+        assert_eq!(destination.address(), &address(4));
+        assert_eq!(ctx.destination().read(), Ok(5));
+    }
+}
@@ -0,0 +1,464 @@
+use {
+    crate::{
+        DepositContext, try_from_deposit_context,
+        util::{Rounding, mul_div, to_u64},
+    },
+    pinocchio::{AccountView, ProgramResult, error::ProgramError},
+};
+
+/// Guard trait checking a venue's own deposit caps before attempting a CPI.
+///
+/// A deposit rejected by the protocol for exceeding its cap fails the whole transaction;
+/// checking upfront lets callers pick another venue or bail out with a typed error instead.
+pub trait Capacity<'info> {
+    /// Protocol-specific accounts required to read the cap
+    type Accounts;
+
+    /// Checks that depositing `amount` stays within the venue's deposit cap
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount` - Amount about to be deposited
+    ///
+    /// # Returns
+    /// * `Ok(())` - The deposit fits within the cap
+    /// * `Err(BeethovenError::DepositCapExceeded)` - The deposit would exceed the cap
+    fn check_capacity(ctx: &Self::Accounts, amount: u64) -> ProgramResult;
+
+    /// Returns how much the venue can still accept before hitting its deposit cap
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Remaining deposit capacity, `u64::MAX` for uncapped venues
+    /// * `Err(ProgramError)` - Venue state could not be read
+    fn available_deposit_capacity(ctx: &Self::Accounts) -> Result<u64, ProgramError>;
+
+    /// Returns how much underlying liquidity the venue can pay out to withdrawals right now
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Liquidity available for withdrawals, `u64::MAX` when not bounded by it
+    /// * `Err(ProgramError)` - Venue state could not be read
+    fn available_liquidity_for_withdraw(ctx: &Self::Accounts) -> Result<u64, ProgramError>;
+
+    /// Returns `false` when the venue is paused or frozen at the protocol level
+    ///
+    /// Lets routers skip a frozen venue upfront instead of interpreting a failed CPI.
+    /// Venues whose state cannot be read are reported as not operational.
+    fn is_operational(ctx: &Self::Accounts) -> bool;
+}
+
+impl<'info> Capacity<'info> for DepositContext<'info> {
+    type Accounts = Self;
+
+    fn check_capacity(ctx: &Self::Accounts, amount: u64) -> ProgramResult {
+        match ctx {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(kamino_ctx) => {
+                crate::programs::kamino::Kamino::check_capacity(kamino_ctx, amount)
+            }
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => {
+                crate::programs::kamino::KaminoLiquidity::check_capacity(kamino_ctx, amount)
+            }
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(jupiter_ctx) => {
+                crate::programs::jupiter::JupiterEarn::check_capacity(jupiter_ctx, amount)
+            }
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => {
+                crate::programs::drift::DriftInsuranceFund::check_capacity(drift_ctx, amount)
+            }
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => {
+                crate::programs::solayer::SolayerRestaking::check_capacity(solayer_ctx, amount)
+            }
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => {
+                crate::programs::marinade::Marinade::check_capacity(marinade_ctx, amount)
+            }
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => {
+                crate::programs::stake_pool::SplStakePool::check_capacity(stake_pool_ctx, amount)
+            }
+        }
+    }
+
+    fn available_deposit_capacity(ctx: &Self::Accounts) -> Result<u64, ProgramError> {
+        match ctx {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(kamino_ctx) => {
+                crate::programs::kamino::Kamino::available_deposit_capacity(kamino_ctx)
+            }
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => {
+                crate::programs::kamino::KaminoLiquidity::available_deposit_capacity(kamino_ctx)
+            }
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(jupiter_ctx) => {
+                crate::programs::jupiter::JupiterEarn::available_deposit_capacity(jupiter_ctx)
+            }
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => {
+                crate::programs::drift::DriftInsuranceFund::available_deposit_capacity(drift_ctx)
+            }
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => {
+                crate::programs::solayer::SolayerRestaking::available_deposit_capacity(solayer_ctx)
+            }
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => {
+                crate::programs::marinade::Marinade::available_deposit_capacity(marinade_ctx)
+            }
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => {
+                crate::programs::stake_pool::SplStakePool::available_deposit_capacity(
+                    stake_pool_ctx,
+                )
+            }
+        }
+    }
+
+    fn available_liquidity_for_withdraw(ctx: &Self::Accounts) -> Result<u64, ProgramError> {
+        match ctx {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(kamino_ctx) => {
+                crate::programs::kamino::Kamino::available_liquidity_for_withdraw(kamino_ctx)
+            }
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => {
+                crate::programs::kamino::KaminoLiquidity::available_liquidity_for_withdraw(
+                    kamino_ctx,
+                )
+            }
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(jupiter_ctx) => {
+                crate::programs::jupiter::JupiterEarn::available_liquidity_for_withdraw(jupiter_ctx)
+            }
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => {
+                crate::programs::drift::DriftInsuranceFund::available_liquidity_for_withdraw(
+                    drift_ctx,
+                )
+            }
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => {
+                crate::programs::solayer::SolayerRestaking::available_liquidity_for_withdraw(
+                    solayer_ctx,
+                )
+            }
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => {
+                crate::programs::marinade::Marinade::available_liquidity_for_withdraw(marinade_ctx)
+            }
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => {
+                crate::programs::stake_pool::SplStakePool::available_liquidity_for_withdraw(
+                    stake_pool_ctx,
+                )
+            }
+        }
+    }
+
+    fn is_operational(ctx: &Self::Accounts) -> bool {
+        match ctx {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(kamino_ctx) => {
+                crate::programs::kamino::Kamino::is_operational(kamino_ctx)
+            }
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => {
+                crate::programs::kamino::KaminoLiquidity::is_operational(kamino_ctx)
+            }
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(jupiter_ctx) => {
+                crate::programs::jupiter::JupiterEarn::is_operational(jupiter_ctx)
+            }
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => {
+                crate::programs::drift::DriftInsuranceFund::is_operational(drift_ctx)
+            }
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => {
+                crate::programs::solayer::SolayerRestaking::is_operational(solayer_ctx)
+            }
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => {
+                crate::programs::marinade::Marinade::is_operational(marinade_ctx)
+            }
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => {
+                crate::programs::stake_pool::SplStakePool::is_operational(stake_pool_ctx)
+            }
+        }
+    }
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and checks the deposit cap.
+///
+/// This is equivalent to calling `try_from_deposit_context` followed by
+/// `DepositContext::check_capacity`.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens about to be deposited
+///
+/// # Returns
+/// * `Ok(())` - The deposit fits within the venue's cap
+/// * `Err(BeethovenError::DepositCapExceeded)` - The deposit would exceed the cap
+/// * `Err(ProgramError)` - Parsing, discrimination, or state reading failed
+pub fn check_capacity(accounts: &[AccountView], amount: u64) -> ProgramResult {
+    let ctx = try_from_deposit_context(accounts)?;
+    DepositContext::check_capacity(&ctx, amount)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and returns the venue's
+/// remaining deposit capacity.
+///
+/// Lets routers size allocations to what each venue can absorb instead of discovering the
+/// limit through a failed CPI.
+///
+/// # Returns
+/// * `Ok(u64)` - Remaining deposit capacity, `u64::MAX` for uncapped venues
+/// * `Err(ProgramError)` - Parsing, discrimination, or state reading failed
+pub fn available_deposit_capacity(accounts: &[AccountView]) -> Result<u64, ProgramError> {
+    let ctx = try_from_deposit_context(accounts)?;
+    DepositContext::available_deposit_capacity(&ctx)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and returns the liquidity
+/// the venue can pay out to withdrawals.
+///
+/// # Returns
+/// * `Ok(u64)` - Liquidity available for withdrawals, `u64::MAX` when not bounded by it
+/// * `Err(ProgramError)` - Parsing, discrimination, or state reading failed
+pub fn available_liquidity_for_withdraw(accounts: &[AccountView]) -> Result<u64, ProgramError> {
+    let ctx = try_from_deposit_context(accounts)?;
+    DepositContext::available_liquidity_for_withdraw(&ctx)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and reports whether the
+/// venue is operational.
+///
+/// # Returns
+/// * `Ok(bool)` - `false` when the venue is paused or frozen at the protocol level
+/// * `Err(ProgramError)` - Parsing or discrimination failed
+pub fn is_operational(accounts: &[AccountView]) -> Result<bool, ProgramError> {
+    let ctx = try_from_deposit_context(accounts)?;
+    Ok(DepositContext::is_operational(&ctx))
+}
+
+/// Maximum number of venues `route_withdraw` splits a withdrawal across.
+pub const MAX_ROUTE_VENUES: usize = 16;
+
+/// Outcome of `route_withdraw`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithdrawFill {
+    /// Amount withdrawn across all venues
+    pub filled: u64,
+    /// Part of the requested amount no venue could pay out
+    pub shortfall: u64,
+}
+
+impl WithdrawFill {
+    /// Returns `true` when the whole requested amount was withdrawn.
+    pub const fn is_complete(&self) -> bool {
+        self.shortfall == 0
+    }
+}
+
+/// Splits a withdrawal of `total_amount` across venues, proportionally to what each can pay.
+///
+/// Each venue contributes at most `min(positions[i], available_liquidity_for_withdraw)`;
+/// venues without instant liquidity, or that are not operational, are skipped. The split is
+/// proportional to those caps, so every venue is drawn down by the same fraction, and
+/// rounding dust goes to the first venues with room left. Whatever the venues cannot cover
+/// is reported as a shortfall instead of failing.
+///
+/// # Arguments
+/// * `contexts` - Parsed contexts of the venues holding the position
+/// * `positions` - Amount of underlying held in each venue, in the same order
+/// * `total_amount` - Amount of underlying to withdraw
+/// * `withdraw` - Executes the withdrawal of an amount from the venue at an index
+///
+/// # Returns
+/// * `Ok(WithdrawFill)` - Amount withdrawn and shortfall
+/// * `Err(ProgramError::InvalidArgument)` - `positions` does not match `contexts`, or more
+///   than `MAX_ROUTE_VENUES` venues
+/// * `Err(ProgramError)` - Reading a venue or a withdrawal failed
+///
+/// # Notes
+/// * All amounts are computed before the first withdrawal, so earlier withdrawals cannot
+///   skew the split.
+pub fn route_withdraw(
+    contexts: &[DepositContext],
+    positions: &[u64],
+    total_amount: u64,
+    mut withdraw: impl FnMut(usize, u64) -> ProgramResult,
+) -> Result<WithdrawFill, ProgramError> {
+    if contexts.len() != positions.len() || contexts.len() > MAX_ROUTE_VENUES {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut caps = [0u64; MAX_ROUTE_VENUES];
+    let mut withdrawable = 0u128;
+    for (index, ctx) in contexts.iter().enumerate() {
+        if !DepositContext::is_operational(ctx) {
+            continue;
+        }
+        caps[index] = positions[index].min(DepositContext::available_liquidity_for_withdraw(ctx)?);
+        withdrawable += caps[index] as u128;
+    }
+
+    let target = (total_amount as u128).min(withdrawable) as u64;
+    let mut amounts = [0u64; MAX_ROUTE_VENUES];
+    let mut remainder = target;
+    for index in 0..contexts.len() {
+        amounts[index] = to_u64(mul_div(
+            caps[index] as u128,
+            target as u128,
+            withdrawable.max(1),
+            Rounding::Down,
+        )?)?;
+        remainder -= amounts[index];
+    }
+    for index in 0..contexts.len() {
+        let extra = (caps[index] - amounts[index]).min(remainder);
+        amounts[index] += extra;
+        remainder -= extra;
+    }
+
+    for (index, amount) in amounts[..contexts.len()].iter().enumerate() {
+        if *amount > 0 {
+            withdraw(index, *amount)?;
+        }
+    }
+
+    Ok(WithdrawFill {
+        filled: target,
+        shortfall: total_amount - target,
+    })
+}
+
+#[cfg(all(test, feature = "jupiter"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            programs::jupiter::tests::{deposit_accounts, paused_deposit_accounts},
+            test_utils::{TestAccount, views},
+        },
+        std::{vec, vec::Vec},
+    };
+
+    /// Routes `total_amount` across Jupiter venues holding `positions`, returning the fill
+    /// and the amount withdrawn from each venue.
+    fn route(
+        venues: Vec<Vec<TestAccount>>,
+        positions: &[u64],
+        total_amount: u64,
+    ) -> (Result<WithdrawFill, ProgramError>, Vec<u64>) {
+        let mut venues = venues;
+        let views: Vec<Vec<AccountView>> = venues.iter_mut().map(|venue| views(venue)).collect();
+        let contexts: Vec<DepositContext> = views
+            .iter()
+            .map(|views| try_from_deposit_context(views).unwrap())
+            .collect();
+
+        let mut withdrawn = vec![0; contexts.len()];
+        let fill = route_withdraw(&contexts, positions, total_amount, |index, amount| {
+            withdrawn[index] += amount;
+            Ok(())
+        });
+        (fill, withdrawn)
+    }
+
+    #[test]
+    fn splits_proportionally_to_each_venue_position() {
+        let (fill, withdrawn) = route(
+            vec![deposit_accounts(), deposit_accounts()],
+            &[300, 100],
+            200,
+        );
+        assert_eq!(
+            fill,
+            Ok(WithdrawFill {
+                filled: 200,
+                shortfall: 0,
+            })
+        );
+        assert_eq!(withdrawn, [150, 50]);
+    }
+
+    #[test]
+    fn gives_rounding_dust_to_the_first_venues() {
+        let venues = vec![deposit_accounts(), deposit_accounts(), deposit_accounts()];
+        let (fill, withdrawn) = route(venues, &[1, 1, 1], 2);
+        assert!(fill.unwrap().is_complete());
+        assert_eq!(withdrawn, [1, 1, 0]);
+    }
+
+    #[test]
+    fn reports_what_the_venues_cannot_cover() {
+        let (fill, withdrawn) = route(
+            vec![paused_deposit_accounts(), deposit_accounts()],
+            &[500, 100],
+            400,
+        );
+        assert_eq!(
+            fill,
+            Ok(WithdrawFill {
+                filled: 100,
+                shortfall: 300,
+            })
+        );
+        assert_eq!(withdrawn, [0, 100]);
+    }
+
+    #[test]
+    fn rejects_mismatched_positions() {
+        let (fill, withdrawn) = route(vec![deposit_accounts()], &[1, 1], 1);
+        assert_eq!(fill, Err(ProgramError::InvalidArgument));
+        assert_eq!(withdrawn, [0]);
+    }
+
+    #[test]
+    fn stops_at_the_first_failed_withdrawal() {
+        let mut venues = [deposit_accounts(), deposit_accounts()];
+        let views: Vec<Vec<AccountView>> = venues.iter_mut().map(|venue| views(venue)).collect();
+        let contexts: Vec<DepositContext> = views
+            .iter()
+            .map(|views| try_from_deposit_context(views).unwrap())
+            .collect();
+
+        let mut calls = 0;
+        let fill = route_withdraw(&contexts, &[10, 10], 20, |_, _| {
+            calls += 1;
+            Err(ProgramError::Custom(7))
+        });
+        assert_eq!(fill, Err(ProgramError::Custom(7)));
+        assert_eq!(calls, 1);
+    }
+}
@@ -1,5 +1,14 @@
-use pinocchio::{
-    AccountView, ProgramResult, address::address_eq, cpi::Signer, error::ProgramError,
+use {
+    crate::{
+        AccountCheck, AccountError, BeethovenError, Capacity, Hooks, is_retryable,
+        programs::{
+            AccountSpec, Detector, ProgramIdDetector, ProgramIds, Protocol, check_layout, dispatch,
+        },
+        util::{AssociatedTokenAccount, DelegatedTransfer},
+    },
+    pinocchio::{
+        AccountView, Address, ProgramResult, address::address_eq, cpi::Signer, error::ProgramError,
+    },
 };
 
 /// Core trait for deposit operations across different protocols (Kamino, Jupiter, etc.)
@@ -35,8 +44,135 @@ pub enum DepositContext<'info> {
     #[cfg(feature = "kamino")]
     Kamino(crate::programs::kamino::KaminoDepositAccounts<'info>),
 
+    #[cfg(feature = "kamino")]
+    KaminoLiquidity(crate::programs::kamino::KaminoDepositLiquidityAccounts<'info>),
+
     #[cfg(feature = "jupiter")]
     Jupiter(crate::programs::jupiter::JupiterEarnDepositAccounts<'info>),
+
+    #[cfg(feature = "drift")]
+    DriftInsuranceFund(crate::programs::drift::DriftInsuranceFundStakeAccounts<'info>),
+
+    #[cfg(feature = "solayer")]
+    Solayer(crate::programs::solayer::SolayerRestakeAccounts<'info>),
+
+    #[cfg(feature = "marinade")]
+    Marinade(crate::programs::marinade::MarinadeDepositAccounts<'info>),
+
+    #[cfg(feature = "stake_pool")]
+    StakePool(crate::programs::stake_pool::SplStakePoolDepositSolAccounts<'info>),
+}
+
+impl DepositContext<'_> {
+    /// Protocol the context routes to.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(_) | DepositContext::KaminoLiquidity(_) => Protocol::Kamino,
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(_) => Protocol::Jupiter,
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(_) => Protocol::Drift,
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(_) => Protocol::Solayer,
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(_) => Protocol::Marinade,
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(_) => Protocol::StakePool,
+        }
+    }
+
+    /// Checks that the deposit spends from `token_account`, with `authority` as the
+    /// depositing authority.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The context spends `token_account` as `authority`
+    /// * `Err(AccountError)` - Either key differs, or the protocol deposits native SOL and
+    ///   spends no token account
+    pub fn check_spender(
+        &self,
+        token_account: &Address,
+        authority: &Address,
+    ) -> Result<(), AccountError> {
+        let (layout, (source_name, source), (signer_name, signer)) = self.spender()?;
+        if !address_eq(source.address(), token_account) {
+            return Err(AccountError::of(layout, source_name, AccountCheck::Key));
+        }
+        if !address_eq(signer.address(), authority) {
+            return Err(AccountError::of(layout, signer_name, AccountCheck::Key));
+        }
+        Ok(())
+    }
+
+    /// Returns the layout of the context with its spent token account and depositing
+    /// authority, each with its field name.
+    #[allow(clippy::type_complexity)]
+    fn spender(
+        &self,
+    ) -> Result<
+        (
+            &'static [AccountSpec],
+            (&'static str, &AccountView),
+            (&'static str, &AccountView),
+        ),
+        AccountError,
+    > {
+        match self {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(ctx) => Ok((
+                crate::programs::kamino::KAMINO_DEPOSIT_ACCOUNTS,
+                ("user_source_liquidity", ctx.user_source_liquidity),
+                ("owner", ctx.owner),
+            )),
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(ctx) => Ok((
+                crate::programs::kamino::KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+                ("user_source_liquidity", ctx.user_source_liquidity),
+                ("owner", ctx.owner),
+            )),
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(ctx) => Ok((
+                crate::programs::jupiter::JUPITER_EARN_DEPOSIT_ACCOUNTS,
+                ("depositor_token_account", ctx.depositor_token_account),
+                ("signer", ctx.signer),
+            )),
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(ctx) => Ok((
+                crate::programs::drift::DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                ("user_token_account", ctx.user_token_account),
+                ("authority", ctx.authority),
+            )),
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(ctx) => Ok((
+                crate::programs::solayer::SOLAYER_RESTAKE_ACCOUNTS,
+                ("lst_ata", ctx.lst_ata),
+                ("signer", ctx.signer),
+            )),
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(_) => Err(AccountError::of(
+                crate::programs::marinade::MARINADE_DEPOSIT_ACCOUNTS,
+                "transfer_from",
+                AccountCheck::Key,
+            )),
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(_) => Err(AccountError::of(
+                crate::programs::stake_pool::SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS,
+                "lamports_from",
+                AccountCheck::Key,
+            )),
+        }
+    }
 }
 
 impl<'info> Deposit<'info> for DepositContext<'info> {
@@ -45,18 +181,63 @@ impl<'info> Deposit<'info> for DepositContext<'info> {
     fn deposit_signed(ctx: &Self::Accounts, amount: u64, signer_seeds: &[Signer]) -> ProgramResult {
         match ctx {
             #[cfg(feature = "kamino")]
-            DepositContext::Kamino(kamino_ctx) => {
+            DepositContext::Kamino(kamino_ctx) => dispatch(Protocol::Kamino, || {
                 crate::programs::kamino::Kamino::deposit_signed(kamino_ctx, amount, signer_seeds)
-            }
+            }),
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => dispatch(Protocol::Kamino, || {
+                crate::programs::kamino::KaminoLiquidity::deposit_signed(
+                    kamino_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
 
             #[cfg(feature = "jupiter")]
-            DepositContext::Jupiter(jupiter_ctx) => {
+            DepositContext::Jupiter(jupiter_ctx) => dispatch(Protocol::Jupiter, || {
                 crate::programs::jupiter::JupiterEarn::deposit_signed(
                     jupiter_ctx,
                     amount,
                     signer_seeds,
                 )
-            }
+            }),
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => dispatch(Protocol::Drift, || {
+                crate::programs::drift::DriftInsuranceFund::deposit_signed(
+                    drift_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => dispatch(Protocol::Solayer, || {
+                crate::programs::solayer::SolayerRestaking::deposit_signed(
+                    solayer_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => dispatch(Protocol::Marinade, || {
+                crate::programs::marinade::Marinade::deposit_signed(
+                    marinade_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => dispatch(Protocol::StakePool, || {
+                crate::programs::stake_pool::SplStakePool::deposit_signed(
+                    stake_pool_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
         }
     }
 
@@ -102,27 +283,192 @@ impl<'info> Deposit<'info> for DepositContext<'info> {
 pub fn try_from_deposit_context<'info>(
     accounts: &'info [AccountView],
 ) -> Result<DepositContext<'info>, ProgramError> {
-    let detector_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    try_from_deposit_context_with_ids(accounts, &ProgramIds::MAINNET)
+}
 
-    #[cfg(feature = "kamino")]
-    if address_eq(
-        detector_account.address(),
-        &crate::programs::kamino::KAMINO_LEND_PROGRAM_ID,
-    ) {
-        let ctx = crate::programs::kamino::KaminoDepositAccounts::try_from(accounts)?;
-        return Ok(DepositContext::Kamino(ctx));
-    }
+/// Same as `try_from_deposit_context`, but discriminates against the provided `ProgramIds`
+/// instead of the mainnet deployments.
+///
+/// Use this to target devnet or staging deployments whose program IDs differ from mainnet.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `ids` - Program IDs to match the first account against
+///
+/// # Returns
+/// * `Ok(DepositContext)` - Typed context for the detected protocol
+/// * `Err(ProgramError::NotEnoughAccountKeys)` - Empty account slice provided
+/// * `Err(ProgramError::InvalidAccountData)` - No matching protocol found or invalid account structure
+pub fn try_from_deposit_context_with_ids<'info>(
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<DepositContext<'info>, ProgramError> {
+    try_from_deposit_context_with(&ProgramIdDetector, accounts, ids)
+}
 
-    #[cfg(feature = "jupiter")]
-    if address_eq(
-        detector_account.address(),
-        &crate::programs::jupiter::JUPITER_EARN_PROGRAM_ID,
-    ) {
-        let ctx = crate::programs::jupiter::JupiterEarnDepositAccounts::try_from(accounts)?;
-        return Ok(DepositContext::Jupiter(ctx));
-    }
+/// Parses accounts using a custom `Detector` strategy to decide the protocol.
+///
+/// # Arguments
+/// * `detector` - Strategy deciding which protocol the accounts belong to
+/// * `accounts` - Slice of accounts in the detected protocol's order
+/// * `ids` - Program IDs to detect and validate against
+///
+/// # Returns
+/// * `Ok(DepositContext)` - Typed context for the detected protocol
+/// * `Err(ProgramError)` - Detection failed or invalid account structure
+///
+/// # Example
+/// ```ignore
+/// // Protocol tag carried in the first byte of instruction data
+/// let ctx = try_from_deposit_context_with(
+///     &TagDetector(instruction_data[0]),
+///     remaining_accounts,
+///     &ProgramIds::MAINNET,
+/// )?;
+/// ```
+pub fn try_from_deposit_context_with<'info, D: Detector>(
+    detector: &D,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<DepositContext<'info>, ProgramError> {
+    let protocol = detector.detect(accounts, ids)?;
+    try_from_deposit_context_for_with_ids(protocol, accounts, ids)
+}
+
+/// Parses accounts for an explicitly selected protocol, skipping owner-based discrimination.
+///
+/// Use this when the target venue is already known (e.g. from instruction data). The first
+/// account must still be the protocol program, since the CPI needs it, but it is validated
+/// against the expected program ID rather than used to detect the protocol.
+///
+/// For Kamino, a `Reserve` at index 2 selects the liquidity-only layout
+/// (`DepositContext::KaminoLiquidity`); anything else is parsed as an obligation deposit.
+///
+/// # Arguments
+/// * `protocol` - Protocol the accounts belong to
+/// * `accounts` - Slice of accounts in the selected protocol's order
+///
+/// # Returns
+/// * `Ok(DepositContext)` - Typed context for the selected protocol
+/// * `Err(ProgramError::NotEnoughAccountKeys)` - Not enough accounts for the selected protocol
+/// * `Err(ProgramError::IncorrectProgramId)` - First account is not the selected protocol's program
+/// * `Err(ProgramError::InvalidAccountData)` - The selected protocol takes no deposits
+/// * `Err(BeethovenError::DuplicateAccount)` - Two writable accounts share an address
+///
+/// # Example
+/// ```ignore
+/// let ctx = try_from_deposit_context_for(Protocol::Kamino, remaining_accounts)?;
+/// DepositContext::deposit(&ctx, amount)?;
+/// ```
+pub fn try_from_deposit_context_for<'info>(
+    protocol: Protocol,
+    accounts: &'info [AccountView],
+) -> Result<DepositContext<'info>, ProgramError> {
+    try_from_deposit_context_for_with_ids(protocol, accounts, &ProgramIds::MAINNET)
+}
+
+/// Same as `try_from_deposit_context_for`, but validates against the provided `ProgramIds`
+/// instead of the mainnet deployments.
+///
+/// # Notes
+/// * The slice is first run through `check_layout`, so short slices and aliased writable
+///   accounts fail with a logged account index before any protocol state is read.
+pub fn try_from_deposit_context_for_with_ids<'info>(
+    protocol: Protocol,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<DepositContext<'info>, ProgramError> {
+    match protocol {
+        #[cfg(feature = "kamino")]
+        Protocol::Kamino => {
+            let program_id = ids.get(protocol);
+            if crate::programs::kamino::KaminoDepositLiquidityAccounts::matches_layout(
+                accounts, program_id,
+            ) {
+                check_layout(
+                    crate::programs::kamino::KAMINO_DEPOSIT_LIQUIDITY_ACCOUNTS,
+                    accounts,
+                )?;
+                let ctx =
+                    crate::programs::kamino::KaminoDepositLiquidityAccounts::try_from_with_program_id(
+                        accounts, program_id,
+                    )?;
+                return Ok(DepositContext::KaminoLiquidity(ctx));
+            }
+
+            check_layout(crate::programs::kamino::KAMINO_DEPOSIT_ACCOUNTS, accounts)?;
+            let ctx =
+                crate::programs::kamino::KaminoDepositAccounts::try_from_with_ids(accounts, ids)?;
+            Ok(DepositContext::Kamino(ctx))
+        }
+
+        #[cfg(feature = "jupiter")]
+        Protocol::Jupiter => {
+            check_layout(
+                crate::programs::jupiter::JUPITER_EARN_DEPOSIT_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx = crate::programs::jupiter::JupiterEarnDepositAccounts::try_from_with_ids(
+                accounts, ids,
+            )?;
+            Ok(DepositContext::Jupiter(ctx))
+        }
+
+        #[cfg(feature = "drift")]
+        Protocol::Drift => {
+            check_layout(
+                crate::programs::drift::DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::drift::DriftInsuranceFundStakeAccounts::try_from_with_program_id(
+                    accounts,
+                    ids.get(protocol),
+                )?;
+            Ok(DepositContext::DriftInsuranceFund(ctx))
+        }
 
-    Err(ProgramError::InvalidAccountData)
+        #[cfg(feature = "solayer")]
+        Protocol::Solayer => {
+            check_layout(crate::programs::solayer::SOLAYER_RESTAKE_ACCOUNTS, accounts)?;
+            let ctx = crate::programs::solayer::SolayerRestakeAccounts::try_from_with_program_id(
+                accounts,
+                ids.get(protocol),
+            )?;
+            Ok(DepositContext::Solayer(ctx))
+        }
+
+        #[cfg(feature = "marinade")]
+        Protocol::Marinade => {
+            check_layout(
+                crate::programs::marinade::MARINADE_DEPOSIT_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx = crate::programs::marinade::MarinadeDepositAccounts::try_from_with_program_id(
+                accounts,
+                ids.get(protocol),
+            )?;
+            Ok(DepositContext::Marinade(ctx))
+        }
+
+        #[cfg(feature = "stake_pool")]
+        Protocol::StakePool => {
+            check_layout(
+                crate::programs::stake_pool::SPL_STAKE_POOL_DEPOSIT_SOL_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::stake_pool::SplStakePoolDepositSolAccounts::try_from_with_program_id(
+                    accounts,
+                    ids.get(protocol),
+                )?;
+            Ok(DepositContext::StakePool(ctx))
+        }
+
+        // Jito vaults only take part in two-phase withdrawals.
+        #[cfg(feature = "jito")]
+        Protocol::Jito => Err(ProgramError::InvalidAccountData),
+    }
 }
 
 /// Convenience function: Parses accounts, discriminates protocol, and executes deposit with PDA signing.
@@ -162,3 +508,279 @@ pub fn deposit_signed(
 pub fn deposit(accounts: &[AccountView], amount: u64) -> ProgramResult {
     deposit_signed(accounts, amount, &[])
 }
+
+/// Convenience function: Parses accounts, discriminates protocol, and executes deposit with
+/// PDA signing, running `hooks` around the CPI.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit
+/// * `signer_seeds` - Seeds for PDA signing
+/// * `hooks` - Callbacks run before and after the deposit
+///
+/// # Returns
+/// * `Ok(())` - Hooks and deposit executed successfully
+/// * `Err(ProgramError)` - Parsing, a hook, or the CPI failed
+pub fn deposit_signed_with_hooks<H: Hooks>(
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+    hooks: &H,
+) -> ProgramResult {
+    let ctx = try_from_deposit_context(accounts)?;
+    hooks.before_deposit(&ctx, amount)?;
+    DepositContext::deposit_signed(&ctx, amount, signer_seeds)?;
+    hooks.after_deposit(&ctx, amount)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and executes deposit,
+/// running `hooks` around the CPI.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit
+/// * `hooks` - Callbacks run before and after the deposit
+///
+/// # Returns
+/// * `Ok(())` - Hooks and deposit executed successfully
+/// * `Err(ProgramError)` - Parsing, a hook, or the CPI failed
+pub fn deposit_with_hooks<H: Hooks>(
+    accounts: &[AccountView],
+    amount: u64,
+    hooks: &H,
+) -> ProgramResult {
+    deposit_signed_with_hooks(accounts, amount, &[], hooks)
+}
+
+/// Deposits into the first venue of `contexts` able to accept `amount`.
+///
+/// Venues are pre-flighted in priority order with `Capacity::check_capacity`; a venue failing
+/// with a retryable error (see [`is_retryable`]) is skipped, any other error aborts. Venues
+/// reporting `Capacity::is_operational` as `false` are skipped without a pre-flight.
+///
+/// # Arguments
+/// * `contexts` - Parsed deposit contexts, in priority order
+/// * `amount` - Amount of tokens to deposit
+/// * `signer_seeds` - Seeds for PDA signing
+///
+/// # Returns
+/// * `Ok(usize)` - Index of the venue the deposit was executed on
+/// * `Err(BeethovenError::NoVenueAvailable)` - Every venue rejected the deposit
+/// * `Err(ProgramError)` - A fatal pre-flight error, or the deposit CPI failed
+///
+/// # Notes
+/// * A failed CPI aborts the whole transaction on Solana, so fallback only happens on the
+///   pre-flight checks. The chosen venue's deposit is executed once, and its failure is final.
+pub fn deposit_with_fallback(
+    contexts: &[DepositContext],
+    amount: u64,
+    signer_seeds: &[Signer],
+) -> Result<usize, ProgramError> {
+    for (index, ctx) in contexts.iter().enumerate() {
+        if !DepositContext::is_operational(ctx) {
+            continue;
+        }
+        match DepositContext::check_capacity(ctx, amount) {
+            Ok(()) => {
+                DepositContext::deposit_signed(ctx, amount, signer_seeds)?;
+                return Ok(index);
+            }
+            Err(error) if is_retryable(&error) => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Err(BeethovenError::NoVenueAvailable.into())
+}
+
+/// Pulls `amount` from a user-owned token account the program is approved on, then deposits it.
+///
+/// The protocol accounts in `accounts` must spend from `source.destination`, with
+/// `source.delegate` as the depositing authority; `signer_seeds` sign both CPIs.
+///
+/// # Arguments
+/// * `source` - User source account, the approved delegate and the intermediate account
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to transfer and deposit
+/// * `signer_seeds` - Seeds of the delegate PDA
+///
+/// # Returns
+/// * `Ok(())` - Transfer and deposit executed successfully
+/// * `Err(ProgramError::InsufficientFunds)` - The user approved less than `amount`
+/// * `Err(ProgramError::InvalidAccountData)` - The context does not spend `source.destination`
+///   as `source.delegate`
+/// * `Err(ProgramError)` - Parsing or either CPI failed
+///
+/// # Notes
+/// * The spender check runs before either CPI, so a context pointing at another token
+///   account cannot strand the transferred tokens in `source.destination`
+pub fn deposit_from_delegate_signed(
+    source: &DelegatedTransfer,
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ctx = try_from_deposit_context(accounts)?;
+    ctx.check_spender(source.destination.address(), source.delegate.address())?;
+    source.transfer_signed(amount, signer_seeds)?;
+    DepositContext::deposit_signed(&ctx, amount, signer_seeds)
+}
+
+/// Creates any missing associated token accounts in `atas`, then parses and deposits.
+///
+/// Opt-in setup for first-time deposits: pass the user's collateral or share accounts (with
+/// the associated token program) so a single transaction can create them and deposit.
+///
+/// # Arguments
+/// * `atas` - Associated token accounts the deposit writes to
+/// * `accounts` - Slice of accounts where the first account's owner determines the protocol
+/// * `amount` - Amount of tokens to deposit
+/// * `signer_seeds` - Seeds for PDA signing, used for both the ATA creations and the deposit
+///
+/// # Returns
+/// * `Ok(())` - Accounts exist and the deposit executed successfully
+/// * `Err(ProgramError)` - ATA creation, parsing, or the deposit CPI failed
+pub fn deposit_with_atas_signed(
+    atas: &[AssociatedTokenAccount],
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ctx = try_from_deposit_context(accounts)?;
+    for ata in atas {
+        ata.create_idempotent_signed(signer_seeds)?;
+    }
+    DepositContext::deposit_signed(&ctx, amount, signer_seeds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "kamino")]
+    use crate::{
+        programs::kamino::{KAMINO_DEPOSIT_ACCOUNTS, KAMINO_LEND_PROGRAM_ID},
+        test_utils::address,
+    };
+    #[cfg(feature = "jupiter")]
+    use {
+        crate::programs::jupiter::tests::{deposit_accounts, paused_deposit_accounts},
+        std::vec,
+    };
+    #[cfg(any(feature = "jupiter", feature = "kamino"))]
+    use {
+        crate::test_utils::{TestAccount, views},
+        std::vec::Vec,
+    };
+
+    #[cfg(feature = "kamino")]
+    fn kamino_accounts() -> Vec<TestAccount> {
+        (0..KAMINO_DEPOSIT_ACCOUNTS.len() as u8)
+            .map(|index| match index {
+                0 => TestAccount::empty(KAMINO_LEND_PROGRAM_ID),
+                _ => TestAccount::empty(address(index)),
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "kamino")]
+    #[test]
+    fn check_spender_accepts_context_accounts() {
+        let mut accounts = kamino_accounts();
+        let views = views(&mut accounts);
+        let ctx = try_from_deposit_context(&views).unwrap();
+        assert_eq!(ctx.check_spender(&address(10), &address(1)), Ok(()));
+    }
+
+    #[cfg(feature = "kamino")]
+    #[test]
+    fn check_spender_reports_mismatched_account() {
+        let mut accounts = kamino_accounts();
+        let views = views(&mut accounts);
+        let ctx = try_from_deposit_context(&views).unwrap();
+
+        let error = ctx.check_spender(&address(42), &address(1)).unwrap_err();
+        assert_eq!((error.index, error.check), (10, AccountCheck::Key));
+
+        let error = ctx.check_spender(&address(10), &address(42)).unwrap_err();
+        assert_eq!((error.index, error.check), (1, AccountCheck::Key));
+    }
+
+    #[cfg(feature = "kamino")]
+    #[test]
+    fn delegate_deposit_rejects_foreign_source_before_transfer() {
+        let mut accounts = kamino_accounts();
+        let views = views(&mut accounts);
+        let mut transfer = [
+            TestAccount::empty(address(100)),
+            TestAccount::empty(address(101)),
+            TestAccount::empty(address(102)),
+            TestAccount::empty(address(103)),
+            TestAccount::empty(address(1)),
+        ];
+        let [token_program, source, mint, destination, delegate] =
+            transfer.each_mut().map(|account| account.view());
+        let source = DelegatedTransfer {
+            token_program: &token_program,
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            delegate: &delegate,
+        };
+
+        // An unknown token program would fail the transfer with `IncorrectProgramId`.
+        assert_eq!(
+            deposit_from_delegate_signed(&source, &views, 1, &[]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    /// Runs `deposit_with_fallback` over Jupiter venues built from `venues`.
+    #[cfg(feature = "jupiter")]
+    fn fallback(venues: Vec<Vec<TestAccount>>, amount: u64) -> Result<usize, ProgramError> {
+        let mut venues = venues;
+        let views: Vec<Vec<AccountView>> = venues.iter_mut().map(|venue| views(venue)).collect();
+        let contexts: Vec<DepositContext> = views
+            .iter()
+            .map(|views| try_from_deposit_context(views).unwrap())
+            .collect();
+        deposit_with_fallback(&contexts, amount, &[])
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn fallback_deposits_into_the_first_open_venue() {
+        assert_eq!(
+            fallback(vec![deposit_accounts(), deposit_accounts()], 1_000),
+            Ok(0)
+        );
+        assert_eq!(
+            fallback(vec![paused_deposit_accounts(), deposit_accounts()], 1_000),
+            Ok(1)
+        );
+    }
+
+    #[cfg(feature = "jupiter")]
+    #[test]
+    fn fallback_skips_venues_over_their_cap() {
+        use crate::programs::jupiter::JupiterEarnDepositAccounts;
+
+        let mut accounts = deposit_accounts();
+        let views = views(&mut accounts);
+        let ctx = JupiterEarnDepositAccounts::try_from(views.as_slice()).unwrap();
+        let capacity =
+            crate::programs::jupiter::JupiterEarn::available_deposit_capacity(&ctx).unwrap();
+
+        assert_eq!(
+            fallback(vec![deposit_accounts(), deposit_accounts()], capacity + 1),
+            Err(BeethovenError::NoVenueAvailable.into())
+        );
+    }
+
+    #[test]
+    fn fallback_without_venues_is_unavailable() {
+        assert_eq!(
+            deposit_with_fallback(&[], 1, &[]),
+            Err(BeethovenError::NoVenueAvailable.into())
+        );
+    }
+}
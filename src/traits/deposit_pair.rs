@@ -0,0 +1,40 @@
+use pinocchio::{ProgramResult, cpi::Signer};
+
+/// Core trait for two-sided liquidity deposits into LP-style protocols (Meteora DLMM, Raydium, etc.)
+///
+/// Single-asset protocols implement `Deposit` instead. Each LP protocol implements this trait
+/// with its specific account requirements and CPI logic.
+pub trait DepositPair<'info> {
+    /// Protocol-specific accounts required for the two-sided deposit CPI
+    type Accounts;
+
+    /// Execute a two-sided deposit with PDA signing capability
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount_a` - Maximum amount of token A to deposit
+    /// * `amount_b` - Maximum amount of token B to deposit
+    /// * `min_lp_out` - Minimum LP tokens (or liquidity shares) to receive
+    /// * `signer_seeds` - Seeds for PDA signing
+    fn deposit_pair_signed(
+        ctx: &Self::Accounts,
+        amount_a: u64,
+        amount_b: u64,
+        min_lp_out: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult;
+
+    /// Execute a two-sided deposit without signing (user is direct signer)
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount_a` - Maximum amount of token A to deposit
+    /// * `amount_b` - Maximum amount of token B to deposit
+    /// * `min_lp_out` - Minimum LP tokens (or liquidity shares) to receive
+    fn deposit_pair(
+        ctx: &Self::Accounts,
+        amount_a: u64,
+        amount_b: u64,
+        min_lp_out: u64,
+    ) -> ProgramResult;
+}
@@ -0,0 +1,186 @@
+use {
+    crate::{Capacity, Deposit, DepositContext, Preview},
+    pinocchio::{ProgramResult, cpi::Signer, error::ProgramError},
+};
+
+/// Object-safe mirror of `Deposit`, `Preview` and `Capacity`.
+///
+/// The operation traits use associated types and associated functions, so they cannot be
+/// turned into trait objects. `DynDeposit` is implemented directly on each protocol's
+/// accounts type, letting routers whose venue set is only known at runtime hold
+/// `&[&dyn DynDeposit]` (or `Vec<Box<dyn DynDeposit>>` with `alloc`).
+///
+/// # Example
+/// ```ignore
+/// let kamino = KaminoDepositAccounts::try_from(kamino_accounts)?;
+/// let jupiter = JupiterEarnDepositAccounts::try_from(jupiter_accounts)?;
+/// let venues: [&dyn DynDeposit; 2] = [&kamino, &jupiter];
+///
+/// for (venue, amount) in venues.iter().zip(split) {
+///     venue.deposit_signed(amount, signers)?;
+/// }
+/// ```
+pub trait DynDeposit {
+    /// Execute a deposit with PDA signing capability
+    fn deposit_signed(&self, amount: u64, signer_seeds: &[Signer]) -> ProgramResult;
+
+    /// Execute a deposit without signing (user is direct signer)
+    fn deposit(&self, amount: u64) -> ProgramResult {
+        self.deposit_signed(amount, &[])
+    }
+
+    /// Returns the shares expected for depositing `amount` (see `Preview::preview_deposit`)
+    fn preview_deposit(&self, amount: u64) -> Result<u64, ProgramError>;
+
+    /// Checks `amount` against the venue's deposit cap (see `Capacity::check_capacity`)
+    fn check_capacity(&self, amount: u64) -> ProgramResult;
+
+    /// Remaining deposit capacity (see `Capacity::available_deposit_capacity`)
+    fn available_deposit_capacity(&self) -> Result<u64, ProgramError>;
+
+    /// Liquidity available for withdrawals (see `Capacity::available_liquidity_for_withdraw`)
+    fn available_liquidity_for_withdraw(&self) -> Result<u64, ProgramError>;
+
+    /// Whether the venue is open for operations (see `Capacity::is_operational`)
+    fn is_operational(&self) -> bool;
+}
+
+macro_rules! impl_dyn_deposit {
+    ($protocol:ty, $accounts:ident) => {
+        impl<'info> DynDeposit for $accounts<'info> {
+            fn deposit_signed(&self, amount: u64, signer_seeds: &[Signer]) -> ProgramResult {
+                <$protocol as Deposit>::deposit_signed(self, amount, signer_seeds)
+            }
+
+            fn preview_deposit(&self, amount: u64) -> Result<u64, ProgramError> {
+                <$protocol as Preview>::preview_deposit(self, amount)
+            }
+
+            fn check_capacity(&self, amount: u64) -> ProgramResult {
+                <$protocol as Capacity>::check_capacity(self, amount)
+            }
+
+            fn available_deposit_capacity(&self) -> Result<u64, ProgramError> {
+                <$protocol as Capacity>::available_deposit_capacity(self)
+            }
+
+            fn available_liquidity_for_withdraw(&self) -> Result<u64, ProgramError> {
+                <$protocol as Capacity>::available_liquidity_for_withdraw(self)
+            }
+
+            fn is_operational(&self) -> bool {
+                <$protocol as Capacity>::is_operational(self)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "kamino")]
+use crate::programs::kamino::{
+    Kamino, KaminoDepositAccounts, KaminoDepositLiquidityAccounts, KaminoLiquidity,
+};
+#[cfg(feature = "kamino")]
+impl_dyn_deposit!(Kamino, KaminoDepositAccounts);
+#[cfg(feature = "kamino")]
+impl_dyn_deposit!(KaminoLiquidity, KaminoDepositLiquidityAccounts);
+
+#[cfg(feature = "jupiter")]
+use crate::programs::jupiter::{JupiterEarn, JupiterEarnDepositAccounts};
+#[cfg(feature = "jupiter")]
+impl_dyn_deposit!(JupiterEarn, JupiterEarnDepositAccounts);
+
+#[cfg(feature = "drift")]
+use crate::programs::drift::{DriftInsuranceFund, DriftInsuranceFundStakeAccounts};
+#[cfg(feature = "drift")]
+impl_dyn_deposit!(DriftInsuranceFund, DriftInsuranceFundStakeAccounts);
+
+#[cfg(feature = "solayer")]
+use crate::programs::solayer::{SolayerRestakeAccounts, SolayerRestaking};
+#[cfg(feature = "solayer")]
+impl_dyn_deposit!(SolayerRestaking, SolayerRestakeAccounts);
+
+#[cfg(feature = "marinade")]
+use crate::programs::marinade::{Marinade, MarinadeDepositAccounts};
+#[cfg(feature = "marinade")]
+impl_dyn_deposit!(Marinade, MarinadeDepositAccounts);
+
+#[cfg(feature = "stake_pool")]
+use crate::programs::stake_pool::{SplStakePool, SplStakePoolDepositSolAccounts};
+#[cfg(feature = "stake_pool")]
+impl_dyn_deposit!(SplStakePool, SplStakePoolDepositSolAccounts);
+
+impl<'info> DepositContext<'info> {
+    /// Returns the parsed protocol accounts as a `DynDeposit` trait object.
+    pub fn as_dyn(&self) -> &dyn DynDeposit {
+        match self {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(kamino_ctx) => kamino_ctx,
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => kamino_ctx,
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(jupiter_ctx) => jupiter_ctx,
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => drift_ctx,
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => solayer_ctx,
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => marinade_ctx,
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => stake_pool_ctx,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "jupiter"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            programs::jupiter::tests::{deposit_accounts, paused_deposit_accounts},
+            test_utils::views,
+            try_from_deposit_context,
+        },
+    };
+
+    #[test]
+    fn as_dyn_forwards_to_the_protocol_traits() {
+        let mut accounts = deposit_accounts();
+        let views = views(&mut accounts);
+        let ctx = try_from_deposit_context(&views).unwrap();
+        let jupiter_ctx = &JupiterEarnDepositAccounts::try_from(&views[..]).unwrap();
+        let venue = ctx.as_dyn();
+
+        assert_eq!(
+            venue.preview_deposit(1_000),
+            JupiterEarn::preview_deposit(jupiter_ctx, 1_000)
+        );
+        assert_eq!(
+            venue.available_deposit_capacity(),
+            JupiterEarn::available_deposit_capacity(jupiter_ctx)
+        );
+        assert_eq!(
+            venue.available_liquidity_for_withdraw(),
+            JupiterEarn::available_liquidity_for_withdraw(jupiter_ctx)
+        );
+        assert_eq!(
+            venue.check_capacity(1_000),
+            JupiterEarn::check_capacity(jupiter_ctx, 1_000)
+        );
+        assert!(venue.is_operational());
+        assert_eq!(venue.deposit(1_000), Ok(()));
+    }
+
+    #[test]
+    fn as_dyn_reports_paused_venues() {
+        let mut accounts = paused_deposit_accounts();
+        let views = views(&mut accounts);
+        let ctx = try_from_deposit_context(&views).unwrap();
+        assert!(!ctx.as_dyn().is_operational());
+    }
+}
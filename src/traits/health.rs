@@ -0,0 +1,71 @@
+pub use crate::util::BPS_DENOMINATOR;
+use pinocchio::error::ProgramError;
+
+/// Scale of the values reported in `HealthInfo` (18 decimals).
+pub const HEALTH_VALUE_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Safety margins of a lending position, normalized across protocols.
+///
+/// Values are denominated in the protocol's quote currency (USD for Kamino) and scaled
+/// by `HEALTH_VALUE_SCALE`. Ratios are in basis points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HealthInfo {
+    /// Current loan-to-value, in basis points
+    pub ltv: u64,
+    /// Loan-to-value at which the position becomes liquidatable, in basis points
+    pub liquidation_threshold: u64,
+    /// Market value of the borrowed assets
+    pub borrow_value: u128,
+    /// Market value of the deposited collateral
+    pub collateral_value: u128,
+}
+
+impl HealthInfo {
+    /// Returns `true` while the position is below its liquidation threshold.
+    pub fn is_healthy(&self) -> bool {
+        self.ltv < self.liquidation_threshold
+    }
+
+    /// Remaining LTV headroom before liquidation, in basis points.
+    pub fn margin(&self) -> u64 {
+        self.liquidation_threshold.saturating_sub(self.ltv)
+    }
+}
+
+/// Reader for the health of lending positions (Kamino obligations, etc.)
+///
+/// Health is computed from the last persisted position state. Protocols such as Kamino
+/// only update it on refresh, so refresh the position in the same transaction before
+/// relying on the result.
+pub trait Health<'info> {
+    /// Protocol-specific accounts identifying the position
+    type Accounts;
+
+    /// Returns the position's current safety margins
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    fn health(ctx: &Self::Accounts) -> Result<HealthInfo, ProgramError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(ltv: u64, liquidation_threshold: u64) -> HealthInfo {
+        HealthInfo {
+            ltv,
+            liquidation_threshold,
+            borrow_value: 0,
+            collateral_value: 0,
+        }
+    }
+
+    #[test]
+    fn positions_at_the_threshold_are_unhealthy() {
+        assert!(health(7_999, 8_000).is_healthy());
+        assert!(!health(8_000, 8_000).is_healthy());
+        assert_eq!(health(6_000, 8_000).margin(), 2_000);
+        assert_eq!(health(9_000, 8_000).margin(), 0);
+    }
+}
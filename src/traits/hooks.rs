@@ -0,0 +1,114 @@
+#[cfg(feature = "kamino")]
+use crate::LiquidateContext;
+use {crate::DepositContext, pinocchio::ProgramResult};
+
+/// Middleware invoked around beethoven operations by the `*_with_hooks` convenience functions.
+///
+/// Implement only the callbacks you need: every method defaults to a no-op, and the
+/// convenience functions are generic over the hooks type, so unused callbacks compile away.
+/// Returning an error from a `before_*` hook aborts the operation before any CPI (e.g. to
+/// enforce a pause flag); `after_*` hooks run once the CPI succeeded (e.g. for accounting or
+/// event emission).
+pub trait Hooks {
+    /// Called after the deposit context is parsed and before the deposit CPI
+    fn before_deposit(&self, _ctx: &DepositContext, _amount: u64) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Called after the deposit CPI succeeded
+    fn after_deposit(&self, _ctx: &DepositContext, _amount: u64) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Called after the liquidate context is parsed and before the liquidation CPI
+    #[cfg(feature = "kamino")]
+    fn before_liquidate(&self, _ctx: &LiquidateContext, _liquidity_amount: u64) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Called after the liquidation CPI succeeded
+    #[cfg(feature = "kamino")]
+    fn after_liquidate(&self, _ctx: &LiquidateContext, _liquidity_amount: u64) -> ProgramResult {
+        Ok(())
+    }
+}
+
+/// Hooks implementation that does nothing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoHooks;
+
+impl Hooks for NoHooks {}
+
+#[cfg(all(test, feature = "jupiter"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            BeethovenError, deposit_signed_with_hooks, deposit_with_hooks,
+            programs::jupiter::tests::deposit_accounts, test_utils::views,
+        },
+        core::cell::Cell,
+        pinocchio::error::ProgramError,
+    };
+
+    /// Records the calls it receives, optionally vetoing the deposit.
+    #[derive(Default)]
+    struct Recorder {
+        paused: bool,
+        before: Cell<u64>,
+        after: Cell<u64>,
+    }
+
+    impl Hooks for Recorder {
+        fn before_deposit(&self, _ctx: &DepositContext, amount: u64) -> ProgramResult {
+            if self.paused {
+                return Err(BeethovenError::VenuePaused.into());
+            }
+            self.before.set(amount);
+            Ok(())
+        }
+
+        fn after_deposit(&self, _ctx: &DepositContext, amount: u64) -> ProgramResult {
+            self.after.set(amount);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn runs_both_hooks_around_the_deposit() {
+        let mut accounts = deposit_accounts();
+        let hooks = Recorder::default();
+        assert_eq!(
+            deposit_signed_with_hooks(&views(&mut accounts), 7, &[], &hooks),
+            Ok(())
+        );
+        assert_eq!((hooks.before.get(), hooks.after.get()), (7, 7));
+        assert_eq!(
+            deposit_signed_with_hooks(&views(&mut accounts), 7, &[], &NoHooks),
+            Ok(())
+        );
+
+        let hooks = Recorder::default();
+        assert_eq!(deposit_with_hooks(&views(&mut accounts), 9, &hooks), Ok(()));
+        assert_eq!((hooks.before.get(), hooks.after.get()), (9, 9));
+    }
+
+    #[test]
+    fn a_failing_before_hook_aborts_the_deposit() {
+        let mut accounts = deposit_accounts();
+        let hooks = Recorder {
+            paused: true,
+            ..Recorder::default()
+        };
+        assert_eq!(
+            deposit_signed_with_hooks(&views(&mut accounts), 7, &[], &hooks),
+            Err(ProgramError::from(BeethovenError::VenuePaused))
+        );
+        assert_eq!(hooks.after.get(), 0);
+        assert_eq!(
+            deposit_with_hooks(&views(&mut accounts), 7, &hooks),
+            Err(ProgramError::from(BeethovenError::VenuePaused))
+        );
+        assert_eq!(hooks.after.get(), 0);
+    }
+}
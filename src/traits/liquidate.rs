@@ -0,0 +1,291 @@
+use pinocchio::{ProgramResult, cpi::Signer};
+#[cfg(feature = "kamino")]
+use {
+    crate::{
+        Hooks,
+        programs::{Detector, ProgramIdDetector, ProgramIds, Protocol, dispatch},
+    },
+    pinocchio::{AccountView, error::ProgramError},
+};
+
+/// Core trait for liquidating unhealthy lending positions (Kamino, etc.)
+///
+/// Lets liquidation keepers reuse beethoven's account parsing instead of building raw
+/// instructions. Each protocol implements this trait with its specific account requirements.
+pub trait Liquidate<'info> {
+    /// Protocol-specific accounts required for the liquidation CPI
+    type Accounts;
+
+    /// Execute a liquidation with PDA signing capability
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `liquidity_amount` - Amount of debt to repay on behalf of the position
+    /// * `min_received` - Minimum amount of collateral liquidity to receive
+    /// * `signer_seeds` - Seeds for PDA signing
+    fn liquidate_signed(
+        ctx: &Self::Accounts,
+        liquidity_amount: u64,
+        min_received: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult;
+
+    /// Execute a liquidation without signing (liquidator is direct signer)
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `liquidity_amount` - Amount of debt to repay on behalf of the position
+    /// * `min_received` - Minimum amount of collateral liquidity to receive
+    fn liquidate(ctx: &Self::Accounts, liquidity_amount: u64, min_received: u64) -> ProgramResult;
+}
+
+/// Typed context for liquidation operations, discriminated by protocol.
+// Only available when at least one enabled protocol supports liquidations.
+#[cfg(feature = "kamino")]
+pub enum LiquidateContext<'info> {
+    #[cfg(feature = "kamino")]
+    Kamino(crate::programs::kamino::KaminoLiquidateAccounts<'info>),
+}
+
+#[cfg(feature = "kamino")]
+impl LiquidateContext<'_> {
+    /// Protocol the context routes to.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            #[cfg(feature = "kamino")]
+            LiquidateContext::Kamino(_) => Protocol::Kamino,
+        }
+    }
+}
+
+#[cfg(feature = "kamino")]
+impl<'info> Liquidate<'info> for LiquidateContext<'info> {
+    type Accounts = Self;
+
+    fn liquidate_signed(
+        ctx: &Self::Accounts,
+        liquidity_amount: u64,
+        min_received: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        match ctx {
+            #[cfg(feature = "kamino")]
+            LiquidateContext::Kamino(kamino_ctx) => dispatch(Protocol::Kamino, || {
+                crate::programs::kamino::Kamino::liquidate_signed(
+                    kamino_ctx,
+                    liquidity_amount,
+                    min_received,
+                    signer_seeds,
+                )
+            }),
+        }
+    }
+
+    fn liquidate(ctx: &Self::Accounts, liquidity_amount: u64, min_received: u64) -> ProgramResult {
+        Self::liquidate_signed(ctx, liquidity_amount, min_received, &[])
+    }
+}
+
+/// Parses accounts and discriminates the protocol based on the first account.
+///
+/// See `try_from_deposit_context` for the discrimination rules.
+///
+/// # Returns
+/// * `Ok(LiquidateContext)` - Typed context for the detected protocol
+/// * `Err(ProgramError::NotEnoughAccountKeys)` - Empty account slice provided
+/// * `Err(ProgramError::InvalidAccountData)` - No matching protocol found or invalid account structure
+#[cfg(feature = "kamino")]
+pub fn try_from_liquidate_context<'info>(
+    accounts: &'info [AccountView],
+) -> Result<LiquidateContext<'info>, ProgramError> {
+    try_from_liquidate_context_with(&ProgramIdDetector, accounts, &ProgramIds::MAINNET)
+}
+
+/// Parses accounts using a custom `Detector` strategy to decide the protocol.
+#[cfg(feature = "kamino")]
+pub fn try_from_liquidate_context_with<'info, D: Detector>(
+    detector: &D,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<LiquidateContext<'info>, ProgramError> {
+    let protocol = detector.detect(accounts, ids)?;
+    try_from_liquidate_context_for_with_ids(protocol, accounts, ids)
+}
+
+/// Parses accounts for an explicitly selected protocol.
+///
+/// # Returns
+/// * `Ok(LiquidateContext)` - Typed context for the selected protocol
+/// * `Err(ProgramError::InvalidAccountData)` - Protocol does not support liquidations
+/// * `Err(ProgramError)` - Invalid account structure
+#[cfg(feature = "kamino")]
+pub fn try_from_liquidate_context_for_with_ids<'info>(
+    protocol: Protocol,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<LiquidateContext<'info>, ProgramError> {
+    let program_id = ids.get(protocol);
+
+    match protocol {
+        #[cfg(feature = "kamino")]
+        Protocol::Kamino => {
+            let ctx = crate::programs::kamino::KaminoLiquidateAccounts::try_from_with_program_id(
+                accounts, program_id,
+            )?;
+            Ok(LiquidateContext::Kamino(ctx))
+        }
+
+        #[allow(unreachable_patterns)]
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and executes the liquidation
+/// with PDA signing.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `liquidity_amount` - Amount of debt to repay on behalf of the position
+/// * `min_received` - Minimum amount of collateral liquidity to receive
+/// * `signer_seeds` - Seeds for PDA signing
+#[cfg(feature = "kamino")]
+pub fn liquidate_signed(
+    accounts: &[AccountView],
+    liquidity_amount: u64,
+    min_received: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ctx = try_from_liquidate_context(accounts)?;
+    LiquidateContext::liquidate_signed(&ctx, liquidity_amount, min_received, signer_seeds)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and executes the liquidation.
+#[cfg(feature = "kamino")]
+pub fn liquidate(
+    accounts: &[AccountView],
+    liquidity_amount: u64,
+    min_received: u64,
+) -> ProgramResult {
+    liquidate_signed(accounts, liquidity_amount, min_received, &[])
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and executes the liquidation
+/// with PDA signing, running `hooks` around the CPI.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `liquidity_amount` - Amount of debt to repay on behalf of the position
+/// * `min_received` - Minimum amount of collateral liquidity to receive
+/// * `signer_seeds` - Seeds for PDA signing
+/// * `hooks` - Callbacks run before and after the liquidation
+#[cfg(feature = "kamino")]
+pub fn liquidate_signed_with_hooks<H: Hooks>(
+    accounts: &[AccountView],
+    liquidity_amount: u64,
+    min_received: u64,
+    signer_seeds: &[Signer],
+    hooks: &H,
+) -> ProgramResult {
+    let ctx = try_from_liquidate_context(accounts)?;
+    hooks.before_liquidate(&ctx, liquidity_amount)?;
+    LiquidateContext::liquidate_signed(&ctx, liquidity_amount, min_received, signer_seeds)?;
+    hooks.after_liquidate(&ctx, liquidity_amount)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and executes the liquidation,
+/// running `hooks` around the CPI.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `liquidity_amount` - Amount of debt to repay on behalf of the position
+/// * `min_received` - Minimum amount of collateral liquidity to receive
+/// * `hooks` - Callbacks run before and after the liquidation
+#[cfg(feature = "kamino")]
+pub fn liquidate_with_hooks<H: Hooks>(
+    accounts: &[AccountView],
+    liquidity_amount: u64,
+    min_received: u64,
+    hooks: &H,
+) -> ProgramResult {
+    liquidate_signed_with_hooks(accounts, liquidity_amount, min_received, &[], hooks)
+}
+
+#[cfg(all(test, feature = "kamino"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            BeethovenError,
+            programs::kamino::{KAMINO_LEND_PROGRAM_ID, KAMINO_LIQUIDATE_ACCOUNTS},
+            test_utils::{TestAccount, address, views},
+        },
+        pinocchio::Address,
+        std::vec::Vec,
+    };
+
+    /// Vetoes liquidations above `max`.
+    struct MaxLiquidation {
+        max: u64,
+    }
+
+    impl Hooks for MaxLiquidation {
+        fn before_liquidate(
+            &self,
+            _ctx: &LiquidateContext,
+            liquidity_amount: u64,
+        ) -> ProgramResult {
+            if liquidity_amount > self.max {
+                return Err(BeethovenError::SlippageExceeded.into());
+            }
+            Ok(())
+        }
+    }
+
+    fn accounts(program: Address) -> Vec<TestAccount> {
+        (0..KAMINO_LIQUIDATE_ACCOUNTS.len() as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(program.clone()),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn routes_kamino_liquidations() {
+        let mut accounts = accounts(KAMINO_LEND_PROGRAM_ID);
+        let accounts = views(&mut accounts);
+        assert_eq!(
+            try_from_liquidate_context(&accounts)
+                .map(|ctx| ctx.protocol())
+                .ok(),
+            Some(Protocol::Kamino)
+        );
+        assert_eq!(liquidate(&accounts, 1_000, 900), Ok(()));
+
+        let mut unknown = self::accounts(address(9));
+        assert!(matches!(
+            try_from_liquidate_context(&views(&mut unknown)),
+            Err(ProgramError::InvalidAccountData)
+        ));
+    }
+
+    #[test]
+    fn hooks_can_veto_liquidations() {
+        let mut accounts = accounts(KAMINO_LEND_PROGRAM_ID);
+        let accounts = views(&mut accounts);
+        let hooks = MaxLiquidation { max: 1_000 };
+        assert_eq!(
+            liquidate_signed_with_hooks(&accounts, 1_000, 0, &[], &hooks),
+            Ok(())
+        );
+        assert_eq!(
+            liquidate_signed_with_hooks(&accounts, 1_001, 0, &[], &hooks),
+            Err(BeethovenError::SlippageExceeded.into())
+        );
+        assert_eq!(liquidate_with_hooks(&accounts, 1_000, 0, &hooks), Ok(()));
+        assert_eq!(
+            liquidate_with_hooks(&accounts, 1_001, 0, &hooks),
+            Err(BeethovenError::SlippageExceeded.into())
+        );
+    }
+}
@@ -1,2 +1,128 @@
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
 pub mod deposit;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
 pub use deposit::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod dyn_deposit;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use dyn_deposit::*;
+
+pub mod deposit_pair;
+pub use deposit_pair::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod preview;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use preview::*;
+
+pub mod health;
+pub use health::*;
+
+pub mod liquidate;
+pub use liquidate::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod capacity;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use capacity::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod balance;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use balance::*;
+
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub mod hooks;
+#[cfg(any(
+    feature = "drift",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub use hooks::*;
+
+pub mod swap;
+pub use swap::*;
+
+pub mod withdraw_request;
+pub use withdraw_request::*;
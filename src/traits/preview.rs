@@ -0,0 +1,63 @@
+use {crate::DepositContext, pinocchio::error::ProgramError};
+
+/// Quote trait computing expected operation outcomes from on-chain state, without any CPI.
+///
+/// Useful on-chain to enforce slippage bounds before executing, and off-chain to show
+/// users what they will receive.
+pub trait Preview<'info> {
+    /// Protocol-specific accounts required to compute the quote
+    type Accounts;
+
+    /// Returns the shares (cTokens, fTokens, ...) expected for depositing `amount`
+    ///
+    /// The quote is computed from the last persisted protocol state, so it can differ from
+    /// the executed deposit by rounding or by interest accrued since the last update.
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount` - Amount to deposit
+    fn preview_deposit(ctx: &Self::Accounts, amount: u64) -> Result<u64, ProgramError>;
+}
+
+impl<'info> Preview<'info> for DepositContext<'info> {
+    type Accounts = Self;
+
+    fn preview_deposit(ctx: &Self::Accounts, amount: u64) -> Result<u64, ProgramError> {
+        match ctx {
+            #[cfg(feature = "kamino")]
+            DepositContext::Kamino(kamino_ctx) => {
+                crate::programs::kamino::Kamino::preview_deposit(kamino_ctx, amount)
+            }
+
+            #[cfg(feature = "kamino")]
+            DepositContext::KaminoLiquidity(kamino_ctx) => {
+                crate::programs::kamino::KaminoLiquidity::preview_deposit(kamino_ctx, amount)
+            }
+
+            #[cfg(feature = "jupiter")]
+            DepositContext::Jupiter(jupiter_ctx) => {
+                crate::programs::jupiter::JupiterEarn::preview_deposit(jupiter_ctx, amount)
+            }
+
+            #[cfg(feature = "drift")]
+            DepositContext::DriftInsuranceFund(drift_ctx) => {
+                crate::programs::drift::DriftInsuranceFund::preview_deposit(drift_ctx, amount)
+            }
+
+            #[cfg(feature = "solayer")]
+            DepositContext::Solayer(solayer_ctx) => {
+                crate::programs::solayer::SolayerRestaking::preview_deposit(solayer_ctx, amount)
+            }
+
+            #[cfg(feature = "marinade")]
+            DepositContext::Marinade(marinade_ctx) => {
+                crate::programs::marinade::Marinade::preview_deposit(marinade_ctx, amount)
+            }
+
+            #[cfg(feature = "stake_pool")]
+            DepositContext::StakePool(stake_pool_ctx) => {
+                crate::programs::stake_pool::SplStakePool::preview_deposit(stake_pool_ctx, amount)
+            }
+        }
+    }
+}
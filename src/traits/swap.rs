@@ -0,0 +1,122 @@
+use {
+    crate::BeethovenError,
+    pinocchio::{
+        ProgramResult,
+        cpi::Signer,
+        sysvars::{Sysvar, clock::Clock},
+    },
+};
+
+/// Core trait for token swaps through AMMs and aggregators (Jupiter, Orca, etc.)
+///
+/// Every swap carries a minimum output and an optional deadline, so arbitrage and rebalance
+/// flows get both protections from the abstraction instead of re-implementing them.
+/// Implementations must call [`check_deadline`] before the CPI and fail with
+/// `BeethovenError::SlippageExceeded` when the output falls below `min_out` (or forward
+/// `min_out` to a protocol that enforces it).
+pub trait Swap<'info> {
+    /// Protocol-specific accounts required for the swap CPI
+    type Accounts;
+
+    /// Execute a swap with PDA signing capability
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount_in` - Amount of the input token to swap
+    /// * `min_out` - Minimum amount of the output token to receive
+    /// * `deadline_slot` - Last slot at which the swap may execute, if any
+    /// * `signer_seeds` - Seeds for PDA signing
+    fn swap_signed(
+        ctx: &Self::Accounts,
+        amount_in: u64,
+        min_out: u64,
+        deadline_slot: Option<u64>,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult;
+
+    /// Execute a swap without signing (user is direct signer)
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount_in` - Amount of the input token to swap
+    /// * `min_out` - Minimum amount of the output token to receive
+    /// * `deadline_slot` - Last slot at which the swap may execute, if any
+    fn swap(
+        ctx: &Self::Accounts,
+        amount_in: u64,
+        min_out: u64,
+        deadline_slot: Option<u64>,
+    ) -> ProgramResult;
+}
+
+/// Fails with `BeethovenError::DeadlineExceeded` when the current slot is past `deadline_slot`.
+///
+/// # Arguments
+/// * `deadline_slot` - Last slot at which the operation may execute; `None` disables the check
+///
+/// # Returns
+/// * `Ok(())` - No deadline, or the deadline has not passed
+/// * `Err(BeethovenError::DeadlineExceeded)` - The current slot is past the deadline
+/// * `Err(ProgramError)` - The Clock sysvar could not be read
+pub fn check_deadline(deadline_slot: Option<u64>) -> ProgramResult {
+    let Some(deadline_slot) = deadline_slot else {
+        return Ok(());
+    };
+
+    check_deadline_at(Some(deadline_slot), Clock::get()?.slot)
+}
+
+/// Same as `check_deadline`, but compares against `slot` instead of reading the Clock sysvar.
+///
+/// # Returns
+/// * `Ok(())` - No deadline, or `slot` is not past it
+/// * `Err(BeethovenError::DeadlineExceeded)` - `slot` is past the deadline
+pub fn check_deadline_at(deadline_slot: Option<u64>, slot: u64) -> ProgramResult {
+    match deadline_slot {
+        Some(deadline_slot) if slot > deadline_slot => Err(BeethovenError::DeadlineExceeded.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Fails with `BeethovenError::SlippageExceeded` when `amount_out` is below `min_out`.
+#[inline(always)]
+pub fn check_min_out(amount_out: u64, min_out: u64) -> ProgramResult {
+    if amount_out < min_out {
+        return Err(BeethovenError::SlippageExceeded.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, pinocchio::error::ProgramError};
+
+    #[test]
+    fn fails_below_the_minimum_output() {
+        assert_eq!(check_min_out(100, 100), Ok(()));
+        assert_eq!(
+            check_min_out(99, 100),
+            Err(ProgramError::from(BeethovenError::SlippageExceeded))
+        );
+    }
+
+    #[test]
+    fn no_deadline_skips_the_clock() {
+        assert_eq!(check_deadline(None), Ok(()));
+    }
+
+    #[test]
+    fn passes_until_the_deadline_slot() {
+        assert_eq!(check_deadline_at(Some(1_000), 999), Ok(()));
+        assert_eq!(check_deadline_at(Some(1_000), 1_000), Ok(()));
+        assert_eq!(check_deadline_at(None, u64::MAX), Ok(()));
+    }
+
+    #[test]
+    fn fails_past_the_deadline_slot() {
+        assert_eq!(
+            check_deadline_at(Some(1_000), 1_001),
+            Err(ProgramError::from(BeethovenError::DeadlineExceeded))
+        );
+    }
+}
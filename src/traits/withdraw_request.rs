@@ -0,0 +1,467 @@
+use pinocchio::{ProgramResult, cpi::Signer};
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+use {
+    crate::programs::{Detector, ProgramIdDetector, ProgramIds, Protocol, check_layout, dispatch},
+    pinocchio::{AccountView, error::ProgramError},
+};
+
+/// First phase of a two-phase exit: asks the venue to release funds later.
+///
+/// Implemented by venues that cannot pay out synchronously (delayed unstake, insurance fund
+/// cooldowns, ...). Funds are later collected with [`ClaimWithdraw`].
+pub trait RequestWithdraw<'info> {
+    /// Protocol-specific accounts required for the request CPI
+    type Accounts;
+
+    /// Request a withdrawal with PDA signing capability
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount` - Amount of the position to withdraw, in the venue's share or receipt units
+    /// * `signer_seeds` - Seeds for PDA signing
+    fn request_withdraw_signed(
+        ctx: &Self::Accounts,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult;
+
+    /// Request a withdrawal without signing (owner is direct signer)
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `amount` - Amount of the position to withdraw, in the venue's share or receipt units
+    fn request_withdraw(ctx: &Self::Accounts, amount: u64) -> ProgramResult;
+}
+
+/// Second phase of a two-phase exit: collects funds released by a [`RequestWithdraw`].
+pub trait ClaimWithdraw<'info> {
+    /// Protocol-specific accounts required for the claim CPI
+    type Accounts;
+
+    /// Claim a matured withdrawal with PDA signing capability
+    ///
+    /// # Arguments
+    /// * `ctx` - Protocol-specific account context
+    /// * `signer_seeds` - Seeds for PDA signing
+    fn claim_withdraw_signed(ctx: &Self::Accounts, signer_seeds: &[Signer]) -> ProgramResult;
+
+    /// Claim a matured withdrawal without signing (owner is direct signer)
+    fn claim_withdraw(ctx: &Self::Accounts) -> ProgramResult;
+}
+
+/// Typed context for withdrawal requests, discriminated by protocol.
+// Only available when at least one enabled protocol supports two-phase exits.
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub enum RequestWithdrawContext<'info> {
+    #[cfg(feature = "drift")]
+    DriftInsuranceFund(crate::programs::drift::DriftInsuranceFundStakeAccounts<'info>),
+
+    #[cfg(feature = "jito")]
+    Jito(crate::programs::jito::JitoEnqueueWithdrawalAccounts<'info>),
+
+    #[cfg(feature = "marinade")]
+    Marinade(crate::programs::marinade::MarinadeOrderUnstakeAccounts<'info>),
+}
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+impl RequestWithdrawContext<'_> {
+    /// Protocol the context routes to.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            #[cfg(feature = "drift")]
+            RequestWithdrawContext::DriftInsuranceFund(_) => Protocol::Drift,
+
+            #[cfg(feature = "jito")]
+            RequestWithdrawContext::Jito(_) => Protocol::Jito,
+
+            #[cfg(feature = "marinade")]
+            RequestWithdrawContext::Marinade(_) => Protocol::Marinade,
+        }
+    }
+}
+
+/// Typed context for withdrawal claims, discriminated by protocol.
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub enum ClaimWithdrawContext<'info> {
+    #[cfg(feature = "drift")]
+    DriftInsuranceFund(crate::programs::drift::DriftInsuranceFundStakeAccounts<'info>),
+
+    #[cfg(feature = "jito")]
+    Jito(crate::programs::jito::JitoBurnWithdrawalTicketAccounts<'info>),
+
+    #[cfg(feature = "marinade")]
+    Marinade(crate::programs::marinade::MarinadeClaimAccounts<'info>),
+}
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+impl ClaimWithdrawContext<'_> {
+    /// Protocol the context routes to.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            #[cfg(feature = "drift")]
+            ClaimWithdrawContext::DriftInsuranceFund(_) => Protocol::Drift,
+
+            #[cfg(feature = "jito")]
+            ClaimWithdrawContext::Jito(_) => Protocol::Jito,
+
+            #[cfg(feature = "marinade")]
+            ClaimWithdrawContext::Marinade(_) => Protocol::Marinade,
+        }
+    }
+}
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+impl<'info> RequestWithdraw<'info> for RequestWithdrawContext<'info> {
+    type Accounts = Self;
+
+    fn request_withdraw_signed(
+        ctx: &Self::Accounts,
+        amount: u64,
+        signer_seeds: &[Signer],
+    ) -> ProgramResult {
+        match ctx {
+            #[cfg(feature = "drift")]
+            RequestWithdrawContext::DriftInsuranceFund(drift_ctx) => {
+                dispatch(Protocol::Drift, || {
+                    crate::programs::drift::DriftInsuranceFund::request_withdraw_signed(
+                        drift_ctx,
+                        amount,
+                        signer_seeds,
+                    )
+                })
+            }
+
+            #[cfg(feature = "jito")]
+            RequestWithdrawContext::Jito(jito_ctx) => dispatch(Protocol::Jito, || {
+                crate::programs::jito::JitoVault::request_withdraw_signed(
+                    jito_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
+
+            #[cfg(feature = "marinade")]
+            RequestWithdrawContext::Marinade(marinade_ctx) => dispatch(Protocol::Marinade, || {
+                crate::programs::marinade::Marinade::request_withdraw_signed(
+                    marinade_ctx,
+                    amount,
+                    signer_seeds,
+                )
+            }),
+        }
+    }
+
+    fn request_withdraw(ctx: &Self::Accounts, amount: u64) -> ProgramResult {
+        Self::request_withdraw_signed(ctx, amount, &[])
+    }
+}
+
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+impl<'info> ClaimWithdraw<'info> for ClaimWithdrawContext<'info> {
+    type Accounts = Self;
+
+    fn claim_withdraw_signed(ctx: &Self::Accounts, signer_seeds: &[Signer]) -> ProgramResult {
+        match ctx {
+            #[cfg(feature = "drift")]
+            ClaimWithdrawContext::DriftInsuranceFund(drift_ctx) => {
+                dispatch(Protocol::Drift, || {
+                    crate::programs::drift::DriftInsuranceFund::claim_withdraw_signed(
+                        drift_ctx,
+                        signer_seeds,
+                    )
+                })
+            }
+
+            #[cfg(feature = "jito")]
+            ClaimWithdrawContext::Jito(jito_ctx) => dispatch(Protocol::Jito, || {
+                crate::programs::jito::JitoVault::claim_withdraw_signed(jito_ctx, signer_seeds)
+            }),
+
+            #[cfg(feature = "marinade")]
+            ClaimWithdrawContext::Marinade(marinade_ctx) => dispatch(Protocol::Marinade, || {
+                crate::programs::marinade::Marinade::claim_withdraw_signed(
+                    marinade_ctx,
+                    signer_seeds,
+                )
+            }),
+        }
+    }
+
+    fn claim_withdraw(ctx: &Self::Accounts) -> ProgramResult {
+        Self::claim_withdraw_signed(ctx, &[])
+    }
+}
+
+/// Parses accounts for a withdrawal request, discriminating the protocol from the first
+/// account.
+///
+/// # Returns
+/// * `Ok(RequestWithdrawContext)` - Typed context for the detected protocol
+/// * `Err(ProgramError::InvalidAccountData)` - Protocol does not support two-phase exits
+/// * `Err(ProgramError)` - Invalid account structure
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn try_from_request_withdraw_context<'info>(
+    accounts: &'info [AccountView],
+) -> Result<RequestWithdrawContext<'info>, ProgramError> {
+    try_from_request_withdraw_context_with(&ProgramIdDetector, accounts, &ProgramIds::MAINNET)
+}
+
+/// Parses withdrawal request accounts using a custom `Detector` strategy.
+///
+/// # Notes
+/// * The slice is first run through `check_layout`, so short slices and aliased writable
+///   accounts fail with a logged account index before the CPI.
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn try_from_request_withdraw_context_with<'info, D: Detector>(
+    detector: &D,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<RequestWithdrawContext<'info>, ProgramError> {
+    let protocol = detector.detect(accounts, ids)?;
+    let program_id = ids.get(protocol);
+
+    match protocol {
+        #[cfg(feature = "drift")]
+        Protocol::Drift => {
+            check_layout(
+                crate::programs::drift::DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::drift::DriftInsuranceFundStakeAccounts::try_from_with_program_id(
+                    accounts, program_id,
+                )?;
+            Ok(RequestWithdrawContext::DriftInsuranceFund(ctx))
+        }
+
+        #[cfg(feature = "jito")]
+        Protocol::Jito => {
+            check_layout(
+                crate::programs::jito::JITO_ENQUEUE_WITHDRAWAL_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::jito::JitoEnqueueWithdrawalAccounts::try_from_with_program_id(
+                    accounts, program_id,
+                )?;
+            Ok(RequestWithdrawContext::Jito(ctx))
+        }
+
+        #[cfg(feature = "marinade")]
+        Protocol::Marinade => {
+            check_layout(
+                crate::programs::marinade::MARINADE_ORDER_UNSTAKE_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::marinade::MarinadeOrderUnstakeAccounts::try_from_with_program_id(
+                    accounts, program_id,
+                )?;
+            Ok(RequestWithdrawContext::Marinade(ctx))
+        }
+
+        #[allow(unreachable_patterns)]
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Parses accounts for a withdrawal claim, discriminating the protocol from the first account.
+///
+/// # Returns
+/// * `Ok(ClaimWithdrawContext)` - Typed context for the detected protocol
+/// * `Err(ProgramError::InvalidAccountData)` - Protocol does not support two-phase exits
+/// * `Err(ProgramError)` - Invalid account structure
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn try_from_claim_withdraw_context<'info>(
+    accounts: &'info [AccountView],
+) -> Result<ClaimWithdrawContext<'info>, ProgramError> {
+    try_from_claim_withdraw_context_with(&ProgramIdDetector, accounts, &ProgramIds::MAINNET)
+}
+
+/// Parses withdrawal claim accounts using a custom `Detector` strategy.
+///
+/// # Notes
+/// * The slice is first run through `check_layout`, so short slices and aliased writable
+///   accounts fail with a logged account index before the CPI.
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn try_from_claim_withdraw_context_with<'info, D: Detector>(
+    detector: &D,
+    accounts: &'info [AccountView],
+    ids: &ProgramIds,
+) -> Result<ClaimWithdrawContext<'info>, ProgramError> {
+    let protocol = detector.detect(accounts, ids)?;
+    let program_id = ids.get(protocol);
+
+    match protocol {
+        #[cfg(feature = "drift")]
+        Protocol::Drift => {
+            check_layout(
+                crate::programs::drift::DRIFT_INSURANCE_FUND_STAKE_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::drift::DriftInsuranceFundStakeAccounts::try_from_with_program_id(
+                    accounts, program_id,
+                )?;
+            Ok(ClaimWithdrawContext::DriftInsuranceFund(ctx))
+        }
+
+        #[cfg(feature = "jito")]
+        Protocol::Jito => {
+            check_layout(
+                crate::programs::jito::JITO_BURN_WITHDRAWAL_TICKET_ACCOUNTS,
+                accounts,
+            )?;
+            let ctx =
+                crate::programs::jito::JitoBurnWithdrawalTicketAccounts::try_from_with_program_id(
+                    accounts, program_id,
+                )?;
+            Ok(ClaimWithdrawContext::Jito(ctx))
+        }
+
+        #[cfg(feature = "marinade")]
+        Protocol::Marinade => {
+            check_layout(crate::programs::marinade::MARINADE_CLAIM_ACCOUNTS, accounts)?;
+            let ctx = crate::programs::marinade::MarinadeClaimAccounts::try_from_with_program_id(
+                accounts, program_id,
+            )?;
+            Ok(ClaimWithdrawContext::Marinade(ctx))
+        }
+
+        #[allow(unreachable_patterns)]
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and requests a withdrawal
+/// with PDA signing.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `amount` - Amount of the position to withdraw
+/// * `signer_seeds` - Seeds for PDA signing
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn request_withdraw_signed(
+    accounts: &[AccountView],
+    amount: u64,
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    let ctx = try_from_request_withdraw_context(accounts)?;
+    RequestWithdrawContext::request_withdraw_signed(&ctx, amount, signer_seeds)
+}
+
+/// Convenience function: Parses accounts, discriminates protocol, and claims a matured
+/// withdrawal with PDA signing.
+///
+/// # Arguments
+/// * `accounts` - Slice of accounts where the first account determines the protocol
+/// * `signer_seeds` - Seeds for PDA signing
+#[cfg(any(feature = "drift", feature = "jito", feature = "marinade"))]
+pub fn claim_withdraw_signed(accounts: &[AccountView], signer_seeds: &[Signer]) -> ProgramResult {
+    let ctx = try_from_claim_withdraw_context(accounts)?;
+    ClaimWithdrawContext::claim_withdraw_signed(&ctx, signer_seeds)
+}
+
+#[cfg(all(test, any(feature = "drift", feature = "jito", feature = "marinade")))]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        pinocchio::Address,
+        std::vec::Vec,
+    };
+
+    /// `len` accounts led by `program`.
+    fn accounts(program: Address, len: usize) -> Vec<TestAccount> {
+        (0..len as u8)
+            .map(|i| match i {
+                0 => TestAccount::empty(program.clone()),
+                _ => TestAccount::empty(address(i)),
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "marinade")]
+    #[test]
+    fn routes_marinade_unstake_orders_and_claims() {
+        use crate::programs::marinade::MARINADE_PROGRAM_ID;
+
+        let mut request = accounts(MARINADE_PROGRAM_ID, 9);
+        let request = views(&mut request);
+        let ctx = try_from_request_withdraw_context(&request).unwrap();
+        assert_eq!(ctx.protocol(), Protocol::Marinade);
+
+        let mut claim = accounts(MARINADE_PROGRAM_ID, 7);
+        let claim = views(&mut claim);
+        let ctx = try_from_claim_withdraw_context(&claim).unwrap();
+        assert_eq!(ctx.protocol(), Protocol::Marinade);
+    }
+
+    #[cfg(feature = "marinade")]
+    #[test]
+    fn rejects_mis_ordered_marinade_accounts_before_the_cpi() {
+        use crate::{BeethovenError, programs::marinade::MARINADE_PROGRAM_ID};
+
+        // The mSOL source passed again as the new ticket account.
+        let mut request = accounts(MARINADE_PROGRAM_ID, 9);
+        request[5] = TestAccount::empty(address(3));
+        assert_eq!(
+            try_from_request_withdraw_context(&views(&mut request)).err(),
+            Some(BeethovenError::DuplicateAccount.into())
+        );
+
+        // The ticket passed again as the SOL destination.
+        let mut claim = accounts(MARINADE_PROGRAM_ID, 7);
+        claim[4] = TestAccount::empty(address(3));
+        assert_eq!(
+            try_from_claim_withdraw_context(&views(&mut claim)).err(),
+            Some(BeethovenError::DuplicateAccount.into())
+        );
+    }
+
+    #[cfg(feature = "jito")]
+    #[test]
+    fn routes_jito_withdrawal_tickets() {
+        use crate::programs::jito::JITO_VAULT_PROGRAM_ID;
+
+        let mut request = accounts(JITO_VAULT_PROGRAM_ID, 10);
+        let request = views(&mut request);
+        let ctx = try_from_request_withdraw_context(&request).unwrap();
+        assert_eq!(ctx.protocol(), Protocol::Jito);
+
+        let mut claim = accounts(JITO_VAULT_PROGRAM_ID, 13);
+        let claim = views(&mut claim);
+        let ctx = try_from_claim_withdraw_context(&claim).unwrap();
+        assert_eq!(ctx.protocol(), Protocol::Jito);
+    }
+
+    #[cfg(feature = "drift")]
+    #[test]
+    fn routes_drift_cooldowns_with_one_layout() {
+        use crate::programs::drift::DRIFT_PROGRAM_ID;
+
+        let mut accounts = accounts(DRIFT_PROGRAM_ID, 11);
+        let accounts = views(&mut accounts);
+        let request = try_from_request_withdraw_context(&accounts).unwrap();
+        let claim = try_from_claim_withdraw_context(&accounts).unwrap();
+        assert_eq!(
+            (request.protocol(), claim.protocol()),
+            (Protocol::Drift, Protocol::Drift)
+        );
+    }
+
+    #[cfg(feature = "kamino")]
+    #[test]
+    fn rejects_venues_without_two_phase_exits() {
+        let mut accounts = accounts(crate::programs::kamino::KAMINO_LEND_PROGRAM_ID, 20);
+        let accounts = views(&mut accounts);
+        assert!(matches!(
+            try_from_request_withdraw_context(&accounts),
+            Err(ProgramError::InvalidAccountData)
+        ));
+        assert!(matches!(
+            try_from_claim_withdraw_context(&accounts),
+            Err(ProgramError::InvalidAccountData)
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+use {
+    super::{Rounding, div, to_u64},
+    pinocchio::error::ProgramError,
+};
+
+/// A raw token amount tagged with its mint's decimals.
+///
+/// Used to move amounts between mints and oracle prices with different precisions without
+/// hand-rolled scaling. All conversions are checked and round down unless stated otherwise.
+///
+/// # Example
+/// ```ignore
+/// let usdc = Amount::new(1_500_000, 6);
+/// let scaled = usdc.rescale(9)?; // 1_500_000_000
+/// let value = usdc.value_at(price.price as u128, price.exponent, 6)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Amount {
+    /// Amount in the smallest unit of the mint
+    pub raw: u64,
+    /// Decimals of the mint
+    pub decimals: u8,
+}
+
+impl Amount {
+    pub const fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Converts the amount to `decimals`, rounding down when precision is lost.
+    ///
+    /// # Returns
+    /// * `Ok(Amount)` - The rescaled amount
+    /// * `Err(ProgramError::ArithmeticOverflow)` - The result does not fit in a `u64`
+    pub fn rescale(self, decimals: u8) -> Result<Self, ProgramError> {
+        let raw = scale(
+            self.raw as u128,
+            decimals as i32 - self.decimals as i32,
+            Rounding::Down,
+        )?;
+        Ok(Self::new(to_u64(raw)?, decimals))
+    }
+
+    /// Converts the amount to `decimals`, rounding up when precision is lost.
+    ///
+    /// Use when the amount is owed by the caller, so truncation never favors them.
+    pub fn rescale_ceil(self, decimals: u8) -> Result<Self, ProgramError> {
+        let raw = scale(
+            self.raw as u128,
+            decimals as i32 - self.decimals as i32,
+            Rounding::Up,
+        )?;
+        Ok(Self::new(to_u64(raw)?, decimals))
+    }
+
+    /// Adds two amounts of the same precision.
+    ///
+    /// # Returns
+    /// * `Ok(Amount)` - The sum
+    /// * `Err(ProgramError::InvalidArgument)` - The amounts have different decimals
+    /// * `Err(ProgramError::ArithmeticOverflow)` - The sum overflows
+    pub fn checked_add(self, other: Self) -> Result<Self, ProgramError> {
+        if self.decimals != other.decimals {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let raw = self
+            .raw
+            .checked_add(other.raw)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    /// Subtracts an amount of the same precision.
+    ///
+    /// # Returns
+    /// * `Ok(Amount)` - The difference
+    /// * `Err(ProgramError::InvalidArgument)` - The amounts have different decimals
+    /// * `Err(ProgramError::ArithmeticOverflow)` - `other` is larger than `self`
+    pub fn checked_sub(self, other: Self) -> Result<Self, ProgramError> {
+        if self.decimals != other.decimals {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let raw = self
+            .raw
+            .checked_sub(other.raw)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    /// Values the amount at `price * 10^exponent` per whole token, rounding down.
+    ///
+    /// # Arguments
+    /// * `price` - Price mantissa (e.g. an oracle price, made non-negative)
+    /// * `exponent` - Price exponent (e.g. `-8` for Pyth USD feeds)
+    /// * `decimals` - Decimals of the returned value
+    ///
+    /// # Returns
+    /// * `Ok(Amount)` - The value in the quote currency, with `decimals` decimals
+    /// * `Err(ProgramError::ArithmeticOverflow)` - The value does not fit in a `u64`
+    pub fn value_at(self, price: u128, exponent: i32, decimals: u8) -> Result<Self, ProgramError> {
+        let value = (self.raw as u128)
+            .checked_mul(price)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let value = scale(
+            value,
+            decimals as i32 + exponent - self.decimals as i32,
+            Rounding::Down,
+        )?;
+        Ok(Self::new(to_u64(value)?, decimals))
+    }
+}
+
+/// Multiplies `value` by `10^exponent`, dividing with `rounding` for negative exponents.
+fn scale(value: u128, exponent: i32, rounding: Rounding) -> Result<u128, ProgramError> {
+    let factor = 10u128
+        .checked_pow(exponent.unsigned_abs())
+        .ok_or(ProgramError::ArithmeticOverflow);
+
+    if exponent >= 0 {
+        return value
+            .checked_mul(factor?)
+            .ok_or(ProgramError::ArithmeticOverflow);
+    }
+
+    // Dividing by more than 10^38 always truncates a u128 to zero.
+    let Ok(factor) = factor else {
+        return Ok((rounding == Rounding::Up && value > 0) as u128);
+    };
+    div(value, factor, rounding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rescale_scales_up_and_truncates_down() {
+        assert_eq!(
+            Amount::new(1_500_000, 6).rescale(9),
+            Ok(Amount::new(1_500_000_000, 9))
+        );
+        assert_eq!(
+            Amount::new(1_999_999_999, 9).rescale(6),
+            Ok(Amount::new(1_999_999, 6))
+        );
+        assert_eq!(
+            Amount::new(1_999_999_999, 9).rescale_ceil(6),
+            Ok(Amount::new(2_000_000, 6))
+        );
+        assert_eq!(
+            Amount::new(u64::MAX, 0).rescale(1),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn rescale_past_u128_precision_rounds_to_zero_or_one() {
+        assert_eq!(Amount::new(5, 255).rescale(0), Ok(Amount::new(0, 0)));
+        assert_eq!(Amount::new(5, 255).rescale_ceil(0), Ok(Amount::new(1, 0)));
+        assert_eq!(Amount::new(0, 255).rescale_ceil(0), Ok(Amount::new(0, 0)));
+    }
+
+    #[test]
+    fn arithmetic_requires_matching_decimals() {
+        let one = Amount::new(1_000_000, 6);
+        assert_eq!(one.checked_add(one), Ok(Amount::new(2_000_000, 6)));
+        assert_eq!(one.checked_sub(one), Ok(Amount::new(0, 6)));
+        assert_eq!(
+            one.checked_add(Amount::new(1, 9)),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            Amount::new(0, 6).checked_sub(one),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            Amount::new(u64::MAX, 6).checked_add(one),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn value_at_applies_the_price_exponent() {
+        // 2.5 SOL at $150.12345678 (Pyth, exponent -8) is $375.308641 in USDC decimals.
+        let sol = Amount::new(2_500_000_000, 9);
+        assert_eq!(
+            sol.value_at(15_012_345_678, -8, 6),
+            Ok(Amount::new(375_308_641, 6))
+        );
+        // A positive exponent multiplies.
+        assert_eq!(
+            Amount::new(3, 0).value_at(2, 3, 0),
+            Ok(Amount::new(6_000, 0))
+        );
+    }
+}
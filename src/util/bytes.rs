@@ -0,0 +1,112 @@
+// Which helpers are used depends on the enabled protocol features.
+#![allow(dead_code)]
+
+use pinocchio::{Address, error::ProgramError};
+
+/// Offset of the `amount` field in an SPL Token (or Token-2022) account.
+pub(crate) const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Offset of the `supply` field in an SPL Token (or Token-2022) mint.
+pub(crate) const MINT_SUPPLY_OFFSET: usize = 36;
+
+/// Reads a little-endian `u16` at `offset`.
+#[inline(always)]
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    Ok(u16::from_le_bytes(read_array(data, offset)?))
+}
+
+/// Reads a little-endian `u64` at `offset`.
+#[inline(always)]
+pub(crate) fn read_u64(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    Ok(u64::from_le_bytes(read_array(data, offset)?))
+}
+
+/// Reads a little-endian `u128` at `offset`.
+#[inline(always)]
+pub(crate) fn read_u128(data: &[u8], offset: usize) -> Result<u128, ProgramError> {
+    Ok(u128::from_le_bytes(read_array(data, offset)?))
+}
+
+/// Reads a 32-byte address at `offset`.
+#[inline(always)]
+pub(crate) fn read_address(data: &[u8], offset: usize) -> Result<Address, ProgramError> {
+    Ok(Address::new_from_array(read_array(data, offset)?))
+}
+
+/// Reads `N` bytes at `offset`, failing with `AccountDataTooSmall` when out of bounds.
+#[inline(always)]
+pub(crate) fn read_array<const N: usize>(
+    data: &[u8],
+    offset: usize,
+) -> Result<[u8; N], ProgramError> {
+    data.get(offset..)
+        .and_then(|data| data.get(..N))
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::AccountDataTooSmall)
+}
+
+/// Checks that `data` starts with the expected 8-byte account discriminator.
+#[inline(always)]
+pub(crate) fn check_discriminator(
+    data: &[u8],
+    discriminator: &[u8; 8],
+) -> Result<(), ProgramError> {
+    if read_array::<8>(data, 0)? != *discriminator {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_little_endian_fields_at_their_offset() {
+        let mut data = [0u8; 59];
+        data[1..3].copy_from_slice(&513u16.to_le_bytes());
+        data[3..11].copy_from_slice(&u64::MAX.to_le_bytes());
+        data[11..27].copy_from_slice(&(u128::from(u64::MAX) + 1).to_le_bytes());
+        data[27..59].fill(7);
+
+        assert_eq!(read_u16(&data, 1), Ok(513));
+        assert_eq!(read_u64(&data, 3), Ok(u64::MAX));
+        assert_eq!(read_u128(&data, 11), Ok(u128::from(u64::MAX) + 1));
+        assert_eq!(
+            read_address(&data, 27),
+            Ok(Address::new_from_array([7; 32]))
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_reads_fail() {
+        let data = [0u8; 8];
+        assert_eq!(read_u64(&data, 0), Ok(0));
+        assert_eq!(read_u64(&data, 1), Err(ProgramError::AccountDataTooSmall));
+        assert_eq!(
+            read_u16(&data, usize::MAX - 1),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+        assert_eq!(
+            read_address(&data, 0),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn checks_the_discriminator() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        assert_eq!(
+            check_discriminator(&data, &[1, 2, 3, 4, 5, 6, 7, 8]),
+            Ok(())
+        );
+        assert_eq!(
+            check_discriminator(&data, &[8; 8]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            check_discriminator(&data[..7], &[1, 2, 3, 4, 5, 6, 7, 8]),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use {
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, ProgramResult,
+        cpi::{MAX_STATIC_CPI_ACCOUNTS, Signer, invoke_signed, invoke_signed_with_bounds},
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+/// Invokes `instruction` with `remaining` appended after its fixed accounts.
+///
+/// Trailing accounts keep the signer/writable flags they were passed with. The combined
+/// account list is stack allocated and bounded by `MAX_STATIC_CPI_ACCOUNTS`.
+///
+/// # Returns
+/// * `Ok(())` - CPI executed successfully
+/// * `Err(ProgramError::InvalidArgument)` - More than `MAX_STATIC_CPI_ACCOUNTS` accounts in total
+/// * `Err(ProgramError)` - CPI failed
+pub(crate) fn invoke_signed_with_remaining<const ACCOUNTS: usize>(
+    instruction: &InstructionView,
+    account_infos: &[&AccountView; ACCOUNTS],
+    remaining: &[AccountView],
+    signer_seeds: &[Signer],
+) -> ProgramResult {
+    if remaining.is_empty() {
+        return invoke_signed(instruction, account_infos, signer_seeds);
+    }
+
+    let len = ACCOUNTS + remaining.len();
+    if len > MAX_STATIC_CPI_ACCOUNTS || instruction.accounts.len() != ACCOUNTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut accounts = MaybeUninit::<[InstructionAccount; MAX_STATIC_CPI_ACCOUNTS]>::uninit();
+    let accounts_ptr = accounts.as_mut_ptr() as *mut InstructionAccount;
+
+    unsafe {
+        for (i, account) in instruction.accounts.iter().enumerate() {
+            core::ptr::write(accounts_ptr.add(i), account.clone());
+        }
+
+        for (i, account) in remaining.iter().enumerate() {
+            core::ptr::write(
+                accounts_ptr.add(ACCOUNTS + i),
+                InstructionAccount::from(account),
+            );
+        }
+    }
+
+    let accounts_slice = unsafe { core::slice::from_raw_parts(accounts_ptr, len) };
+
+    // Unused slots are filled with an existing account; only the first `len` are passed on
+    let mut all_account_infos = [&remaining[0]; MAX_STATIC_CPI_ACCOUNTS];
+    all_account_infos[..ACCOUNTS].copy_from_slice(account_infos);
+
+    for (i, account) in remaining.iter().enumerate() {
+        all_account_infos[ACCOUNTS + i] = account;
+    }
+
+    let instruction = InstructionView {
+        program_id: instruction.program_id,
+        accounts: accounts_slice,
+        data: instruction.data,
+    };
+
+    invoke_signed_with_bounds::<MAX_STATIC_CPI_ACCOUNTS>(
+        &instruction,
+        &all_account_infos[..len],
+        signer_seeds,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address, views},
+        std::vec::Vec,
+    };
+
+    fn invoke(fixed_metas: usize, remaining: usize) -> ProgramResult {
+        let program = address(0);
+        let mut fixed = TestAccount::empty(address(1));
+        let fixed = fixed.view();
+        let mut remaining: Vec<TestAccount> = (0..remaining)
+            .map(|i| TestAccount::empty(address(2 + i as u8)))
+            .collect();
+        let metas = [
+            InstructionAccount::writable(fixed.address()),
+            InstructionAccount::writable(fixed.address()),
+        ];
+        let instruction = InstructionView {
+            program_id: &program,
+            accounts: &metas[..fixed_metas],
+            data: &[],
+        };
+        invoke_signed_with_remaining(&instruction, &[&fixed], &views(&mut remaining), &[])
+    }
+
+    #[test]
+    fn appends_remaining_accounts_up_to_the_static_limit() {
+        assert_eq!(invoke(1, 0), Ok(()));
+        assert_eq!(invoke(1, MAX_STATIC_CPI_ACCOUNTS - 1), Ok(()));
+        assert_eq!(
+            invoke(1, MAX_STATIC_CPI_ACCOUNTS),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn rejects_metas_not_matching_the_account_infos() {
+        assert_eq!(invoke(2, 1), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,83 @@
+// Which helpers are used depends on the enabled features.
+#![allow(dead_code)]
+
+/// Stack buffer assembling a log line without `alloc` or `format!`.
+///
+/// Input beyond the buffer capacity is truncated. Logging is a no-op off-chain.
+pub(crate) struct LogLine {
+    buffer: [u8; 128],
+    len: usize,
+}
+
+impl LogLine {
+    pub(crate) const fn new() -> Self {
+        Self {
+            buffer: [0; 128],
+            len: 0,
+        }
+    }
+
+    /// Appends raw bytes.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> &mut Self {
+        let count = bytes.len().min(self.buffer.len() - self.len);
+        self.buffer[self.len..self.len + count].copy_from_slice(&bytes[..count]);
+        self.len += count;
+        self
+    }
+
+    /// Appends the decimal representation of `value`.
+    pub(crate) fn push_u64(&mut self, value: u64) -> &mut Self {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        let mut value = value;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.push(&digits[start..])
+    }
+
+    /// Writes the line to the program logs.
+    pub(crate) fn log(&self) {
+        #[cfg(any(target_os = "solana", target_arch = "bpf"))]
+        // SAFETY: `buffer[..len]` is initialized and outlives the syscall.
+        unsafe {
+            pinocchio::syscalls::sol_log_(self.buffer.as_ptr(), self.len as u64);
+        }
+
+        #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
+        core::hint::black_box(&self.buffer[..self.len]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers_in_decimal() {
+        let mut line = LogLine::new();
+        line.push(b"account #")
+            .push_u64(0)
+            .push(b", amount ")
+            .push_u64(u64::MAX);
+        assert_eq!(
+            &line.buffer[..line.len],
+            b"account #0, amount 18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn truncates_at_the_buffer_capacity() {
+        let mut line = LogLine::new();
+        line.push(&[b'a'; 120])
+            .push_u64(1_234_567_890)
+            .push(b"dropped");
+        assert_eq!(line.len, 128);
+        assert_eq!(&line.buffer[120..], b"12345678");
+    }
+}
@@ -0,0 +1,220 @@
+use pinocchio::error::ProgramError;
+
+/// Basis points denominator used by ratios and fees.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Rounding direction of a fixed-point operation.
+///
+/// Round against the party the result is paid to: `Down` for shares minted or assets paid
+/// out to a user, `Up` for amounts the user owes, so rounding never drains the venue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round toward zero
+    Down,
+    /// Round away from zero
+    Up,
+}
+
+/// Computes `value * numerator / denominator` on `u128` with an explicit rounding direction.
+///
+/// # Returns
+/// * `Ok(u128)` - The rounded quotient
+/// * `Err(ProgramError::ArithmeticOverflow)` - The product overflows, or `denominator` is 0
+pub fn mul_div(
+    value: u128,
+    numerator: u128,
+    denominator: u128,
+    rounding: Rounding,
+) -> Result<u128, ProgramError> {
+    let product = value
+        .checked_mul(numerator)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    div(product, denominator, rounding)
+}
+
+/// Same as `mul_div`, for `u64` operands, checking that the result fits in a `u64`.
+///
+/// The product is computed on `u128`, so only the final quotient can overflow.
+pub fn mul_div_u64(
+    value: u64,
+    numerator: u64,
+    denominator: u64,
+    rounding: Rounding,
+) -> Result<u64, ProgramError> {
+    to_u64(mul_div(
+        value as u128,
+        numerator as u128,
+        denominator as u128,
+        rounding,
+    )?)
+}
+
+/// Computes `value / denominator` with an explicit rounding direction.
+///
+/// # Returns
+/// * `Ok(u128)` - The rounded quotient
+/// * `Err(ProgramError::ArithmeticOverflow)` - `denominator` is 0
+pub fn div(value: u128, denominator: u128, rounding: Rounding) -> Result<u128, ProgramError> {
+    let quotient = value
+        .checked_div(denominator)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(match rounding {
+        Rounding::Up if !value.is_multiple_of(denominator) => quotient + 1,
+        _ => quotient,
+    })
+}
+
+/// Converts a `u128` intermediate into a `u64`.
+///
+/// # Returns
+/// * `Ok(u64)` - The converted value
+/// * `Err(ProgramError::ArithmeticOverflow)` - `value` does not fit in a `u64`
+#[inline(always)]
+pub fn to_u64(value: u128) -> Result<u64, ProgramError> {
+    u64::try_from(value).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{PROPERTY_CASES, Rng},
+    };
+
+    #[test]
+    fn div_rounds_in_requested_direction() {
+        assert_eq!(div(10, 3, Rounding::Down), Ok(3));
+        assert_eq!(div(10, 3, Rounding::Up), Ok(4));
+        assert_eq!(div(9, 3, Rounding::Up), Ok(3));
+        assert_eq!(div(0, 3, Rounding::Up), Ok(0));
+    }
+
+    #[test]
+    fn div_rejects_zero_denominator() {
+        assert_eq!(
+            div(1, 0, Rounding::Down),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            mul_div(1, 1, 0, Rounding::Up),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn mul_div_rounds_after_the_product() {
+        // 7 * 5 / 3 = 11.67: rounding the factors first would give 10 or 12.
+        assert_eq!(mul_div(7, 5, 3, Rounding::Down), Ok(11));
+        assert_eq!(mul_div(7, 5, 3, Rounding::Up), Ok(12));
+    }
+
+    #[test]
+    fn mul_div_rejects_overflowing_product() {
+        assert_eq!(
+            mul_div(u128::MAX, 2, 2, Rounding::Down),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn mul_div_u64_keeps_full_precision_intermediate() {
+        assert_eq!(
+            mul_div_u64(u64::MAX, u64::MAX, u64::MAX, Rounding::Down),
+            Ok(u64::MAX)
+        );
+        assert_eq!(
+            mul_div_u64(u64::MAX, 3, 4, Rounding::Up),
+            Ok(13_835_058_055_282_163_712)
+        );
+    }
+
+    #[test]
+    fn mul_div_u64_rejects_quotient_above_u64() {
+        assert_eq!(
+            mul_div_u64(u64::MAX, 2, 1, Rounding::Down),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn to_u64_truncation_is_an_error() {
+        assert_eq!(to_u64(u64::MAX as u128), Ok(u64::MAX));
+        assert_eq!(
+            to_u64(u64::MAX as u128 + 1),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn rounding_brackets_the_exact_quotient() {
+        let mut rng = Rng::new(1);
+        for _ in 0..PROPERTY_CASES {
+            let (value, numerator) = (rng.amount() as u128, rng.amount() as u128);
+            let denominator = rng.amount().max(1) as u128;
+            let down = mul_div(value, numerator, denominator, Rounding::Down).unwrap();
+            let up = mul_div(value, numerator, denominator, Rounding::Up).unwrap();
+            assert!(down * denominator <= value * numerator);
+            assert!(up * denominator >= value * numerator);
+            assert!(up - down <= 1);
+        }
+    }
+
+    /// Shares for `assets` at a price of `total_assets / total_shares`.
+    fn to_shares(
+        assets: u64,
+        total_assets: u64,
+        total_shares: u64,
+        rounding: Rounding,
+    ) -> Option<u64> {
+        mul_div_u64(assets, total_shares, total_assets, rounding).ok()
+    }
+
+    /// Assets for `shares` at a price of `total_assets / total_shares`.
+    fn to_assets(
+        shares: u64,
+        total_assets: u64,
+        total_shares: u64,
+        rounding: Rounding,
+    ) -> Option<u64> {
+        mul_div_u64(shares, total_assets, total_shares, rounding).ok()
+    }
+
+    #[test]
+    fn rounding_down_payouts_never_returns_more_than_was_paid_in() {
+        let mut rng = Rng::new(2);
+        for _ in 0..PROPERTY_CASES {
+            let (assets, shares) = (rng.amount().max(1), rng.amount().max(1));
+            let (x, s) = (rng.amount(), rng.amount());
+
+            if let Some(minted) = to_shares(x, assets, shares, Rounding::Down) {
+                let value = to_assets(minted, assets, shares, Rounding::Down);
+                assert!(value.is_some_and(|value| value <= x));
+            }
+            if let Some(paid) = to_assets(s, assets, shares, Rounding::Down) {
+                let bought = to_shares(paid, assets, shares, Rounding::Down);
+                assert!(bought.is_some_and(|bought| bought <= s));
+            }
+        }
+    }
+
+    #[test]
+    fn rounding_up_amounts_owed_never_charges_less_than_is_paid_out() {
+        let mut rng = Rng::new(3);
+        for _ in 0..PROPERTY_CASES {
+            let (assets, shares) = (rng.amount().max(1), rng.amount().max(1));
+            let (x, s) = (rng.amount(), rng.amount());
+
+            // Shares burned to withdraw `x` are worth at least `x`.
+            // (A value past `u64::MAX` is `None` and trivially covers the amount.)
+            if let Some(burned) = to_shares(x, assets, shares, Rounding::Up) {
+                let value = to_assets(burned, assets, shares, Rounding::Down);
+                assert!(value.is_none_or(|value| value >= x));
+            }
+            // Assets charged to mint `s` shares buy at least `s`.
+            if let Some(charged) = to_assets(s, assets, shares, Rounding::Up) {
+                let bought = to_shares(charged, assets, shares, Rounding::Down);
+                assert!(bought.is_none_or(|bought| bought >= s));
+            }
+        }
+    }
+}
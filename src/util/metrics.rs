@@ -0,0 +1,46 @@
+/// Runs `op` and, with the `metrics` feature on-chain, logs the compute units it consumed.
+///
+/// The log line is compact and grep-friendly: `beethoven:<label> cu=<units>`. Without the
+/// `metrics` feature (or off-chain) this is a plain call with no overhead.
+#[inline(always)]
+pub(crate) fn metered<R>(label: &str, op: impl FnOnce() -> R) -> R {
+    #[cfg(all(feature = "metrics", any(target_os = "solana", target_arch = "bpf")))]
+    {
+        // SAFETY: `sol_remaining_compute_units` takes no arguments and has no side effects.
+        let before = unsafe { pinocchio::syscalls::sol_remaining_compute_units() };
+        let result = op();
+        let after = unsafe { pinocchio::syscalls::sol_remaining_compute_units() };
+
+        crate::util::log::LogLine::new()
+            .push(b"beethoven:")
+            .push(label.as_bytes())
+            .push(b" cu=")
+            .push_u64(before.saturating_sub(after))
+            .log();
+        result
+    }
+
+    #[cfg(not(all(feature = "metrics", any(target_os = "solana", target_arch = "bpf"))))]
+    {
+        let _ = label;
+        op()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metered_returns_the_operation_result() {
+        let mut calls = 0;
+        assert_eq!(
+            metered("deposit", || {
+                calls += 1;
+                42
+            }),
+            42
+        );
+        assert_eq!(calls, 1);
+    }
+}
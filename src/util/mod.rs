@@ -0,0 +1,38 @@
+pub(crate) mod bytes;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub(crate) mod cpi;
+pub(crate) mod log;
+#[cfg(any(
+    feature = "drift",
+    feature = "jito",
+    feature = "jupiter",
+    feature = "kamino",
+    feature = "marinade",
+    feature = "solayer",
+    feature = "stake_pool"
+))]
+pub(crate) mod metrics;
+
+pub mod pda;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub use pda::*;
+
+pub mod seeds;
+pub use seeds::*;
+
+pub mod amount;
+pub use amount::*;
+
+pub mod math;
+pub use math::*;
+
+pub mod token;
+pub use token::*;
@@ -0,0 +1,87 @@
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+use pinocchio::{AccountView, Address, address::address_eq, error::ProgramError};
+
+/// Finds the canonical PDA and bump for `seeds` under `program_id`.
+///
+/// Host builds need the `curve25519` feature, which provides the backend the runtime's
+/// syscall stands in for on-chain.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Address) -> (Address, u8) {
+    Address::find_program_address(seeds, program_id)
+}
+
+/// Checks that `account` is the canonical PDA for `seeds` under `program_id`.
+///
+/// # Returns
+/// * `Ok(u8)` - The canonical bump
+/// * `Err(ProgramError::InvalidSeeds)` - Account address does not match the derived PDA
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub fn check_program_address(
+    account: &AccountView,
+    seeds: &[&[u8]],
+    program_id: &Address,
+) -> Result<u8, ProgramError> {
+    let (address, bump) = find_program_address(seeds, program_id);
+
+    if !address_eq(account.address(), &address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Derives the PDA for `seeds`, bump included, under `program_id`.
+///
+/// # Returns
+/// * `Ok(Address)` - The derived address
+/// * `Err(ProgramError::InvalidSeeds)` - The seeds derive an on-curve point or are too long
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+pub fn create_program_address(
+    seeds: &[&[u8]],
+    program_id: &Address,
+) -> Result<Address, ProgramError> {
+    Address::create_program_address(seeds, program_id).map_err(|_| ProgramError::InvalidSeeds)
+}
+
+#[cfg(all(test, feature = "curve25519"))]
+mod tests {
+    use {super::*, crate::test_utils::TestAccount};
+
+    const PROGRAM_ID: Address = Address::new_from_array([7; 32]);
+
+    #[test]
+    fn find_and_create_agree() {
+        let (address, bump) = find_program_address(&[b"vault"], &PROGRAM_ID);
+        assert_eq!(
+            create_program_address(&[b"vault", &[bump]], &PROGRAM_ID),
+            Ok(address)
+        );
+    }
+
+    #[test]
+    fn create_rejects_too_many_seeds() {
+        let seeds = [b"s".as_slice(); 17];
+        assert_eq!(
+            create_program_address(&seeds, &PROGRAM_ID),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn check_program_address_compares_the_account() {
+        let (address, bump) = find_program_address(&[b"vault"], &PROGRAM_ID);
+        let mut pda = TestAccount::empty(address);
+        assert_eq!(
+            check_program_address(&pda.view(), &[b"vault"], &PROGRAM_ID),
+            Ok(bump)
+        );
+
+        let mut other = TestAccount::empty(Address::new_from_array([1; 32]));
+        assert_eq!(
+            check_program_address(&other.view(), &[b"vault"], &PROGRAM_ID),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+/// Maximum number of seeds (bump included) accepted by the runtime for a PDA signer.
+pub const MAX_SEEDS: usize = 16;
+
+/// Builds the `&[Signer]` pinocchio expects from PDA seeds and a bump.
+///
+/// # Example
+/// ```ignore
+/// let seeds = SeedsBuilder::new(&[b"vault", authority.address().as_ref()], bump)?;
+///
+/// seeds.with_signer(|signers| beethoven::deposit_signed(accounts, amount, signers))?;
+/// ```
+pub struct SeedsBuilder<'a> {
+    seeds: [&'a [u8]; MAX_SEEDS],
+    len: usize,
+    bump: [u8; 1],
+}
+
+impl<'a> SeedsBuilder<'a> {
+    /// Creates a builder from the PDA seeds (without the bump) and its bump.
+    ///
+    /// # Returns
+    /// * `Ok(SeedsBuilder)` - Seeds fit within `MAX_SEEDS`
+    /// * `Err(ProgramError::MaxSeedLengthExceeded)` - More than `MAX_SEEDS - 1` seeds provided
+    pub fn new(seeds: &[&'a [u8]], bump: u8) -> Result<Self, ProgramError> {
+        if seeds.len() >= MAX_SEEDS {
+            return Err(ProgramError::MaxSeedLengthExceeded);
+        }
+
+        let mut builder = Self {
+            seeds: [&[]; MAX_SEEDS],
+            len: seeds.len(),
+            bump: [bump],
+        };
+        builder.seeds[..seeds.len()].copy_from_slice(seeds);
+
+        Ok(builder)
+    }
+
+    /// Appends a seed before the bump.
+    ///
+    /// # Returns
+    /// * `Ok(SeedsBuilder)` - Seed appended
+    /// * `Err(ProgramError::MaxSeedLengthExceeded)` - Builder already holds `MAX_SEEDS - 1` seeds
+    pub fn seed(mut self, seed: &'a [u8]) -> Result<Self, ProgramError> {
+        if self.len + 1 >= MAX_SEEDS {
+            return Err(ProgramError::MaxSeedLengthExceeded);
+        }
+
+        self.seeds[self.len] = seed;
+        self.len += 1;
+
+        Ok(self)
+    }
+
+    /// Returns the bump appended after the seeds.
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    /// Calls `f` with a single-signer slice built from the seeds followed by the bump.
+    ///
+    /// The signer borrows stack storage that only lives for the duration of the call, which
+    /// is exactly the lifetime `deposit_signed`-style functions need.
+    pub fn with_signer<R>(&self, f: impl FnOnce(&[Signer]) -> R) -> R {
+        let seeds: [Seed; MAX_SEEDS] = core::array::from_fn(|i| match i {
+            i if i < self.len => Seed::from(self.seeds[i]),
+            i if i == self.len => Seed::from(&self.bump),
+            _ => Seed::from(&[][..]),
+        });
+
+        let signer = Signer::from(&seeds[..=self.len]);
+        f(&[signer])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_seeds_before_the_bump() {
+        let seeds = SeedsBuilder::new(&[b"vault"], 254)
+            .and_then(|seeds| seeds.seed(b"authority"))
+            .unwrap();
+        assert_eq!(seeds.bump(), 254);
+        assert_eq!(seeds.seeds[..seeds.len], [&b"vault"[..], &b"authority"[..]]);
+        assert_eq!(seeds.with_signer(|signers| signers.len()), 1);
+    }
+
+    #[test]
+    fn leaves_room_for_the_bump() {
+        let fifteen = [&b"seed"[..]; MAX_SEEDS - 1];
+        let full = SeedsBuilder::new(&fifteen, 255).unwrap();
+        assert!(matches!(
+            full.seed(b"one too many"),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        ));
+        assert!(matches!(
+            SeedsBuilder::new(&[&b"seed"[..]; MAX_SEEDS], 255),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        ));
+    }
+}
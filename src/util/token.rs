@@ -0,0 +1,383 @@
+use {
+    crate::util::bytes::{TOKEN_ACCOUNT_AMOUNT_OFFSET, read_array, read_u64},
+    core::mem::MaybeUninit,
+    pinocchio::{
+        AccountView, Address, ProgramResult,
+        address::address_eq,
+        cpi::{Signer, invoke_signed},
+        error::ProgramError,
+        instruction::{InstructionAccount, InstructionView},
+    },
+};
+
+pub const TOKEN_PROGRAM_ID: Address = Address::new_from_array([
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237,
+    95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+]);
+pub const TOKEN_2022_PROGRAM_ID: Address = Address::new_from_array([
+    6, 221, 246, 225, 238, 117, 143, 222, 24, 66, 93, 188, 228, 108, 205, 218, 182, 26, 252, 77,
+    131, 185, 13, 39, 254, 189, 249, 40, 216, 161, 139, 252,
+]);
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Address = Address::new_from_array([
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218,
+    255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+]);
+pub const SYSTEM_PROGRAM_ID: Address = Address::new_from_array([0; 32]);
+const TRANSFER_CHECKED_TAG: u8 = 12;
+const CREATE_IDEMPOTENT_TAG: u8 = 1;
+
+const MINT_DECIMALS_OFFSET: usize = 44;
+const TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 72;
+const TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET: usize = 121;
+
+/// Accounts for pulling tokens out of a user-owned account the program was approved on.
+///
+/// The user keeps custody of `source` and only `approve`s `delegate` (usually a program PDA)
+/// for the amount to deposit. Beethoven moves the tokens into `destination`, the account the
+/// protocol deposit then spends from.
+pub struct DelegatedTransfer<'info> {
+    /// SPL Token or Token-2022 program
+    pub token_program: &'info AccountView,
+    /// User-owned source token account (writable)
+    pub source: &'info AccountView,
+    /// Mint of the transferred token
+    pub mint: &'info AccountView,
+    /// Token account spent by the deposit, owned by `delegate` (writable)
+    pub destination: &'info AccountView,
+    /// Approved delegate of `source` (signer, usually via PDA seeds)
+    pub delegate: &'info AccountView,
+}
+
+impl DelegatedTransfer<'_> {
+    /// Returns the amount `delegate` is currently approved to move out of `source`.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Delegated amount, `0` when `source` has another or no delegate
+    /// * `Err(ProgramError::IncorrectProgramId)` - `token_program` is not a token program
+    /// * `Err(ProgramError::IllegalOwner)` - `source` is not owned by `token_program`
+    pub fn delegated_amount(&self) -> Result<u64, ProgramError> {
+        check_token_program(self.token_program)?;
+        if !self.source.owned_by(self.token_program.address()) {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let data = self.source.try_borrow()?;
+        // `COption<Pubkey>`: 4-byte tag (1 = Some) followed by the key
+        let tag = read_array::<4>(&data, TOKEN_ACCOUNT_DELEGATE_OFFSET)?;
+        let delegate = read_array::<32>(&data, TOKEN_ACCOUNT_DELEGATE_OFFSET + 4)?;
+        if tag != [1, 0, 0, 0]
+            || !address_eq(&Address::new_from_array(delegate), self.delegate.address())
+        {
+            return Ok(0);
+        }
+        read_u64(&data, TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET)
+    }
+
+    /// Moves `amount` from `source` into `destination` with `transfer_checked`, signing as
+    /// `delegate`.
+    ///
+    /// # Arguments
+    /// * `amount` - Amount to transfer, in base units of `mint`
+    /// * `signer_seeds` - PDA seeds of `delegate`, empty when it signs the transaction itself
+    ///
+    /// # Returns
+    /// * `Ok(())` - Tokens transferred
+    /// * `Err(ProgramError::InsufficientFunds)` - Approved amount is below `amount`
+    /// * `Err(ProgramError)` - Invalid accounts or CPI failure
+    pub fn transfer_signed(&self, amount: u64, signer_seeds: &[Signer]) -> ProgramResult {
+        if self.delegated_amount()? < amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let decimals = {
+            let data = self.mint.try_borrow()?;
+            read_array::<1>(&data, MINT_DECIMALS_OFFSET)?[0]
+        };
+
+        let accounts = [
+            InstructionAccount::writable(self.source.address()),
+            InstructionAccount::readonly(self.mint.address()),
+            InstructionAccount::writable(self.destination.address()),
+            InstructionAccount::readonly_signer(self.delegate.address()),
+        ];
+
+        let account_infos = [self.source, self.mint, self.destination, self.delegate];
+
+        // Build instruction data: tag (1 byte) + amount (8 bytes) + decimals (1 byte)
+        let mut instruction_data = MaybeUninit::<[u8; 10]>::uninit();
+        unsafe {
+            let ptr = instruction_data.as_mut_ptr() as *mut u8;
+            *ptr = TRANSFER_CHECKED_TAG;
+            core::ptr::copy_nonoverlapping(amount.to_le_bytes().as_ptr(), ptr.add(1), 8);
+            *ptr.add(9) = decimals;
+        }
+
+        let transfer_ix = InstructionView {
+            program_id: self.token_program.address(),
+            accounts: &accounts,
+            data: unsafe {
+                core::slice::from_raw_parts(instruction_data.as_ptr() as *const u8, 10)
+            },
+        };
+
+        invoke_signed(&transfer_ix, &account_infos, signer_seeds)
+    }
+
+    /// Returns the balance of `destination` (e.g. to deposit everything pulled so far).
+    pub fn destination_amount(&self) -> Result<u64, ProgramError> {
+        read_u64(&self.destination.try_borrow()?, TOKEN_ACCOUNT_AMOUNT_OFFSET)
+    }
+}
+
+/// Accounts for creating an associated token account if it does not exist yet.
+///
+/// Lets first-time deposits create the user's collateral or share account in the same
+/// transaction instead of requiring a separate setup transaction.
+pub struct AssociatedTokenAccount<'info> {
+    /// Account paying the rent (mutable, signer)
+    pub payer: &'info AccountView,
+    /// Associated token account to create (writable)
+    pub account: &'info AccountView,
+    /// Wallet owning the associated token account
+    pub owner: &'info AccountView,
+    /// Mint of the associated token account
+    pub mint: &'info AccountView,
+    /// System program
+    pub system_program: &'info AccountView,
+    /// SPL Token or Token-2022 program owning `mint`
+    pub token_program: &'info AccountView,
+    /// Associated token program
+    pub associated_token_program: &'info AccountView,
+}
+
+impl AssociatedTokenAccount<'_> {
+    /// Creates `account` with the associated token program's `CreateIdempotent` instruction.
+    ///
+    /// # Arguments
+    /// * `signer_seeds` - PDA seeds of `payer`, empty when it signs the transaction itself
+    ///
+    /// # Returns
+    /// * `Ok(())` - The account exists, either created now or already initialized
+    /// * `Err(ProgramError::IncorrectProgramId)` - `token_program` or `associated_token_program`
+    ///   is not the expected program
+    /// * `Err(ProgramError)` - CPI failure (e.g. `account` is not the owner's ATA for `mint`)
+    ///
+    /// # Notes
+    /// * Skips the CPI when `account` is already owned by `token_program`.
+    pub fn create_idempotent_signed(&self, signer_seeds: &[Signer]) -> ProgramResult {
+        check_token_program(self.token_program)?;
+        if !address_eq(
+            self.associated_token_program.address(),
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        ) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if self.account.owned_by(self.token_program.address()) {
+            return Ok(());
+        }
+
+        let accounts = [
+            InstructionAccount::writable_signer(self.payer.address()),
+            InstructionAccount::writable(self.account.address()),
+            InstructionAccount::readonly(self.owner.address()),
+            InstructionAccount::readonly(self.mint.address()),
+            InstructionAccount::readonly(self.system_program.address()),
+            InstructionAccount::readonly(self.token_program.address()),
+        ];
+
+        let account_infos = [
+            self.payer,
+            self.account,
+            self.owner,
+            self.mint,
+            self.system_program,
+            self.token_program,
+        ];
+
+        let create_ix = InstructionView {
+            program_id: self.associated_token_program.address(),
+            accounts: &accounts,
+            data: &[CREATE_IDEMPOTENT_TAG],
+        };
+
+        invoke_signed(&create_ix, &account_infos, signer_seeds)
+    }
+}
+
+fn check_token_program(token_program: &AccountView) -> ProgramResult {
+    let program = token_program.address();
+    if !address_eq(program, &TOKEN_PROGRAM_ID) && !address_eq(program, &TOKEN_2022_PROGRAM_ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{TestAccount, address},
+        std::{vec, vec::Vec},
+    };
+
+    /// SPL token account holding `amount`, with `delegate` approved for `delegated_amount`.
+    fn token_account_data(
+        amount: u64,
+        delegate: Option<&Address>,
+        delegated_amount: u64,
+    ) -> Vec<u8> {
+        let mut data = vec![0u8; 165];
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&amount.to_le_bytes());
+        if let Some(delegate) = delegate {
+            data[TOKEN_ACCOUNT_DELEGATE_OFFSET] = 1;
+            data[TOKEN_ACCOUNT_DELEGATE_OFFSET + 4..TOKEN_ACCOUNT_DELEGATE_OFFSET + 36]
+                .copy_from_slice(delegate.as_ref());
+        }
+        data[TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET..TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&delegated_amount.to_le_bytes());
+        data
+    }
+
+    /// `[token_program, source, mint, destination, delegate]` with `source` approved to
+    /// `delegate` (address 5) for 500.
+    fn transfer_accounts(token_program: Address, source_delegate: u8) -> [TestAccount; 5] {
+        let mut mint = vec![0u8; 82];
+        mint[MINT_DECIMALS_OFFSET] = 6;
+        [
+            TestAccount::empty(token_program),
+            TestAccount::new(
+                address(2),
+                TOKEN_PROGRAM_ID,
+                &token_account_data(1_000, Some(&address(source_delegate)), 500),
+            ),
+            TestAccount::new(address(3), TOKEN_PROGRAM_ID, &mint),
+            TestAccount::new(
+                address(4),
+                TOKEN_PROGRAM_ID,
+                &token_account_data(42, None, 0),
+            ),
+            TestAccount::empty(address(5)).signer(),
+        ]
+    }
+
+    fn with_transfer<R>(
+        accounts: &mut [TestAccount; 5],
+        f: impl FnOnce(&DelegatedTransfer) -> R,
+    ) -> R {
+        let [token_program, source, mint, destination, delegate] =
+            accounts.each_mut().map(TestAccount::view);
+        f(&DelegatedTransfer {
+            token_program: &token_program,
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            delegate: &delegate,
+        })
+    }
+
+    #[test]
+    fn reads_the_amount_approved_to_the_delegate() {
+        let mut accounts = transfer_accounts(TOKEN_PROGRAM_ID, 5);
+        with_transfer(&mut accounts, |transfer| {
+            assert_eq!(transfer.delegated_amount(), Ok(500));
+            assert_eq!(transfer.destination_amount(), Ok(42));
+        });
+
+        let mut accounts = transfer_accounts(TOKEN_PROGRAM_ID, 9);
+        with_transfer(&mut accounts, |transfer| {
+            assert_eq!(transfer.delegated_amount(), Ok(0));
+        });
+    }
+
+    #[test]
+    fn transfers_at_most_the_approved_amount() {
+        let mut accounts = transfer_accounts(TOKEN_PROGRAM_ID, 5);
+        with_transfer(&mut accounts, |transfer| {
+            assert_eq!(transfer.transfer_signed(500, &[]), Ok(()));
+            assert_eq!(
+                transfer.transfer_signed(501, &[]),
+                Err(ProgramError::InsufficientFunds)
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_foreign_token_programs() {
+        let mut accounts = transfer_accounts(address(1), 5);
+        with_transfer(&mut accounts, |transfer| {
+            assert_eq!(
+                transfer.delegated_amount(),
+                Err(ProgramError::IncorrectProgramId)
+            );
+        });
+
+        // A Token-2022 program cannot vouch for an SPL Token account.
+        let mut accounts = transfer_accounts(TOKEN_2022_PROGRAM_ID, 5);
+        with_transfer(&mut accounts, |transfer| {
+            assert_eq!(transfer.delegated_amount(), Err(ProgramError::IllegalOwner));
+        });
+    }
+
+    /// `[payer, account, owner, mint, system_program, token_program, associated_token_program]`
+    fn ata_accounts(account_owner: Address, associated_token_program: Address) -> [TestAccount; 7] {
+        [
+            TestAccount::empty(address(1)).signer(),
+            TestAccount::new(address(2), account_owner, &[]),
+            TestAccount::empty(address(3)),
+            TestAccount::new(address(4), TOKEN_PROGRAM_ID, &[0; 82]),
+            TestAccount::empty(SYSTEM_PROGRAM_ID),
+            TestAccount::empty(TOKEN_PROGRAM_ID),
+            TestAccount::empty(associated_token_program),
+        ]
+    }
+
+    fn create(accounts: &mut [TestAccount; 7]) -> ProgramResult {
+        let [
+            payer,
+            account,
+            owner,
+            mint,
+            system_program,
+            token_program,
+            associated_token_program,
+        ] = accounts.each_mut().map(TestAccount::view);
+        AssociatedTokenAccount {
+            payer: &payer,
+            account: &account,
+            owner: &owner,
+            mint: &mint,
+            system_program: &system_program,
+            token_program: &token_program,
+            associated_token_program: &associated_token_program,
+        }
+        .create_idempotent_signed(&[])
+    }
+
+    #[test]
+    fn creates_missing_and_skips_existing_accounts() {
+        assert_eq!(
+            create(&mut ata_accounts(
+                SYSTEM_PROGRAM_ID,
+                ASSOCIATED_TOKEN_PROGRAM_ID
+            )),
+            Ok(())
+        );
+        assert_eq!(
+            create(&mut ata_accounts(
+                TOKEN_PROGRAM_ID,
+                ASSOCIATED_TOKEN_PROGRAM_ID
+            )),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_a_foreign_associated_token_program() {
+        assert_eq!(
+            create(&mut ata_accounts(SYSTEM_PROGRAM_ID, address(9))),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+}